@@ -1,2 +1,88 @@
+pub mod acyclic;
+pub mod alias;
+pub mod antichain;
+pub mod astar;
+pub mod bellman_ford;
+pub mod binary;
+pub mod bipartite;
+pub mod builder;
+pub mod call_graph;
+pub mod centrality;
+pub mod checks;
+pub mod coarsen;
+pub mod component_par;
+pub mod condensation;
+pub mod connectivity;
 pub mod core;
+pub mod critical_path;
+pub mod cycles;
+pub mod degree;
+pub mod depfile;
+pub mod diff;
+pub mod dijkstra;
+pub mod dot;
+pub mod dyn_node;
+pub mod edge_coloring;
+pub mod edge_label;
+pub mod edge_list;
+pub mod edge_log;
+pub mod edit_distance;
+pub mod error;
+pub mod executor;
+pub mod feedback;
+pub mod flow;
+pub mod floyd_warshall;
+pub mod folded;
+mod format;
+pub mod frozen;
+pub mod gexf;
+pub mod gomory_hu;
+#[allow(clippy::module_inception)]
 pub mod graph;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+pub mod handle;
+pub mod iddfs;
+pub mod json;
+pub mod latency;
+pub mod layered;
+pub mod layout;
+pub mod link_prediction;
+pub mod longest_path;
+mod macros;
+pub mod matrix;
+pub mod merge;
+pub mod multigraph;
+pub mod mutual;
+pub mod node_data;
+pub mod ordering;
+pub mod parity;
+pub mod path;
+pub mod path_cover;
+pub mod provenance;
+pub mod random_walk;
+pub mod reachability;
+pub mod reverse;
+pub mod rewire;
+pub mod rng;
+pub mod robustness;
+pub mod rules;
+pub mod scc;
+pub mod schedule;
+pub mod self_loop;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod shortest_path;
+pub mod signed;
+#[cfg(feature = "linalg")]
+pub mod spectral;
+pub mod stats;
+pub mod subgraph;
+pub mod summary;
+pub mod temporal;
+pub mod topological;
+pub mod transitive;
+pub mod traversal;
+mod union_find;
+pub mod weight;
+pub mod wl;
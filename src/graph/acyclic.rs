@@ -0,0 +1,257 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+};
+
+/// エッジの追加に合わせて更新していくトポロジカル順 (Pearce–Kelly 法)
+///
+/// エッジ from → to が順序に反するときだけ，順位が to 以上 from 以下のノードのうち
+/// 影響を受けるものを並べ替える．グラフ全体をたどり直す必要はない
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TopoOrder {
+    // ノードの順位
+    pos: HashMap<NodeID, usize>,
+    // 順位ごとのノード
+    order: Vec<NodeID>,
+}
+
+impl TopoOrder {
+    pub(crate) fn new(order: Vec<NodeID>) -> Self {
+        let pos = order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        Self { pos, order }
+    }
+
+    pub(crate) fn order(&self) -> &[NodeID] {
+        &self.order
+    }
+
+    // 新しいノードは最後に置く (まだエッジが無いのでどこに置いてもよい)
+    pub(crate) fn push(&mut self, id: NodeID) {
+        self.pos.insert(id, self.order.len());
+        self.order.push(id);
+    }
+
+    pub(crate) fn remove(&mut self, id: NodeID) {
+        let Some(p) = self.pos.remove(&id) else {
+            return;
+        };
+        self.order.remove(p);
+        for (i, &u) in self.order.iter().enumerate().skip(p) {
+            self.pos.insert(u, i);
+        }
+    }
+
+    // エッジ from → to を追加したときの順序に直す．サイクルができる場合は
+    // 順序を変えずにサイクル (from → to → … → from) を返す
+    pub(crate) fn insert_edge(
+        &mut self,
+        core: &GraphCore,
+        from: NodeID,
+        to: NodeID,
+    ) -> Result<(), Vec<NodeID>> {
+        if from == to {
+            return Err(vec![from, from]);
+        }
+        let (Some(&ub), Some(&lb)) = (self.pos.get(&from), self.pos.get(&to)) else {
+            return Ok(());
+        };
+        if ub < lb {
+            return Ok(());
+        }
+
+        // to から順位が from より前のノードを前向きにたどる．from に着いたらサイクル
+        let mut forward = Vec::new();
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut seen = HashSet::from([to]);
+        let mut stack = vec![to];
+        while let Some(id) = stack.pop() {
+            forward.push(id);
+            for &c in &core.nodes_dict[&id].children {
+                if c == from {
+                    let mut path = vec![id];
+                    let mut cur = id;
+                    while cur != to {
+                        cur = parent[&cur];
+                        path.push(cur);
+                    }
+                    path.push(from);
+                    path.reverse();
+                    path.push(from);
+                    return Err(path);
+                }
+                if self.pos.get(&c).is_some_and(|&p| p < ub) && seen.insert(c) {
+                    parent.insert(c, id);
+                    stack.push(c);
+                }
+            }
+        }
+
+        // from から順位が to より後のノードを後ろ向きにたどる
+        let mut backward = Vec::new();
+        let mut seen = HashSet::from([from]);
+        let mut stack = vec![from];
+        while let Some(id) = stack.pop() {
+            backward.push(id);
+            for &p in &core.nodes_dict[&id].parents {
+                if self.pos.get(&p).is_some_and(|&x| x > lb) && seen.insert(p) {
+                    stack.push(p);
+                }
+            }
+        }
+
+        // 両方が使っていた順位に，後ろ向きに見つけたノード，前向きに見つけたノードの順で詰め直す
+        backward.sort_unstable_by_key(|id| self.pos[id]);
+        forward.sort_unstable_by_key(|id| self.pos[id]);
+        let mut slots: Vec<usize> = backward
+            .iter()
+            .chain(&forward)
+            .map(|id| self.pos[id])
+            .collect();
+        slots.sort_unstable();
+        for (&id, slot) in backward.iter().chain(&forward).zip(slots) {
+            self.pos.insert(id, slot);
+            self.order[slot] = id;
+        }
+        Ok(())
+    }
+}
+
+impl GraphCore {
+    // トポロジカル順を作り，以後はノードとエッジの追加に合わせて更新する．サイクルがあればそのサイクル
+    pub fn maintain_topological_order(&mut self) -> Result<(), Vec<NodeID>> {
+        if self.topo.is_none() {
+            match self.topological_order() {
+                Some(order) => self.topo = Some(TopoOrder::new(order)),
+                None => return Err(self.detect_cycle().unwrap()),
+            }
+        }
+        Ok(())
+    }
+
+    // サイクルができないときだけエッジを追加する．すでにエッジがあれば true (add_edge と同じ)．
+    // サイクルができる場合は追加せずにそのサイクル (from → to → … → from) を返す．
+    // グラフがすでにサイクルを持っていればそのサイクルを返す
+    pub fn add_edge_acyclic(&mut self, from: NodeID, to: NodeID) -> Result<bool, Vec<NodeID>> {
+        self.prepare_acyclic_edge(from, to)?;
+        Ok(self.add_edge(from, to).unwrap())
+    }
+
+    // from → to を足してもサイクルができないことを確かめ，トポロジカル順を先に直しておく
+    // (そうすれば add_edge での更新はすぐ終わる)．エッジはまだ追加しない
+    fn prepare_acyclic_edge(&mut self, from: NodeID, to: NodeID) -> Result<(), Vec<NodeID>> {
+        self.maintain_topological_order()?;
+        if self.nodes_dict[&from].children.contains(&to) {
+            return Ok(());
+        }
+
+        let mut topo = self.topo.take().unwrap();
+        let ret = topo.insert_edge(self, from, to);
+        self.topo = Some(topo);
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 以後のノードとエッジの追加に合わせてトポロジカル順を更新していく．
+    // 逆向きのエッジを追加したときは影響を受ける範囲だけを並べ替えるので，
+    // topological_sort を追加のたびに呼んでもグラフ全体を調べ直さない．
+    // サイクルができた時点で更新をやめる (もう一度呼べば作り直す)．
+    // すでにサイクルがあれば CycleDetected を返す
+    pub fn maintain_topological_order(&mut self) -> Result<(), GraphError> {
+        self.core
+            .maintain_topological_order()
+            .map_err(|cycle| GraphError::CycleDetected {
+                cycle: self.handles(&cycle),
+            })
+    }
+
+    // サイクルができないときだけエッジを追加する (トポロジカル順を保ちながら追加していくので，
+    // 毎回グラフ全体を調べ直すことはない)．すでにエッジがあれば true を返す (多重グラフなら本数が増える)．
+    // サイクルができる場合はグラフを変更せずに CycleDetected を返す
+    pub fn add_edge_acyclic<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = *self
+            .node_id(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let to_id = *self
+            .node_id(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+        self.check_self_loop(from_id, to_id)?;
+
+        self.core
+            .prepare_acyclic_edge(from_id, to_id)
+            .map_err(|cycle| GraphError::CycleDetected {
+                cycle: self.handles(&cycle),
+            })?;
+        self.add_edge_by_id(from_id, to_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_add_edge_acyclic() {
+        let mut g = Graph::new();
+        for u in ["E", "D", "C", "B", "A"] {
+            let _ = g.add_node(u);
+        }
+        // ノードの追加順と逆向きのエッジなので順序の並べ替えが起こる
+        for (a, b) in [("A", "B"), ("C", "D"), ("B", "C"), ("D", "E"), ("E", "A")] {
+            let ret = g.add_edge_acyclic(&a, &b);
+            if a == "E" {
+                assert_eq!(
+                    ret,
+                    Err(GraphError::CycleDetected {
+                        cycle: [0, 4, 3, 2, 1, 0].map(|i| NodeHandle::new(i, 0)).to_vec()
+                    })
+                );
+            } else {
+                assert_eq!(ret, Ok(false));
+            }
+        }
+        assert!(!g.contains_edge(&"E", &"A"));
+        assert_eq!(g.add_edge_acyclic(&"A", &"B"), Ok(true));
+        assert_eq!(g.add_edge_acyclic(&"A", &"E"), Ok(false));
+        assert!(g.add_edge_acyclic(&"C", &"C").is_err());
+
+        let order = g.core.topo.as_ref().unwrap().order().to_vec();
+        let rank = |u: &str| order.iter().position(|&id| id == g.id_dict[u]).unwrap();
+        for (a, b) in [("A", "B"), ("B", "C"), ("C", "D"), ("D", "E"), ("A", "E")] {
+            assert!(rank(a) < rank(b));
+        }
+
+        // 通常の add_edge でサイクルを作った後はそのサイクルを返す
+        let _ = g.add_edge(&"E", &"C");
+        assert!(matches!(
+            g.add_edge_acyclic(&"A", &"D"),
+            Err(GraphError::CycleDetected { cycle }) if cycle.len() == 4
+        ));
+    }
+
+    #[test]
+    fn test_add_edge_acyclic_multigraph() {
+        let mut g = Graph::new_multigraph();
+        for u in ["A", "B"] {
+            let _ = g.add_node(u);
+        }
+        assert_eq!(g.add_edge_acyclic(&"A", &"B"), Ok(false));
+        assert_eq!(g.add_edge_acyclic(&"A", &"B"), Ok(true));
+        assert_eq!(g.edge_multiplicity(&"A", &"B"), 2);
+        assert_eq!(g.in_degree(&"B"), Some(2));
+        assert!(g.add_edge_acyclic(&"B", &"A").is_err());
+        assert_eq!(g.edge_count(), 2);
+    }
+}
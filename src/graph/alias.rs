@@ -0,0 +1,98 @@
+use std::{borrow::Borrow, hash::Hash};
+
+use super::{error::GraphError, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // `alias` を `canonical` と同じノードを指す別名として登録する ("crate::foo" と "foo.rs" など)．
+    // 以後 add_edge や問い合わせに別名を渡すと実体のノードとして扱う．`canonical` は別名でもよい．
+    // `alias` が既にノードか別のノードの別名なら NodeAlreadyExists
+    pub fn alias<Q>(&mut self, canonical: &Q, alias: &Q) -> Result<(), GraphError>
+    where
+        T: Borrow<Q>,
+        Q: ToOwned<Owned = T> + Hash + Eq + ?Sized,
+    {
+        let id = *self
+            .node_id(canonical)
+            .ok_or(GraphError::NodeNotFound { arg: "canonical" })?;
+        if let Some(&node) = self.node_id(alias) {
+            if self.aliases.contains_key(alias) && node == id {
+                return Ok(());
+            }
+            return Err(GraphError::NodeAlreadyExists {
+                node: self.handle(node),
+            });
+        }
+
+        self.aliases.insert(alias.to_owned(), id);
+        Ok(())
+    }
+
+    // 別名を実体のノードに解決する．ノード自身ならそのまま返し，どちらでもなければ None
+    pub fn canonical<Q>(&self, u: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some((k, _)) = self.id_dict.get_key_value(u) {
+            return Some(k);
+        }
+        let id = self.aliases.get(u)?;
+        self.id_dict.iter().find(|(_, v)| *v == id).map(|(k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_alias() {
+        let mut g = Graph::new();
+        for u in ["foo.rs", "bar.rs"] {
+            let _ = g.add_node(u.to_string());
+        }
+        assert_eq!(g.alias("foo.rs", "crate::foo"), Ok(()));
+        assert_eq!(g.alias("crate::foo", "foo"), Ok(()));
+        assert_eq!(g.alias("foo.rs", "foo"), Ok(()));
+
+        // 別名で張ったエッジは実体のノードのエッジになる
+        let _ = g.add_edge("bar.rs", "crate::foo");
+        let _ = g.add_weighted_edge("foo", "bar.rs", 2.0);
+        assert!(g.contains_edge("bar.rs", "foo.rs"));
+        assert_eq!(g.edge_weight("foo.rs", "bar.rs"), Some(2.0));
+        assert!(g.contains_node("crate::foo"));
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.nodes().count(), 2);
+        assert_eq!(g.canonical("foo").map(|u| u.as_str()), Some("foo.rs"));
+        assert_eq!(g.canonical("bar.rs").map(|u| u.as_str()), Some("bar.rs"));
+        assert_eq!(g.canonical("baz"), None);
+
+        let p = g
+            .shortest_path(&"crate::foo".to_string(), &"bar.rs".to_string())
+            .unwrap();
+        assert_eq!(p.nodes(), &[&"foo.rs", &"bar.rs"]);
+
+        assert_eq!(
+            g.add_node("crate::foo".to_string()),
+            Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(0, 0)
+            })
+        );
+        assert_eq!(
+            g.alias("bar.rs", "foo"),
+            Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(0, 0)
+            })
+        );
+        assert_eq!(
+            g.alias("foo.rs", "bar.rs"),
+            Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(1, 0)
+            })
+        );
+        assert_eq!(
+            g.alias("baz", "qux"),
+            Err(GraphError::NodeNotFound { arg: "canonical" })
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    path_cover::{bipartite_matching, chains_from_matching},
+};
+
+impl GraphCore {
+    // 各ノードから到達できるノード (自身は含まない，ID 順)
+    fn reachable_sets(&self, ids: &[NodeID]) -> HashMap<NodeID, Vec<NodeID>> {
+        ids.iter()
+            .map(|&id| {
+                let mut reach = Vec::new();
+                self.traverse(id, |x| {
+                    if x != id {
+                        reach.push(x);
+                    }
+                });
+                reach.sort_unstable();
+                (id, reach)
+            })
+            .collect()
+    }
+
+    fn sorted_ids(&self) -> Vec<NodeID> {
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    // DAG を最小本数の鎖 (互いに到達可能なノードの列) に分ける (Dilworth)．
+    // 推移閉包の上での最小パス被覆なので，鎖の隣り合うノードは直接のエッジでなくてもよい
+    pub fn chain_decomposition(&self) -> Vec<Vec<NodeID>> {
+        let ids = self.sorted_ids();
+        let reach = self.reachable_sets(&ids);
+
+        let (next, prev) = bipartite_matching(&ids, |x| reach[&x].clone());
+        chains_from_matching(&ids, &next, &prev)
+    }
+
+    // DAG の最大反鎖 (互いに到達できないノードの最大集合，ID 順)．
+    // 推移閉包の二部グラフの最大マッチングから König の定理で最小頂点被覆を作り，その補集合を取る
+    pub fn maximum_antichain(&self) -> Vec<NodeID> {
+        let ids = self.sorted_ids();
+        let reach = self.reachable_sets(&ids);
+        let (next, prev) = bipartite_matching(&ids, |x| reach[&x].clone());
+
+        // マッチしていない左のノードから交互路でたどれるノード
+        let mut left: HashSet<NodeID> = ids
+            .iter()
+            .copied()
+            .filter(|id| !next.contains_key(id))
+            .collect();
+        let mut right: HashSet<NodeID> = HashSet::new();
+        let mut queue: VecDeque<NodeID> = left.iter().copied().collect();
+        while let Some(x) = queue.pop_front() {
+            for &v in &reach[&x] {
+                if !right.insert(v) {
+                    continue;
+                }
+                if let Some(&y) = prev.get(&v) {
+                    if left.insert(y) {
+                        queue.push_back(y);
+                    }
+                }
+            }
+        }
+
+        // 頂点被覆は (左 \ left) ∪ right なので，どちら側も被覆に入らないノードが反鎖になる
+        ids.into_iter()
+            .filter(|id| left.contains(id) && !right.contains(id))
+            .collect()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 互いに依存しないノードの最大集合 (= 同時に実行できるタスクの最大数)．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn maximum_antichain(&self) -> Result<Vec<&T>, Vec<&T>> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(cycle);
+        }
+
+        Ok(self.nodes_from_ids(&self.core.maximum_antichain()))
+    }
+
+    // 最小本数の鎖への分解．鎖の本数は最大反鎖の大きさと等しい．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn chain_decomposition(&self) -> Result<Vec<Vec<&T>>, Vec<&T>> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(cycle);
+        }
+
+        Ok(self
+            .core
+            .chain_decomposition()
+            .iter()
+            .map(|chain| self.nodes_from_ids(chain))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<&'static str> {
+        // A → B → D,  A → C → D,  A → E,  D → F
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("A", "B"),
+            ("A", "C"),
+            ("B", "D"),
+            ("C", "D"),
+            ("A", "E"),
+            ("D", "F"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+        g
+    }
+
+    #[test]
+    fn test_maximum_antichain() {
+        let g = sample();
+        let antichain = g.maximum_antichain().unwrap();
+        assert_eq!(antichain.len(), 3);
+        for &a in &antichain {
+            for &b in &antichain {
+                assert!(a == b || !g.is_reachable(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_decomposition() {
+        let g = sample();
+        let chains = g.chain_decomposition().unwrap();
+        assert_eq!(chains.len(), 3);
+
+        let mut all: Vec<&str> = chains.iter().flatten().map(|u| **u).collect();
+        all.sort_unstable();
+        assert_eq!(all, vec!["A", "B", "C", "D", "E", "F"]);
+        for chain in &chains {
+            for pair in chain.windows(2) {
+                assert!(g.is_reachable(pair[0], pair[1]));
+            }
+        }
+
+        let mut cyclic = sample();
+        let _ = cyclic.add_edge(&"F", &"A");
+        assert!(cyclic.chain_decomposition().is_err());
+        assert!(cyclic.maximum_antichain().is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    dijkstra::{path_to, unreached_overflow, MinWeighted},
+    error::GraphError,
+    graph::Graph,
+    path::Path,
+    weight::Weight,
+};
+
+impl GraphCore {
+    // A* 探索．`heuristic` は目的地までのコストの下界 (許容的) であること
+    pub fn astar<F, H>(
+        &self,
+        start: NodeID,
+        goal: NodeID,
+        weight: F,
+        heuristic: H,
+    ) -> Option<Vec<NodeID>>
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+        H: Fn(NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        let found = self.astar_by(start, goal, weight, heuristic).unwrap();
+        found.map(|(path, _)| path)
+    }
+
+    // 重みの型を選べる A* 探索．パスとその長さを返す．
+    // コストを足して桁あふれした候補は飛ばし，そのせいで `goal` に届かなかったかもしれなければ
+    // 桁あふれしたエッジをエラーとして返す
+    #[allow(clippy::type_complexity)]
+    pub fn astar_by<W, F, H>(
+        &self,
+        start: NodeID,
+        goal: NodeID,
+        weight: F,
+        heuristic: H,
+    ) -> Result<Option<(Vec<NodeID>, W)>, (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+        H: Fn(NodeID) -> W,
+    {
+        if !self.nodes_dict.contains_key(&start) {
+            return Ok(None);
+        }
+
+        let mut g_score: HashMap<NodeID, W> = HashMap::from([(start, W::zero())]);
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut overflowed: HashMap<NodeID, (NodeID, NodeID)> = HashMap::new();
+        let mut heap = BinaryHeap::from([MinWeighted(heuristic(start), start)]);
+
+        while let Some(MinWeighted(f, node)) = heap.pop() {
+            if node == goal {
+                return Ok(Some((path_to(&parent, start, goal), g_score[&goal])));
+            }
+            let g = g_score[&node];
+            if g.checked_add(heuristic(node))
+                .is_some_and(|h| f.cmp_weight(&h) == Ordering::Greater)
+            {
+                continue; // 古いエントリ
+            }
+
+            for child in self.children_of(node) {
+                // heuristic は実際のコストを超えないので，ng + h が桁あふれすれば
+                // child を通る goal へのコストも表せない
+                let Some((ng, nf)) = g
+                    .checked_add(weight(node, child))
+                    .and_then(|ng| Some((ng, ng.checked_add(heuristic(child))?)))
+                else {
+                    overflowed.entry(child).or_insert((node, child));
+                    continue;
+                };
+                if g_score
+                    .get(&child)
+                    .is_none_or(|old| ng.cmp_weight(old) == Ordering::Less)
+                {
+                    g_score.insert(child, ng);
+                    parent.insert(child, node);
+                    heap.push(MinWeighted(nf, child));
+                }
+            }
+        }
+
+        match unreached_overflow(&overflowed, |_| false) {
+            Some(edge) => Err(edge),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // ヒューリスティック付きの最短経路探索．`heuristic(u)` は u から `to` までのコストを超えないこと
+    pub fn astar<Q, H>(&self, from: &Q, to: &Q, heuristic: H) -> Option<Path<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        H: Fn(&T) -> f64,
+    {
+        let from_id = *self.node_id(from)?;
+        let to_id = *self.node_id(to)?;
+        let nodes = self.nodes_by_id();
+
+        let ids = self.core.astar(
+            from_id,
+            to_id,
+            |a, b| self.weight_of(a, b),
+            |id| heuristic(nodes[&id]),
+        )?;
+        Some(self.path_from_ids(&ids))
+    }
+
+    // 重みを `weight` で与える A* 探索 (u32 や Duration などの重みも使える)．
+    // `heuristic(u)` は u から `to` までのコストを超えないこと．
+    // 到達できなければ Ok(None)，コストが桁あふれしたらエラー
+    pub fn astar_by<Q, W, F, H>(
+        &self,
+        from: &Q,
+        to: &Q,
+        weight: F,
+        heuristic: H,
+    ) -> Result<Option<Path<'_, T, W>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+        H: Fn(&T) -> W,
+    {
+        let &from_id = self
+            .node_id(from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let &to_id = self
+            .node_id(to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+        let nodes = self.nodes_by_id();
+
+        let found = self
+            .core
+            .astar_by(
+                from_id,
+                to_id,
+                |a, b| weight(nodes[&a], nodes[&b]),
+                |id| heuristic(nodes[&id]),
+            )
+            .map_err(|(a, b)| GraphError::WeightOverflow {
+                from: self.handle(a),
+                to: self.handle(b),
+            })?;
+        Ok(found.map(|(ids, len)| Path::new(self.nodes_from_ids(&ids), len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::{error::GraphError, graph::Graph};
+
+    // 5x5 の格子 (中央付近に壁) をマンハッタン距離で探索する
+    #[test]
+    fn test_astar_grid() {
+        let walls = [(1, 2), (2, 2), (3, 2)];
+        let mut g = Graph::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                if !walls.contains(&(x, y)) {
+                    let _ = g.add_node((x, y));
+                }
+            }
+        }
+        for x in 0..5i32 {
+            for y in 0..5i32 {
+                for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let _ = g.add_edge(&(x, y), &(x + dx, y + dy));
+                }
+            }
+        }
+
+        let goal = (2, 4);
+        let manhattan = |&(x, y): &(i32, i32)| ((x - goal.0).abs() + (y - goal.1).abs()) as f64;
+        let p = g.astar(&(2, 0), &goal, manhattan).unwrap();
+
+        assert_eq!(p.weight(), 8.0);
+        assert_eq!(p.source(), Some(&(2, 0)));
+        assert_eq!(p.target(), Some(&goal));
+        assert!(walls.iter().all(|w| !p.contains(w)));
+
+        // ヒューリスティックが 0 なら Dijkstra と同じ
+        let d = g.shortest_path_dijkstra(&(2, 0), &goal).unwrap();
+        assert_eq!(
+            g.astar(&(2, 0), &goal, |_| 0.0).unwrap().weight(),
+            d.weight()
+        );
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_weighted_edge(&"B", &"A", 1.0);
+
+        assert!(g.astar(&"A", &"B", |_| 0.0).is_none());
+        assert!(g.astar(&"A", &"Z", |_| 0.0).is_none());
+        assert_eq!(g.astar(&"B", &"A", |_| 0.0).unwrap().weight(), 1.0);
+    }
+
+    #[test]
+    fn test_astar_by() {
+        // 一辺 3 の格子．右へは 2 秒，下へは 1 秒かかる
+        let mut g = Graph::new();
+        for x in 0..3u32 {
+            for y in 0..3u32 {
+                let _ = g.add_node((x, y));
+            }
+        }
+        for x in 0..3u32 {
+            for y in 0..3u32 {
+                let _ = g.add_edge(&(x, y), &(x + 1, y));
+                let _ = g.add_edge(&(x, y), &(x, y + 1));
+            }
+        }
+        let cost =
+            |a: &(u32, u32), b: &(u32, u32)| Duration::from_secs(if b.0 > a.0 { 2 } else { 1 });
+        let goal = (2, 2);
+        let remaining =
+            |&(x, y): &(u32, u32)| Duration::from_secs(u64::from(2 * (goal.0 - x) + (goal.1 - y)));
+
+        let p = g
+            .astar_by(&(0, 0), &goal, cost, remaining)
+            .unwrap()
+            .unwrap();
+        assert_eq!(p.weight(), Duration::from_secs(6));
+        assert_eq!(p.edge_count(), 4);
+        assert_eq!(g.astar_by(&goal, &(0, 0), cost, remaining), Ok(None));
+        assert_eq!(
+            g.astar_by(&(0, 0), &(9, 9), cost, remaining),
+            Err(GraphError::NodeNotFound { arg: "to" })
+        );
+
+        // 整数のコストは巻き戻らずにエラーになる
+        assert!(matches!(
+            g.astar_by(&(0, 0), &goal, |_, _| u8::MAX / 2, |_| 0),
+            Err(GraphError::WeightOverflow { .. })
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+use std::{borrow::Borrow, cmp::Ordering, collections::HashMap, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    dijkstra::{unreached_overflow, ShortestPathTree},
+    error::GraphError,
+    graph::Graph,
+    weight::Weight,
+};
+
+impl GraphCore {
+    // Bellman-Ford 法．負の重みを扱える
+    // 始点から到達できる負閉路があれば，そのサイクルを閉じた形 (先頭 == 末尾) で Err として返す
+    pub fn bellman_ford<F>(&self, start: NodeID, weight: F) -> Result<ShortestPathTree, Vec<NodeID>>
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        self.bellman_ford_by(start, weight).unwrap()
+    }
+
+    // 重みの型を選べる Bellman-Ford 法．距離を足して上に桁あふれした候補は短くないものとして飛ばす．
+    // 下に桁あふれした (距離を表せない) か，到達したのに距離を表せないノードが残ったら，
+    // そこへのエッジを外側の Err で返す
+    #[allow(clippy::type_complexity)]
+    pub fn bellman_ford_by<W, F>(
+        &self,
+        start: NodeID,
+        weight: F,
+    ) -> Result<Result<ShortestPathTree<W>, Vec<NodeID>>, (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut dist: HashMap<NodeID, W> = HashMap::new();
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut overflowed: HashMap<NodeID, (NodeID, NodeID)> = HashMap::new();
+
+        if !self.nodes_dict.contains_key(&start) {
+            return Ok(Ok((dist, parent)));
+        }
+        dist.insert(start, W::zero());
+
+        let edges: Vec<(NodeID, NodeID)> = self
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&c| (id, c)))
+            .collect();
+
+        let mut updated = None;
+        for _ in 0..self.nodes_dict.len() {
+            updated = None;
+            for &(a, b) in &edges {
+                let Some(&da) = dist.get(&a) else {
+                    continue;
+                };
+                let w = weight(a, b);
+                let Some(nd) = da.checked_add(w) else {
+                    if w.cmp_weight(&W::zero()) == Ordering::Less {
+                        return Err((a, b));
+                    }
+                    overflowed.entry(b).or_insert((a, b));
+                    continue;
+                };
+                if dist
+                    .get(&b)
+                    .is_none_or(|old| nd.cmp_weight(old) == Ordering::Less)
+                {
+                    dist.insert(b, nd);
+                    parent.insert(b, a);
+                    updated = Some(b);
+                }
+            }
+            if updated.is_none() {
+                if let Some(edge) = unreached_overflow(&overflowed, |id| dist.contains_key(&id)) {
+                    return Err(edge);
+                }
+                return Ok(Ok((dist, parent)));
+            }
+        }
+
+        // |V| 回目でも更新があれば負閉路がある．親を |V| 回たどればサイクル上に乗る
+        let mut node = updated.unwrap();
+        for _ in 0..self.nodes_dict.len() {
+            node = parent[&node];
+        }
+
+        let mut cycle = vec![node];
+        let mut cur = parent[&node];
+        while cur != node {
+            cycle.push(cur);
+            cur = parent[&cur];
+        }
+        cycle.push(node);
+        cycle.reverse();
+
+        Ok(Err(cycle))
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 負の重みを許す単一始点最短距離．負閉路に当たった場合はそのサイクルを返す
+    pub fn bellman_ford<Q>(&self, from: &Q) -> Result<HashMap<&T, f64>, Vec<&T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&from_id) = self.node_id(from) else {
+            return Ok(HashMap::new());
+        };
+
+        match self.core.bellman_ford(from_id, |a, b| self.weight_of(a, b)) {
+            Ok((dist, _)) => {
+                let nodes = self.nodes_by_id();
+                Ok(dist.into_iter().map(|(id, d)| (nodes[&id], d)).collect())
+            }
+            Err(cycle) => Err(self.nodes_from_ids(&cycle)),
+        }
+    }
+
+    // 重みを `weight` で与える単一始点最短距離 (i64 や Duration などの重みも使える)．
+    // 無いノードは NodeNotFound，負閉路に当たったら CycleDetected，距離が桁あふれしたら WeightOverflow
+    pub fn bellman_ford_by<Q, W, F>(
+        &self,
+        from: &Q,
+        weight: F,
+    ) -> Result<HashMap<&T, W>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let &from_id = self
+            .node_id(from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let nodes = self.nodes_by_id();
+
+        let result = self
+            .core
+            .bellman_ford_by(from_id, |a, b| weight(nodes[&a], nodes[&b]))
+            .map_err(|(a, b)| GraphError::WeightOverflow {
+                from: self.handle(a),
+                to: self.handle(b),
+            })?;
+        match result {
+            Ok((dist, _)) => Ok(dist.into_iter().map(|(id, d)| (nodes[&id], d)).collect()),
+            Err(cycle) => Err(GraphError::CycleDetected {
+                cycle: self.handles(&cycle),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_bellman_ford_negative_weights() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 4.0);
+        let _ = g.add_weighted_edge(&"A", &"C", 2.0);
+        let _ = g.add_weighted_edge(&"B", &"C", -3.0);
+        let _ = g.add_weighted_edge(&"C", &"D", 1.0);
+
+        let dist = g.bellman_ford(&"A").unwrap();
+        assert_eq!(dist[&"A"], 0.0);
+        assert_eq!(dist[&"B"], 4.0);
+        assert_eq!(dist[&"C"], 1.0);
+        assert_eq!(dist[&"D"], 2.0);
+
+        assert_eq!(g.bellman_ford(&"Z"), Ok(Default::default()));
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        let mut g = Graph::new();
+        for u in ["S", "A", "B", "C", "X"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"S", &"A", 1.0);
+        let _ = g.add_weighted_edge(&"A", &"B", 1.0);
+        let _ = g.add_weighted_edge(&"B", &"C", -2.0);
+        let _ = g.add_weighted_edge(&"C", &"A", -1.0); // A → B → C → A の重みは -2
+        let _ = g.add_weighted_edge(&"X", &"X", -1.0); // S から到達できない負閉路
+
+        let cycle = g.bellman_ford(&"S").unwrap_err();
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.first(), cycle.last());
+        for u in ["A", "B", "C"] {
+            assert!(cycle.contains(&&u));
+        }
+        let weight: f64 = cycle
+            .windows(2)
+            .map(|w| g.edge_weight(w[0], w[1]).unwrap())
+            .sum();
+        assert_eq!(weight, -2.0);
+
+        assert_eq!(g.bellman_ford(&"X").unwrap_err(), vec![&"X", &"X"]);
+    }
+
+    #[test]
+    fn test_bellman_ford_by() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+
+        let weight = |a: &&str, b: &&str| match (*a, *b) {
+            ("A", "B") => 4i8,
+            ("B", "C") => -3,
+            _ => 1,
+        };
+        let dist = g.bellman_ford_by(&"A", weight).unwrap();
+        assert_eq!(dist[&"C"], 1i8);
+        assert_eq!(
+            g.bellman_ford_by(&"Z", weight),
+            Err(GraphError::NodeNotFound { arg: "from" })
+        );
+
+        // 整数の重みは巻き戻らずにエラーになる
+        assert_eq!(
+            g.bellman_ford_by(&"A", |_, _| i8::MAX),
+            Err(GraphError::WeightOverflow {
+                from: NodeHandle::new(1, 0),
+                to: NodeHandle::new(2, 0)
+            })
+        );
+
+        // 最短でない経路 (A → B → C) が桁あふれしても最短距離が収まればよい
+        let _ = g.add_edge(&"A", &"C");
+        let dist = g.bellman_ford_by(&"A", |_, _| 100i8).unwrap();
+        assert_eq!(dist[&"C"], 100);
+        // 負の重みで下に桁あふれした距離は表せない
+        assert!(matches!(
+            g.bellman_ford_by(&"A", |_, _| i8::MIN),
+            Err(GraphError::WeightOverflow { .. })
+        ));
+
+        let _ = g.add_edge(&"C", &"B");
+        assert!(matches!(
+            g.bellman_ford_by(&"A", weight),
+            Err(GraphError::CycleDetected { cycle }) if cycle.len() == 3
+        ));
+    }
+}
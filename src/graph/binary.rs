@@ -0,0 +1,214 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    error::GraphError,
+    graph::Graph,
+    json::{check_version, graph_from_document, migrate, JsonValue, FORMAT_VERSION},
+};
+
+/// バイナリ形式の先頭のマジックナンバー．続けてリトルエンディアンの u32 で文書のバージョンを置く
+pub const BINARY_MAGIC: [u8; 4] = *b"GAGB";
+
+// 値の種類を表す先頭のバイト
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_NUMBER: u8 = 3;
+const TAG_STRING: u8 = 4;
+const TAG_ARRAY: u8 = 5;
+const TAG_OBJECT: u8 = 6;
+
+fn write_len<W: Write>(w: &mut W, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many items"))?;
+    w.write_all(&len.to_le_bytes())
+}
+
+fn write_str<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write_len(w, s.len())?;
+    w.write_all(s.as_bytes())
+}
+
+// JSON 形式と同じ文書を，種類のバイトと長さを前に付けて書く (数は f64 のまま)
+fn write_value<W: Write>(w: &mut W, value: &JsonValue) -> io::Result<()> {
+    match value {
+        JsonValue::Null => w.write_all(&[TAG_NULL]),
+        JsonValue::Bool(false) => w.write_all(&[TAG_FALSE]),
+        JsonValue::Bool(true) => w.write_all(&[TAG_TRUE]),
+        JsonValue::Number(n) => {
+            w.write_all(&[TAG_NUMBER])?;
+            w.write_all(&n.to_le_bytes())
+        }
+        JsonValue::String(s) => {
+            w.write_all(&[TAG_STRING])?;
+            write_str(w, s)
+        }
+        JsonValue::Array(items) => {
+            w.write_all(&[TAG_ARRAY])?;
+            write_len(w, items.len())?;
+            items.iter().try_for_each(|item| write_value(w, item))
+        }
+        JsonValue::Object(fields) => {
+            w.write_all(&[TAG_OBJECT])?;
+            write_len(w, fields.len())?;
+            fields.iter().try_for_each(|(k, v)| {
+                write_str(w, k)?;
+                write_value(w, v)
+            })
+        }
+    }
+}
+
+// バイト列を先頭から読む
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| format!("unexpected end of input at {}", self.pos))?;
+        let ret = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(ret)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        let at = self.pos;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| format!("invalid UTF-8 at {}", at))
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        let at = self.pos;
+        match self.take(1)?[0] {
+            TAG_NULL => Ok(JsonValue::Null),
+            TAG_FALSE => Ok(JsonValue::Bool(false)),
+            TAG_TRUE => Ok(JsonValue::Bool(true)),
+            TAG_NUMBER => Ok(JsonValue::Number(f64::from_le_bytes(
+                self.take(8)?.try_into().unwrap(),
+            ))),
+            TAG_STRING => Ok(JsonValue::String(self.string()?)),
+            TAG_ARRAY => {
+                let len = self.u32()?;
+                (0..len)
+                    .map(|_| self.value())
+                    .collect::<Result<_, _>>()
+                    .map(JsonValue::Array)
+            }
+            TAG_OBJECT => {
+                let len = self.u32()?;
+                (0..len)
+                    .map(|_| Ok((self.string()?, self.value()?)))
+                    .collect::<Result<_, String>>()
+                    .map(JsonValue::Object)
+            }
+            tag => Err(format!("invalid tag {} at {}", tag, at)),
+        }
+    }
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Graph<String>, String> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(BINARY_MAGIC.len()).ok() != Some(&BINARY_MAGIC[..]) {
+        return Err("missing header: not a graph_analyses binary document".to_string());
+    }
+    let version = r.u32()? as u64;
+    // 今より新しいバージョンの中身は読めないので，先にバージョンを確かめる
+    check_version(version)?;
+    let doc = migrate(version, r.value()?)?;
+    if r.pos != bytes.len() {
+        return Err(format!("trailing bytes at {}", r.pos));
+    }
+    graph_from_document(&doc)
+}
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // このクレートのバイナリ形式で書き出す．マジックナンバーと文書のバージョンに続けて，
+    // write_json と同じ内容の文書を書く
+    pub fn write_binary<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&BINARY_MAGIC)?;
+        w.write_all(&(FORMAT_VERSION as u32).to_le_bytes())?;
+        write_value(w, &JsonValue::Object(self.to_document()))
+    }
+
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.write_binary(&mut buf).unwrap(); // Vec への書き込みは失敗しない
+        buf
+    }
+}
+
+impl Graph<String> {
+    // write_binary で書き出したバイト列を読み込む．古いバージョンの文書は読み込み時に変換し，
+    // ヘッダが無いものやこのビルドより新しいバージョンのものはエラーにする
+    pub fn from_binary(bytes: &[u8]) -> Result<Graph<String>, GraphError> {
+        parse_binary(bytes).map_err(GraphError::Parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        error::GraphError, graph::Graph, provenance::Provenance, self_loop::SelfLoopPolicy,
+    };
+    use super::BINARY_MAGIC;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut g = Graph::new_multigraph();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.alias("A", "a");
+        let _ = g.add_edge_with_label("A", "B", "depends-on");
+        let _ = g.add_edge("A", "B");
+        let _ = g.add_weighted_edge("B", "C", f64::NAN);
+        let _ = g.add_edge_with_provenance("C", "A", Provenance::new("c.rs", 7, "use"));
+        let _ = g.set_self_loop_policy(SelfLoopPolicy::IgnoreInCycles);
+
+        let bytes = g.to_binary();
+        assert_eq!(bytes[..4], BINARY_MAGIC);
+        assert_eq!(bytes[4..8], 2u32.to_le_bytes());
+
+        let loaded = Graph::from_binary(&bytes).unwrap();
+        assert_eq!(loaded.edge_multiplicity("A", "B"), 2);
+        assert_eq!(loaded.edge_label("a", "B"), Some("depends-on"));
+        assert!(loaded.edge_weight("B", "C").unwrap().is_nan());
+        assert_eq!(loaded.provenance("C", "A"), g.provenance("C", "A"));
+        assert_eq!(loaded.self_loop_policy(), SelfLoopPolicy::IgnoreInCycles);
+        assert_eq!(loaded.to_binary(), bytes);
+        // JSON と同じ文書なので JSON にしても同じ
+        assert_eq!(loaded.to_json(), g.to_json());
+    }
+
+    #[test]
+    fn test_from_binary_invalid() {
+        let bytes = Graph::<String>::new().to_binary();
+        let parse_error = |bytes: &[u8]| match Graph::from_binary(bytes) {
+            Err(GraphError::Parse(msg)) => msg,
+            other => panic!("{:?}", other.map(|g| g.to_json())),
+        };
+
+        assert!(parse_error(b"GAGX\x02\0\0\0").starts_with("missing header"));
+        let mut newer = bytes.clone();
+        newer[4] = 3;
+        assert!(parse_error(&newer).starts_with("unsupported version 3"));
+        assert!(parse_error(&bytes[..bytes.len() - 1]).starts_with("unexpected end of input"));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(parse_error(&trailing).starts_with("trailing bytes"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{core::NodeID, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 二部グラフの片側 (side が true のノード) と反対側のノード (ハブ) に分け，
+    // ハブごとの隣接ノード (向きは無視) を ID の昇順で返す．同じ側同士のエッジは無視する
+    fn bipartite_hubs<F>(&self, side: &F) -> (Vec<NodeID>, Vec<(NodeID, Vec<NodeID>)>)
+    where
+        F: Fn(&T) -> bool,
+    {
+        let nodes = self.nodes_by_id();
+        let on_side = |id: &NodeID| side(nodes[id]);
+
+        let mut members: Vec<NodeID> = nodes.keys().copied().filter(on_side).collect();
+        members.sort_unstable();
+
+        let neighbors = self.core.undirected_neighbors();
+        let mut hubs: Vec<(NodeID, Vec<NodeID>)> = neighbors
+            .iter()
+            .filter(|(id, _)| !on_side(id))
+            .map(|(&hub, ns)| {
+                let mut ns: Vec<NodeID> = ns.iter().copied().filter(on_side).collect();
+                ns.sort_unstable();
+                (hub, ns)
+            })
+            .collect();
+        hubs.sort_unstable();
+
+        (members, hubs)
+    }
+
+    // クリーク展開: side 側のノードだけを残し，同じハブを共有するノード同士を双方向のエッジで結ぶ．
+    // 重みは共有しているハブの数 (共起回数)．著者–論文のグラフから共著グラフを作るときなどに使う
+    pub fn clique_expansion<F>(&self, side: F) -> Graph<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (members, hubs) = self.bipartite_hubs(&side);
+
+        let mut counts: HashMap<(NodeID, NodeID), usize> = HashMap::new();
+        for (_, ns) in &hubs {
+            for (i, &a) in ns.iter().enumerate() {
+                for &b in &ns[i + 1..] {
+                    *counts.entry((a, b)).or_default() += 1;
+                }
+            }
+        }
+
+        let nodes = self.nodes_by_id();
+        let mut g = Graph::new();
+        for id in &members {
+            let _ = g.add_node(nodes[id]);
+        }
+        for ((a, b), count) in counts {
+            let _ = g.add_weighted_edge(&nodes[&a], &nodes[&b], count as f64);
+            let _ = g.add_weighted_edge(&nodes[&b], &nodes[&a], count as f64);
+        }
+        g
+    }
+
+    // スター展開: 各ハブを中心とする星として，ハブと side 側のノードを双方向のエッジで結ぶ．
+    // 大きなハブの影響が強くなりすぎないよう，重みは 1 / (ハブの次数)．
+    // side 側の孤立したノードも残す (どのハブにも属さないノードは残さない)
+    pub fn star_expansion<F>(&self, side: F) -> Graph<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (members, hubs) = self.bipartite_hubs(&side);
+
+        let nodes = self.nodes_by_id();
+        let mut g = Graph::new();
+        for id in &members {
+            let _ = g.add_node(nodes[id]);
+        }
+        for (hub, ns) in hubs.iter().filter(|(_, ns)| !ns.is_empty()) {
+            let _ = g.add_node(nodes[hub]);
+            let w = 1.0 / ns.len() as f64;
+            for m in ns {
+                let _ = g.add_weighted_edge(&nodes[hub], &nodes[m], w);
+                let _ = g.add_weighted_edge(&nodes[m], &nodes[hub], w);
+            }
+        }
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    // 著者 a1..a3 と論文 p1, p2
+    fn authorship() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["a1", "a2", "a3", "a4", "p1", "p2"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"a1", &"p1");
+        let _ = g.add_edge(&"a2", &"p1");
+        let _ = g.add_edge(&"a3", &"p1");
+        let _ = g.add_edge(&"p2", &"a1"); // 向きは問わない
+        let _ = g.add_edge(&"a2", &"p2");
+        let _ = g.add_edge(&"a1", &"a2"); // 同じ側同士のエッジは無視される
+        g
+    }
+
+    #[test]
+    fn test_clique_expansion() {
+        let g = authorship();
+        let mut co = g.clique_expansion(|u| u.starts_with('a'));
+
+        assert_eq!(co.edge_weight(&&"a1", &&"a2"), Some(2.0));
+        assert_eq!(co.edge_weight(&&"a2", &&"a1"), Some(2.0));
+        assert_eq!(co.edge_weight(&&"a1", &&"a3"), Some(1.0));
+        assert_eq!(co.edge_weight(&&"a3", &&"a2"), Some(1.0));
+        assert_eq!(co.edge_weight(&&"a1", &&"p1"), None);
+        // 論文の無い著者も残り，論文は残らない
+        assert!(co.add_node(&"a4").is_err());
+        assert!(co.add_node(&"p1").is_ok());
+    }
+
+    #[test]
+    fn test_star_expansion() {
+        let g = authorship();
+        let star = g.star_expansion(|u| u.starts_with('a'));
+
+        assert_eq!(star.edge_weight(&&"p1", &&"a3"), Some(1.0 / 3.0));
+        assert_eq!(star.edge_weight(&&"a3", &&"p1"), Some(1.0 / 3.0));
+        assert_eq!(star.edge_weight(&&"a1", &&"p2"), Some(0.5));
+        assert_eq!(star.edge_weight(&&"a1", &&"a2"), None);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+/// ノードとエッジをまとめて登録してから `build` でグラフを作るビルダー
+///
+/// ノードとエッジはどの順で登録してもよく，エッジにだけ現れるノードは自動で追加する
+/// (`strict` にするとエラーにする)．検査はすべて `build` で行う
+pub struct GraphBuilder<T> {
+    nodes: Vec<T>,
+    edges: Vec<(T, T, Option<f64>)>,
+    strict: bool,
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> Default for GraphBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> GraphBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            strict: false,
+        }
+    }
+
+    // エッジの端点が node で登録されていなければエラーにする
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    pub fn node(mut self, u: T) -> Self {
+        self.nodes.push(u);
+        self
+    }
+
+    pub fn nodes<I: IntoIterator<Item = T>>(mut self, us: I) -> Self {
+        self.nodes.extend(us);
+        self
+    }
+
+    pub fn edge(mut self, u_from: T, u_to: T) -> Self {
+        self.edges.push((u_from, u_to, None));
+        self
+    }
+
+    pub fn weighted_edge(mut self, u_from: T, u_to: T, weight: f64) -> Self {
+        self.edges.push((u_from, u_to, Some(weight)));
+        self
+    }
+
+    pub fn edges<I: IntoIterator<Item = (T, T)>>(mut self, es: I) -> Self {
+        self.edges.extend(es.into_iter().map(|(a, b)| (a, b, None)));
+        self
+    }
+
+    // グラフを作る．ノードは登録順 (自動で追加したものはその後ろにエッジの順) に追加する．
+    // 同じノードを何度登録してもよいが，同じエッジに異なる重みを付けたり，
+    // 重みが有限でなかったりするとエラーになる
+    pub fn build(self) -> Result<Graph<T>, GraphError> {
+        let mut g = Graph::new();
+        for u in self.nodes {
+            if !g.contains_node(&u) {
+                g.add_node(u)?;
+            }
+        }
+
+        let mut seen: HashMap<(&T, &T), Option<f64>> = HashMap::new();
+        for (a, b, w) in &self.edges {
+            for (u, arg) in [(a, "from"), (b, "to")] {
+                if !g.contains_node(u) {
+                    if self.strict {
+                        return Err(GraphError::NodeNotFound { arg });
+                    }
+                    g.add_node(u.clone())?;
+                }
+            }
+            let (from, to) = (g.id_dict[a], g.id_dict[b]);
+
+            if let Some(w) = w {
+                if !w.is_finite() {
+                    return Err(GraphError::InvalidArgument(format!(
+                        "weight of edge {} -> {} is not finite: {}",
+                        g.handle(from),
+                        g.handle(to),
+                        w
+                    )));
+                }
+            }
+            match seen.get(&(a, b)) {
+                Some(prev) if prev != w => {
+                    return Err(GraphError::ConflictingWeights {
+                        from: g.handle(from),
+                        to: g.handle(to),
+                        existing: prev.unwrap_or(1.0),
+                        incoming: w.unwrap_or(1.0),
+                    })
+                }
+                _ => {
+                    seen.insert((a, b), *w);
+                }
+            }
+
+            match w {
+                Some(w) => g.add_weighted_edge(a, b, *w)?,
+                None => g.add_edge(a, b)?,
+            };
+        }
+        Ok(g)
+    }
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> Graph<T> {
+    pub fn builder() -> GraphBuilder<T> {
+        GraphBuilder::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // ノードの ID．無ければ追加する
+    pub(super) fn id_or_insert(&mut self, u: T) -> NodeID {
+        match self.node_id(&u) {
+            Some(&id) => id,
+            None => self.add_node(u).unwrap().index(), // 無いことを確かめた
+        }
+    }
+}
+
+// エッジの列を追加する．端点が無ければ自動で追加する．
+// self_loop_policy で禁止された自己ループは追加しない
+impl<T: PartialEq + Eq + Hash> Extend<(T, T)> for Graph<T> {
+    fn extend<I: IntoIterator<Item = (T, T)>>(&mut self, iter: I) {
+        for (a, b) in iter {
+            let from = self.id_or_insert(a);
+            let to = self.id_or_insert(b);
+            let _ = self.add_edge_by_id(from, to);
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> FromIterator<(T, T)> for Graph<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut g = Graph::new();
+        g.extend(iter);
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    #[test]
+    fn test_builder() {
+        let g = Graph::builder()
+            .edge("A", "B")
+            .weighted_edge("B", "C", 2.0)
+            .node("D")
+            .node("A")
+            .edges([("C", "A"), ("A", "B")])
+            .build()
+            .unwrap();
+
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.edge_weight(&"B", &"C"), Some(2.0));
+        assert!(g.contains_node(&"D"));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let strict = Graph::builder().node("A").edge("A", "B").strict().build();
+        assert!(matches!(
+            strict,
+            Err(GraphError::NodeNotFound { arg: "to" })
+        ));
+
+        let conflict = Graph::builder()
+            .weighted_edge("A", "B", 1.0)
+            .weighted_edge("A", "B", 3.0)
+            .build();
+        assert!(matches!(
+            conflict,
+            Err(GraphError::ConflictingWeights { .. })
+        ));
+
+        let nan = Graph::builder().weighted_edge("A", "B", f64::NAN).build();
+        assert!(matches!(nan, Err(GraphError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_from_iterator_and_extend() {
+        let mut g: Graph<&str> = [("A", "B"), ("B", "C"), ("A", "B")].into_iter().collect();
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+        assert!(g.contains_edge(&"B", &"C"));
+
+        g.extend([("C", "A"), ("D", "D")]);
+        assert_eq!(g.node_count(), 4);
+        assert!(g.contains_edge(&"D", &"D"));
+        assert!(g.detect_cycle().is_some());
+    }
+
+    #[test]
+    fn test_extend_multigraph() {
+        // 多重グラフでは同じエッジを繰り返すと平行なエッジになる
+        let mut g = Graph::new_multigraph();
+        g.extend([("A", "B"), ("A", "B"), ("B", "C")]);
+        assert_eq!(g.edge_multiplicity(&"A", &"B"), 2);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.out_degree(&"A"), Some(2));
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read},
+};
+
+use super::{error::GraphError, graph::Graph};
+
+/// `(caller, callee)` の呼び出しイベントを受け取りながらコールグラフを作る
+///
+/// エッジの重みはその呼び出しが記録された回数．tracing のスパンのように
+/// 入った・出たことしか分からない場合は `enter` と `exit` で呼び出し元を補う
+pub struct CallGraphBuilder {
+    graph: Graph<String>,
+    // 現在入っているスパン (末尾が最も内側)
+    stack: Vec<String>,
+}
+
+impl Default for CallGraphBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CallGraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    // ここまでに作られたコールグラフ
+    pub fn graph(&self) -> &Graph<String> {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph<String> {
+        self.graph
+    }
+
+    fn ensure_node(&mut self, u: &str) {
+        if !self.graph.contains_node(u) {
+            let _ = self.graph.add_node(u.to_string());
+        }
+    }
+
+    // `caller` から `callee` への呼び出しを 1 回記録する
+    pub fn record(&mut self, caller: &str, callee: &str) {
+        self.ensure_node(caller);
+        self.ensure_node(callee);
+
+        let count = self.call_count(caller, callee) + 1;
+        // 両端のノードは追加済みなので失敗しない
+        let _ = self.graph.add_weighted_edge(caller, callee, count as f64);
+    }
+
+    // 呼び出しの列をまとめて記録する
+    pub fn extend<I, S>(&mut self, events: I)
+    where
+        I: IntoIterator<Item = (S, S)>,
+        S: AsRef<str>,
+    {
+        for (caller, callee) in events {
+            self.record(caller.as_ref(), callee.as_ref());
+        }
+    }
+
+    // スパン `name` に入る．外側のスパンがあればそこからの呼び出しとして記録する
+    pub fn enter(&mut self, name: &str) {
+        match self.stack.last().cloned() {
+            Some(caller) => self.record(&caller, name),
+            None => self.ensure_node(name),
+        }
+        self.stack.push(name.to_string());
+    }
+
+    // 最も内側のスパンから出る．入っているスパンが無ければ None
+    pub fn exit(&mut self) -> Option<String> {
+        self.stack.pop()
+    }
+
+    // ログを 1 行ずつ `parse` に渡し，(caller, callee) が得られた行を記録する．
+    // 正規表現などで抜き出すのは呼び出し側に任せる．記録した行数を返す
+    pub fn record_lines<R, F>(&mut self, reader: R, mut parse: F) -> Result<usize, GraphError>
+    where
+        R: Read,
+        F: FnMut(&str) -> Option<(&str, &str)>,
+    {
+        let mut recorded = 0;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            if let Some((caller, callee)) = parse(&line) {
+                self.record(caller, callee);
+                recorded += 1;
+            }
+        }
+        Ok(recorded)
+    }
+
+    // `caller` から `callee` が呼ばれた回数
+    pub fn call_count(&self, caller: &str, callee: &str) -> u64 {
+        self.graph
+            .edge_weight(caller, callee)
+            .map_or(0, |w| w as u64)
+    }
+
+    // 呼ばれた回数の合計が多い関数の上位 `n` 個 (同数なら名前順)
+    pub fn hotspots(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut calls: HashMap<&str, u64> = HashMap::new();
+        for (caller, callee) in self.graph.edges() {
+            *calls.entry(callee.as_str()).or_default() += self.call_count(caller, callee);
+        }
+
+        let mut ret: Vec<(&str, u64)> = calls.into_iter().collect();
+        ret.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ret.truncate(n);
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallGraphBuilder;
+
+    #[test]
+    fn test_call_graph_from_events() {
+        let mut b = CallGraphBuilder::new();
+        b.extend([("main", "parse"), ("main", "eval"), ("eval", "eval")]);
+        b.record("eval", "lookup");
+        b.record("eval", "lookup");
+
+        // tracing のスパン: main > eval > lookup
+        b.enter("main");
+        b.enter("eval");
+        b.enter("lookup");
+        assert_eq!(b.exit(), Some("lookup".to_string()));
+        b.exit();
+        b.exit();
+        assert_eq!(b.exit(), None);
+
+        assert_eq!(b.call_count("main", "eval"), 2);
+        assert_eq!(b.call_count("eval", "lookup"), 3);
+        assert_eq!(b.call_count("lookup", "main"), 0);
+        assert_eq!(b.hotspots(2), vec![("eval", 3), ("lookup", 3)]);
+        // 再帰呼び出しは自己ループになる
+        assert!(b.graph().detect_cycle().is_some());
+    }
+
+    #[test]
+    fn test_call_graph_from_log_lines() {
+        let log = "\
+INFO call main -> init
+DEBUG cache hit
+INFO call init -> load
+INFO call load -> init
+INFO call main -> init
+";
+        let mut b = CallGraphBuilder::new();
+        let n = b
+            .record_lines(log.as_bytes(), |line| {
+                line.split_once("call ")?.1.split_once(" -> ")
+            })
+            .unwrap();
+
+        assert_eq!(n, 4);
+        assert_eq!(b.call_count("main", "init"), 2);
+        let g = b.into_graph();
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.detect_cycle().unwrap().len(), 3);
+    }
+}
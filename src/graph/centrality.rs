@@ -0,0 +1,373 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    rng::Rng,
+};
+
+impl GraphCore {
+    // PageRank (べき乗法)．遷移確率は出ていくエッジの重みに比例し，
+    // 出ていくエッジが無いノードからは全ノードに均等に移る
+    pub fn pagerank<F>(
+        &self,
+        damping: f64,
+        max_iterations: usize,
+        weight: F,
+    ) -> HashMap<NodeID, f64>
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        let n = self.nodes_dict.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+
+        let out_weight: HashMap<NodeID, f64> = self
+            .nodes_dict
+            .iter()
+            .map(|(&id, node)| (id, node.children.iter().map(|&c| weight(id, c)).sum()))
+            .collect();
+
+        let mut rank: HashMap<NodeID, f64> = self
+            .nodes_dict
+            .keys()
+            .map(|&id| (id, 1.0 / n as f64))
+            .collect();
+        for _ in 0..max_iterations {
+            let dangling: f64 = rank
+                .iter()
+                .filter(|(id, _)| out_weight[id] <= 0.0)
+                .map(|(_, r)| r)
+                .sum();
+            let base = (1.0 - damping) / n as f64 + damping * dangling / n as f64;
+
+            let mut next: HashMap<NodeID, f64> =
+                self.nodes_dict.keys().map(|&id| (id, base)).collect();
+            for (&id, node) in self.nodes_dict.iter() {
+                if out_weight[&id] <= 0.0 {
+                    continue;
+                }
+                for &child in &node.children {
+                    *next.get_mut(&child).unwrap() +=
+                        damping * rank[&id] * weight(id, child) / out_weight[&id];
+                }
+            }
+
+            let diff: f64 = next.iter().map(|(id, r)| (r - rank[id]).abs()).sum();
+            rank = next;
+            if diff < 1e-12 {
+                break;
+            }
+        }
+        rank
+    }
+
+    // 媒介中心性 (Brandes 法)．経路の長さはホップ数で数え，正規化はしない
+    pub fn betweenness(&self) -> HashMap<NodeID, f64> {
+        let mut ret: HashMap<NodeID, f64> = self.nodes_dict.keys().map(|&id| (id, 0.0)).collect();
+
+        for &s in self.nodes_dict.keys() {
+            let mut order = Vec::new();
+            let mut preds: HashMap<NodeID, Vec<NodeID>> = HashMap::new();
+            let mut sigma: HashMap<NodeID, f64> = HashMap::from([(s, 1.0)]);
+            let mut dist: HashMap<NodeID, usize> = HashMap::from([(s, 0)]);
+
+            let mut queue = VecDeque::from([s]);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                for &w in &self.nodes_dict[&v].children {
+                    if !dist.contains_key(&w) {
+                        dist.insert(w, dist[&v] + 1);
+                        queue.push_back(w);
+                    }
+                    if dist[&w] == dist[&v] + 1 {
+                        *sigma.entry(w).or_default() += sigma[&v];
+                        preds.entry(w).or_default().push(v);
+                    }
+                }
+            }
+
+            let mut delta: HashMap<NodeID, f64> = HashMap::new();
+            for &w in order.iter().rev() {
+                let dw = delta.get(&w).copied().unwrap_or(0.0);
+                for &v in preds.get(&w).into_iter().flatten() {
+                    *delta.entry(v).or_default() += sigma[&v] / sigma[&w] * (1.0 + dw);
+                }
+                if w != s {
+                    *ret.get_mut(&w).unwrap() += dw;
+                }
+            }
+        }
+        ret
+    }
+
+    // 媒介中心性の近似 (Riondato–Kornaropoulos 法)．始点と終点の組を `samples` 回選び，
+    // それぞれ最短経路を 1 本一様に選んで途中のノードに数える．betweenness と同じ尺度に直して返す
+    pub fn betweenness_sampled<R: Rng>(&self, samples: usize, rng: &mut R) -> HashMap<NodeID, f64> {
+        let mut ret: HashMap<NodeID, f64> = self.nodes_dict.keys().map(|&id| (id, 0.0)).collect();
+        let n = self.nodes_dict.len();
+        if n < 2 || samples == 0 {
+            return ret;
+        }
+
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        let scale = (n * (n - 1)) as f64 / samples as f64;
+
+        for _ in 0..samples {
+            let s = ids[rng.gen_index(n)];
+            let t = loop {
+                let t = ids[rng.gen_index(n)];
+                if t != s {
+                    break t;
+                }
+            };
+
+            // s からの BFS (t の層まで) で最短経路の数と直前のノードを数える
+            let mut preds: HashMap<NodeID, Vec<NodeID>> = HashMap::new();
+            let mut sigma: HashMap<NodeID, f64> = HashMap::from([(s, 1.0)]);
+            let mut dist: HashMap<NodeID, usize> = HashMap::from([(s, 0)]);
+            let mut queue = VecDeque::from([s]);
+            while let Some(v) = queue.pop_front() {
+                if dist.get(&t).is_some_and(|&dt| dist[&v] >= dt) {
+                    break;
+                }
+                let mut children: Vec<NodeID> =
+                    self.nodes_dict[&v].children.iter().copied().collect();
+                children.sort_unstable();
+                for w in children {
+                    if !dist.contains_key(&w) {
+                        dist.insert(w, dist[&v] + 1);
+                        queue.push_back(w);
+                    }
+                    if dist[&w] == dist[&v] + 1 {
+                        *sigma.entry(w).or_default() += sigma[&v];
+                        preds.entry(w).or_default().push(v);
+                    }
+                }
+            }
+            if !dist.contains_key(&t) {
+                continue;
+            }
+
+            // t から s へ，経路の数に比例した確率で直前のノードを選んで戻る
+            let mut w = t;
+            while w != s {
+                let mut x = rng.next_f64() * sigma[&w];
+                let ps = &preds[&w];
+                let mut v = ps[ps.len() - 1];
+                for &p in ps {
+                    if x < sigma[&p] {
+                        v = p;
+                        break;
+                    }
+                    x -= sigma[&p];
+                }
+                if v != s {
+                    *ret.get_mut(&v).unwrap() += scale;
+                }
+                w = v;
+            }
+        }
+        ret
+    }
+}
+
+// 媒介中心性の近似に必要な標本数．頂点直径は n で上から抑える
+// (Riondato & Kornaropoulos, 2016．定数 c は 0.5)
+fn betweenness_sample_size(n: usize, epsilon: f64, delta: f64) -> usize {
+    let vd = n.max(3) as f64;
+    let r = 0.5 / (epsilon * epsilon) * ((vd - 2.0).log2().floor() + 1.0 + (1.0 / delta).ln());
+    r.ceil() as usize
+}
+
+// スコアの大きい順に k 個選ぶ (同点なら NodeID の小さい順)．全体は整列しない
+fn select_top_k(mut scores: Vec<(NodeID, f64)>, k: usize) -> Vec<(NodeID, f64)> {
+    let by_score = |a: &(NodeID, f64), b: &(NodeID, f64)| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0));
+
+    if k < scores.len() {
+        if k == 0 {
+            return Vec::new();
+        }
+        scores.select_nth_unstable_by(k - 1, by_score);
+        scores.truncate(k);
+    }
+    scores.sort_unstable_by(by_score);
+    scores
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // PageRank (減衰率 0.85)．重み付きのエッジは重みに比例して遷移する
+    pub fn pagerank(&self) -> HashMap<&T, f64> {
+        let nodes = self.nodes_by_id();
+        self.core
+            .pagerank(0.85, 100, |a, b| self.weight_of(a, b))
+            .into_iter()
+            .map(|(id, r)| (nodes[&id], r))
+            .collect()
+    }
+
+    // 媒介中心性 (ホップ数での最短経路に基づく)
+    pub fn betweenness(&self) -> HashMap<&T, f64> {
+        let nodes = self.nodes_by_id();
+        self.core
+            .betweenness()
+            .into_iter()
+            .map(|(id, b)| (nodes[&id], b))
+            .collect()
+    }
+
+    // 媒介中心性の近似．確率 1 - `delta` 以上で，すべてのノードについて betweenness との差が
+    // `epsilon` * n * (n - 1) 以下になる．標本数はノード数の対数にしか比例しないので，
+    // 巨大なグラフでも厳密な計算 (全ノードからの BFS) よりはるかに速い
+    pub fn betweenness_approx<R: Rng>(
+        &self,
+        epsilon: f64,
+        delta: f64,
+        rng: &mut R,
+    ) -> Result<HashMap<&T, f64>, GraphError> {
+        if !(epsilon > 0.0 && epsilon < 1.0 && delta > 0.0 && delta < 1.0) {
+            return Err(GraphError::InvalidArgument(format!(
+                "epsilon and delta must be in (0, 1), got {} and {}",
+                epsilon, delta
+            )));
+        }
+
+        let samples = betweenness_sample_size(self.node_count(), epsilon, delta);
+        let nodes = self.nodes_by_id();
+        Ok(self
+            .core
+            .betweenness_sampled(samples, rng)
+            .into_iter()
+            .map(|(id, b)| (nodes[&id], b))
+            .collect())
+    }
+
+    // 次数 (入次数 + 出次数) の大きい順に k 個．大きさ k のヒープで選ぶ
+    pub fn top_k_by_degree(&self, k: usize) -> Vec<(&T, usize)> {
+        let mut heap: BinaryHeap<Reverse<(usize, Reverse<NodeID>)>> = BinaryHeap::new();
+        for (&id, n) in self.core.nodes_dict.iter() {
+            let item = Reverse((n.children.len() + n.parents.len(), Reverse(id)));
+            if heap.len() < k {
+                heap.push(item);
+            } else if heap
+                .peek()
+                .is_some_and(|min| item.cmp(min) == Ordering::Less)
+            {
+                heap.pop();
+                heap.push(item);
+            }
+        }
+
+        let nodes = self.nodes_by_id();
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((d, Reverse(id)))| (nodes[&id], d))
+            .collect()
+    }
+
+    pub fn top_k_by_pagerank(&self, k: usize) -> Vec<(&T, f64)> {
+        let scores = self.core.pagerank(0.85, 100, |a, b| self.weight_of(a, b));
+        let nodes = self.nodes_by_id();
+        select_top_k(scores.into_iter().collect(), k)
+            .into_iter()
+            .map(|(id, s)| (nodes[&id], s))
+            .collect()
+    }
+
+    pub fn top_k_by_betweenness(&self, k: usize) -> Vec<(&T, f64)> {
+        let scores = self.core.betweenness();
+        let nodes = self.nodes_by_id();
+        select_top_k(scores.into_iter().collect(), k)
+            .into_iter()
+            .map(|(id, s)| (nodes[&id], s))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{graph::Graph, rng::XorShift64};
+
+    // 0 を中心とした双方向の星と 4 → 5 の枝
+    fn star() -> Graph<u32> {
+        let mut g = Graph::new();
+        for u in 0..6 {
+            let _ = g.add_node(u);
+        }
+        for u in 1..5 {
+            let _ = g.add_edge(&0, &u);
+            let _ = g.add_edge(&u, &0);
+        }
+        let _ = g.add_edge(&4, &5);
+        g
+    }
+
+    #[test]
+    fn test_pagerank() {
+        let g = star();
+        let rank = g.pagerank();
+
+        let total: f64 = rank.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(rank.iter().all(|(u, &r)| **u == 0 || r < rank[&0]));
+        assert_eq!(g.top_k_by_pagerank(1)[0].0, &0);
+    }
+
+    #[test]
+    fn test_betweenness() {
+        let g = star();
+        let b = g.betweenness();
+
+        // 1..=4 の間の 4 * 3 組と 1..=3 → 5 の 3 組は 0 を通り，0..=3 → 5 の 4 組は 4 を通る
+        assert_eq!(b[&0], 15.0);
+        assert_eq!(b[&4], 4.0);
+        assert_eq!(b[&1], 0.0);
+
+        let top = g.top_k_by_betweenness(2);
+        assert_eq!(top, vec![(&0, 15.0), (&4, 4.0)]);
+    }
+
+    #[test]
+    fn test_betweenness_approx() {
+        let g = star();
+        let exact = g.betweenness();
+
+        let (epsilon, delta) = (0.05, 0.1);
+        let approx = g
+            .betweenness_approx(epsilon, delta, &mut XorShift64::new(7))
+            .unwrap();
+        for (u, b) in &exact {
+            assert!(
+                (approx[u] - b).abs() <= epsilon * 30.0,
+                "{u}: {}",
+                approx[u]
+            );
+        }
+        assert_eq!(approx[&1], 0.0);
+
+        assert!(g
+            .betweenness_approx(0.0, 0.1, &mut XorShift64::new(7))
+            .is_err());
+        assert!(g
+            .betweenness_approx(0.1, 1.0, &mut XorShift64::new(7))
+            .is_err());
+    }
+
+    #[test]
+    fn test_top_k_by_degree() {
+        let g = star();
+
+        assert_eq!(g.top_k_by_degree(2), vec![(&0, 8), (&4, 3)]);
+        // 同点は追加順
+        assert_eq!(g.top_k_by_degree(4)[2..], [(&1, 2), (&2, 2)]);
+        assert!(g.top_k_by_degree(0).is_empty());
+        assert_eq!(g.top_k_by_degree(10).len(), 6);
+    }
+}
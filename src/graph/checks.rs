@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
+use super::{core::NodeID, format::node_label, graph::Graph};
+
+/// グラフの検査で見つかった違反
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation<'a, T> {
+    // 基準のグラフには無かったサイクル．members は強連結成分，cycle はその中の代表サイクル (閉じた形)
+    NewCycle {
+        members: Vec<&'a T>,
+        cycle: Vec<&'a T>,
+    },
+    // ポリシーで許されないエッジ
+    ForbiddenEdge {
+        from: &'a T,
+        to: &'a T,
+    },
+}
+
+impl<T: Debug> Display for Violation<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::NewCycle { cycle, .. } => {
+                let path: Vec<String> = cycle.iter().map(node_label).collect();
+                write!(f, "new cycle: {}", path.join(" -> "))
+            }
+            Violation::ForbiddenEdge { from, to } => {
+                write!(
+                    f,
+                    "forbidden edge: {} -> {}",
+                    node_label(from),
+                    node_label(to)
+                )
+            }
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // baseline に無かったサイクルが無いことを確かめる．自明でない強連結成分のうち，
+    // baseline のどれか 1 つの強連結成分 (自明でないもの) に含まれないものを違反として返す．
+    // 自己ループの扱いは self の self_loop_policy に従う
+    pub fn assert_no_new_cycles(&self, baseline: &Graph<T>) -> Result<(), Vec<Violation<'_, T>>> {
+        let skip_self_loops = self.ignores_self_loops();
+        // baseline の各ノードが属する自明でない成分の番号
+        let mut baseline_component: HashMap<&T, usize> = HashMap::new();
+        let baseline_nodes = baseline.nodes_by_id();
+        for (i, c) in baseline
+            .core
+            .strongly_connected_components()
+            .iter()
+            .enumerate()
+        {
+            if baseline.core.is_nontrivial_component(c, skip_self_loops) {
+                for id in c {
+                    baseline_component.insert(baseline_nodes[id], i);
+                }
+            }
+        }
+
+        let nodes = self.nodes_by_id();
+        let mut violations = Vec::new();
+        let mut sccs: Vec<Vec<NodeID>> = self
+            .core
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|c| self.core.is_nontrivial_component(c, skip_self_loops))
+            .collect();
+        sccs.sort_unstable_by_key(|c| c[0]);
+
+        for c in sccs {
+            let first = baseline_component.get(nodes[&c[0]]);
+            let known = first.is_some()
+                && c.iter()
+                    .all(|id| baseline_component.get(nodes[id]) == first);
+            if known {
+                continue;
+            }
+
+            let cycle = self.core.cycle_in_component(&c, skip_self_loops).unwrap(); // 自明でない成分にはサイクルがある
+            violations.push(Violation::NewCycle {
+                members: c.iter().map(|id| nodes[id]).collect(),
+                cycle: self.nodes_from_ids(&cycle),
+            });
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    // すべてのエッジが policy(from, to) を満たすことを確かめる．違反は追加順に並ぶ
+    pub fn assert_edge_policy<F>(&self, policy: F) -> Result<(), Vec<Violation<'_, T>>>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let nodes = self.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .collect();
+        edges.sort_unstable();
+
+        let violations: Vec<Violation<'_, T>> = edges
+            .into_iter()
+            .filter(|(a, b)| !policy(nodes[a], nodes[b]))
+            .map(|(a, b)| Violation::ForbiddenEdge {
+                from: nodes[&a],
+                to: nodes[&b],
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::Violation;
+
+    fn layered(extra: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["ui", "service", "db", "cache"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"ui", &"service");
+        let _ = g.add_edge(&"service", &"db");
+        let _ = g.add_edge(&"service", &"cache");
+        let _ = g.add_edge(&"cache", &"service"); // 既知のサイクル
+        for (a, b) in extra {
+            let _ = g.add_edge(a, b);
+        }
+        g
+    }
+
+    #[test]
+    fn test_assert_no_new_cycles() {
+        let baseline = layered(&[]);
+        assert_eq!(layered(&[]).assert_no_new_cycles(&baseline), Ok(()));
+
+        let current = layered(&[("db", "ui")]);
+        let violations = current.assert_no_new_cycles(&baseline).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        match &violations[0] {
+            Violation::NewCycle { members, cycle } => {
+                assert_eq!(members.len(), 4);
+                assert_eq!(cycle.first(), cycle.last());
+            }
+            v => panic!("unexpected {v:?}"),
+        }
+        assert!(violations[0].to_string().starts_with("new cycle: "));
+    }
+
+    #[test]
+    fn test_assert_edge_policy() {
+        let g = layered(&[("db", "ui")]);
+        let rank = |u: &&str| ["ui", "service", "cache", "db"].iter().position(|x| x == u);
+
+        // 上位の層から下位の層へのエッジのみ許す (service ⇄ cache は例外)
+        let violations = g
+            .assert_edge_policy(|a, b| rank(a) < rank(b) || (*a, *b) == ("cache", "service"))
+            .unwrap_err();
+        assert_eq!(
+            violations,
+            vec![Violation::ForbiddenEdge {
+                from: &"db",
+                to: &"ui"
+            }]
+        );
+        assert_eq!(violations[0].to_string(), "forbidden edge: db -> ui");
+        assert!(g.assert_edge_policy(|_, _| true).is_ok());
+    }
+}
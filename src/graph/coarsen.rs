@@ -0,0 +1,231 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    rng::{Rng, XorShift64},
+};
+
+/// `coarsen` でどのノード同士をまとめるか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchingStrategy {
+    // 両方向の重みの和が最も大きい隣接ノードと組む (heavy-edge matching)
+    HeavyEdge,
+    // ランダムな順にノードを見て，まだ組んでいない隣接ノードをランダムに選ぶ
+    Random { seed: u64 },
+}
+
+impl GraphCore {
+    // `order` の順にノードを見て，まだ組んでいない隣接ノード (向きは無視) のうち
+    // `score` が最大のものと組ませる．組めなかったノードは 1 つだけのグループになる．
+    // グループは最初のメンバーを見た順に並ぶ
+    pub fn greedy_matching<F>(&self, order: &[NodeID], mut score: F) -> Vec<Vec<NodeID>>
+    where
+        F: FnMut(NodeID, NodeID) -> f64,
+    {
+        let mut matched: HashSet<NodeID> = HashSet::new();
+        let mut ret = Vec::new();
+
+        for &u in order {
+            if matched.contains(&u) {
+                continue;
+            }
+            matched.insert(u);
+
+            let n = &self.nodes_dict[&u];
+            let mut candidates: Vec<NodeID> = n
+                .children
+                .union(&n.parents)
+                .copied()
+                .filter(|v| !matched.contains(v))
+                .collect();
+            candidates.sort_unstable();
+
+            let mut best: Option<(f64, NodeID)> = None;
+            for v in candidates {
+                let s = score(u, v);
+                if best.is_none_or(|(b, _)| s > b) {
+                    best = Some((s, v));
+                }
+            }
+            match best {
+                Some((_, v)) => {
+                    matched.insert(v);
+                    ret.push(vec![u, v]);
+                }
+                None => ret.push(vec![u]),
+            }
+        }
+
+        ret
+    }
+}
+
+/// `coarsen` の結果．`graph` のノード i は `members[i]` をまとめたスーパーノード
+pub struct Coarsening<'a, T: PartialEq + Eq + Hash> {
+    pub graph: Graph<usize>,
+    pub members: Vec<Vec<&'a T>>,
+    source: &'a Graph<T>,
+    supernode_of: HashMap<NodeID, usize>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> Coarsening<'a, T> {
+    // ノードが属するスーパーノード
+    pub fn supernode_of<Q>(&self, u: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.source.node_id(u)?;
+        self.supernode_of.get(id).copied()
+    }
+
+    // 粗いグラフの上で計算したノードごとの値 (レイアウト座標，スコアなど) を
+    // 元のノードに写す．値の無いスーパーノードのメンバーは含まれない
+    pub fn refine<V: Clone>(&self, values: &HashMap<&usize, V>) -> HashMap<&'a T, V> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ms)| values.get(&i).map(|v| (ms, v)))
+            .flat_map(|(ms, v)| ms.iter().map(move |&u| (u, v.clone())))
+            .collect()
+    }
+
+    // 粗いグラフの分割 (クラスタなど) を元のノードの分割に戻す
+    pub fn refine_partition(&self, parts: &[Vec<&usize>]) -> Vec<Vec<&'a T>> {
+        parts
+            .iter()
+            .map(|part| {
+                part.iter()
+                    .flat_map(|&&i| self.members[i].iter().copied())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 隣接するノードを 2 つずつまとめて小さいグラフを作る (マルチレベル分割やレイアウトの前処理)．
+    // スーパーノード間のエッジの重みは元のエッジの重みの和で，スーパーノード内のエッジは捨てる
+    pub fn coarsen(&self, strategy: MatchingStrategy) -> Coarsening<'_, T> {
+        let mut order: Vec<NodeID> = self.core.nodes_dict.keys().copied().collect();
+        order.sort_unstable();
+
+        let groups = match strategy {
+            MatchingStrategy::HeavyEdge => self.core.greedy_matching(&order, |u, v| {
+                let w = |a, b| {
+                    if self.core.nodes_dict[&a].children.contains(&b) {
+                        self.weight_of(a, b)
+                    } else {
+                        0.0
+                    }
+                };
+                w(u, v) + w(v, u)
+            }),
+            MatchingStrategy::Random { seed } => {
+                let mut rng = XorShift64::new(seed);
+                // Fisher–Yates
+                for i in (1..order.len()).rev() {
+                    let j = rng.gen_index(i + 1);
+                    order.swap(i, j);
+                }
+                self.core.greedy_matching(&order, |_, _| rng.next_f64())
+            }
+        };
+
+        let mut supernode: HashMap<NodeID, usize> = HashMap::new();
+        for (i, group) in groups.iter().enumerate() {
+            for &id in group {
+                supernode.insert(id, i);
+            }
+        }
+
+        let mut g = Graph::new();
+        for i in 0..groups.len() {
+            let _ = g.add_node(i);
+        }
+        let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+        for (&id, n) in &self.core.nodes_dict {
+            for &child in &n.children {
+                let (a, b) = (supernode[&id], supernode[&child]);
+                if a != b {
+                    *weights.entry((a, b)).or_insert(0.0) += self.weight_of(id, child);
+                }
+            }
+        }
+        for ((a, b), w) in weights {
+            let _ = g.add_weighted_edge(&a, &b, w);
+        }
+
+        let nodes = self.nodes_by_id();
+        Coarsening {
+            graph: g,
+            members: groups
+                .iter()
+                .map(|group| group.iter().map(|id| nodes[id]).collect())
+                .collect(),
+            source: self,
+            supernode_of: supernode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::super::graph::Graph;
+    use super::MatchingStrategy;
+
+    fn sample() -> Graph<&'static str> {
+        // A =5= B -1- C =5= D (両方向の和)，D → A は 0.5
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 5.0);
+        let _ = g.add_weighted_edge(&"B", &"C", 1.0);
+        let _ = g.add_weighted_edge(&"C", &"D", 2.0);
+        let _ = g.add_weighted_edge(&"D", &"C", 3.0);
+        let _ = g.add_weighted_edge(&"D", &"A", 0.5);
+        g
+    }
+
+    #[test]
+    fn test_coarsen_heavy_edge() {
+        let g = sample();
+        let c = g.coarsen(MatchingStrategy::HeavyEdge);
+
+        assert_eq!(c.members, vec![vec![&"A", &"B"], vec![&"C", &"D"]]);
+        assert_eq!(c.supernode_of(&"D"), Some(1));
+        assert_eq!(c.graph.edge_weight(&0, &1), Some(1.0));
+        assert_eq!(c.graph.edge_weight(&1, &0), Some(0.5));
+
+        let score: HashMap<&usize, f64> = HashMap::from([(&0, 0.25), (&1, 0.75)]);
+        let refined = c.refine(&score);
+        assert_eq!(refined[&"B"], 0.25);
+        assert_eq!(refined[&"C"], 0.75);
+        assert_eq!(
+            c.refine_partition(&[vec![&1], vec![&0]]),
+            vec![vec![&"C", &"D"], vec![&"A", &"B"]]
+        );
+    }
+
+    #[test]
+    fn test_coarsen_random() {
+        let g = sample();
+        let c = g.coarsen(MatchingStrategy::Random { seed: 7 });
+
+        // どの組み合わせでもすべてのノードがちょうど 1 回ずつ現れる
+        let mut all: Vec<&str> = c.members.iter().flatten().map(|u| **u).collect();
+        all.sort_unstable();
+        assert_eq!(all, vec!["A", "B", "C", "D"]);
+        assert!(c.members.iter().all(|m| m.len() <= 2));
+        assert_eq!(c.graph.node_count(), c.members.len());
+    }
+}
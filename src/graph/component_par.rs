@@ -0,0 +1,96 @@
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use super::{error::GraphError, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 弱連結成分ごとの部分グラフに `analysis` を `threads` 本のスレッドで並列に適用し，
+    // 結果を成分の順 (weakly_connected_components と同じ) に並べて返す．
+    // 大きい成分から先に割り当てるので，巨大な成分が最後に残って待たされることは少ない
+    pub fn for_each_component_par<R, F>(
+        &self,
+        threads: usize,
+        analysis: F,
+    ) -> Result<Vec<R>, GraphError>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&Graph<&T>) -> R + Sync,
+    {
+        if threads == 0 {
+            return Err(GraphError::InvalidArgument(
+                "thread count must be positive".to_string(),
+            ));
+        }
+
+        let components = self.core.weakly_connected_components();
+        let mut by_size: Vec<usize> = (0..components.len()).collect();
+        by_size.sort_by_key(|&i| std::cmp::Reverse(components[i].len()));
+
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<R>>> =
+            Mutex::new((0..components.len()).map(|_| None).collect());
+
+        thread::scope(|s| {
+            for _ in 0..threads.min(components.len()) {
+                s.spawn(|| {
+                    while let Some(&i) = by_size.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let sub = self.induced_subgraph(&components[i], |u| u);
+                        let r = analysis(&sub);
+                        results.lock().unwrap()[i] = Some(r);
+                    }
+                });
+            }
+        });
+
+        Ok(results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.unwrap()) // すべての成分を処理し終えている
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_for_each_component_par() {
+        // {A → B → C → A}, {D → E (重み 2)}, {F}
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "B"), ("B", "C"), ("C", "A")] {
+            let _ = g.add_edge(&a, &b);
+        }
+        let _ = g.add_weighted_edge(&"D", &"E", 2.0);
+
+        let results = g
+            .for_each_component_par(2, |sub| {
+                let total: f64 = sub
+                    .edges()
+                    .map(|(a, b)| sub.edge_weight(a, b).unwrap())
+                    .sum();
+                (sub.node_count(), sub.detect_cycle().is_some(), total)
+            })
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![(3, true, 3.0), (2, false, 2.0), (1, false, 0.0)]
+        );
+
+        // 結果はスレッド数によらない
+        let sizes = g.for_each_component_par(8, |sub| sub.node_count());
+        assert_eq!(sizes, Ok(vec![3, 2, 1]));
+        assert!(g.for_each_component_par(0, |sub| sub.node_count()).is_err());
+    }
+}
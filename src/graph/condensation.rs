@@ -0,0 +1,140 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // 強連結成分の所属 (ノード → 成分番号) と，成分を縮約した DAG の隣接リスト．
+    // 成分番号は strongly_connected_components の順 (逆トポロジカル順) で，
+    // エッジ i → j (i ≠ j) について常に j < i となる
+    pub fn condensation(&self) -> (HashMap<NodeID, usize>, Vec<Vec<usize>>) {
+        let sccs = self.strongly_connected_components();
+        let mut component = HashMap::new();
+        for (i, c) in sccs.iter().enumerate() {
+            for &id in c {
+                component.insert(id, i);
+            }
+        }
+
+        let dag = sccs
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let succ: HashSet<usize> = c
+                    .iter()
+                    .flat_map(|&id| self.nodes_dict[&id].children.iter())
+                    .map(|child| component[child])
+                    .filter(|&j| j != i)
+                    .collect();
+                let mut succ: Vec<usize> = succ.into_iter().collect();
+                succ.sort_unstable();
+                succ
+            })
+            .collect();
+
+        (component, dag)
+    }
+}
+
+/// 強連結成分を 1 つのノードに縮約した DAG
+///
+/// `dag` のノード i は `components[i]` の成分を表す．番号はトポロジカル順
+/// (エッジ i → j なら i < j) に振ってある
+pub struct Condensation<'a, T: PartialEq + Eq + Hash> {
+    pub dag: Graph<usize>,
+    pub components: Vec<Vec<&'a T>>,
+    graph: &'a Graph<T>,
+    component_of: HashMap<NodeID, usize>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> Condensation<'a, T> {
+    // ノードが属する成分の番号
+    pub fn component_of<Q>(&self, u: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.graph.node_id(u)?;
+        self.component_of.get(id).copied()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 強連結成分を縮約した DAG を作る．成分をまたぐエッジは 1 本にまとめる (重みは持たない)
+    pub fn condensation(&self) -> Condensation<'_, T> {
+        let (component, dag) = self.core.condensation();
+        let k = dag.len();
+        // 逆トポロジカル順をトポロジカル順に並べ替える
+        let topo = |i: usize| k - 1 - i;
+
+        let nodes = self.nodes_by_id();
+        let mut components: Vec<Vec<NodeID>> = vec![Vec::new(); k];
+        for (&id, &c) in &component {
+            components[topo(c)].push(id);
+        }
+        for c in components.iter_mut() {
+            c.sort_unstable();
+        }
+
+        let mut g = Graph::new();
+        for i in 0..k {
+            let _ = g.add_node(i);
+        }
+        for (i, succ) in dag.iter().enumerate() {
+            for &j in succ {
+                let _ = g.add_edge(&topo(i), &topo(j));
+            }
+        }
+
+        Condensation {
+            dag: g,
+            graph: self,
+            component_of: component.iter().map(|(&id, &c)| (id, topo(c))).collect(),
+            components: components
+                .iter()
+                .map(|c| c.iter().map(|id| nodes[id]).collect())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_condensation() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"D", &"C");
+        let _ = g.add_edge(&"A", &"D");
+        let _ = g.add_edge(&"E", &"E");
+
+        let c = g.condensation();
+        assert_eq!(c.components.len(), 3);
+        assert_eq!(c.dag.detect_cycle(), None);
+
+        let ab = c.component_of(&"A").unwrap();
+        let cd = c.component_of(&"D").unwrap();
+        assert_eq!(c.component_of(&"B"), Some(ab));
+        assert_eq!(c.components[ab], vec![&"A", &"B"]);
+        assert_eq!(c.components[cd], vec![&"C", &"D"]);
+        assert!(ab < cd);
+        // A → D と B → C は 1 本にまとまる
+        assert_eq!(c.dag.edge_weight(&ab, &cd), Some(1.0));
+        assert_eq!(c.dag.edge_weight(&cd, &ab), None);
+        assert_eq!(c.component_of(&"Z"), None);
+    }
+}
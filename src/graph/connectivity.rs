@@ -0,0 +1,230 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    union_find::UnionFind,
+};
+
+impl GraphCore {
+    // エッジの向きを無視した連結成分 (各成分は ID の昇順，成分は先頭の ID の昇順)
+    pub fn weakly_connected_components(&self) -> Vec<Vec<NodeID>> {
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        let index: HashMap<NodeID, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut uf = UnionFind::new(ids.len());
+        for (&id, n) in self.nodes_dict.iter() {
+            for child in &n.children {
+                if let Some(&j) = index.get(child) {
+                    uf.union(index[&id], j);
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<NodeID>> = HashMap::new();
+        for (i, &id) in ids.iter().enumerate() {
+            groups.entry(uf.find(i)).or_default().push(id);
+        }
+
+        let mut ret: Vec<Vec<NodeID>> = groups.into_values().collect();
+        ret.sort_unstable_by_key(|c| c[0]);
+        ret
+    }
+}
+
+enum Op {
+    RemoveEdge(Option<(NodeID, NodeID)>),
+    Query(Option<(NodeID, NodeID)>),
+}
+
+/// エッジ削除と連結性の問い合わせを記録し，まとめて (オフラインで) 答える
+///
+/// 連結性はエッジの向きを無視して判定する．記録した操作を逆順にたどり，
+/// 削除されたエッジを素集合に戻しながら問い合わせに答える
+pub struct OfflineConnectivity<'a, T: PartialEq + Eq + Hash> {
+    graph: &'a Graph<T>,
+    ops: Vec<Op>,
+    query_count: usize,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> OfflineConnectivity<'a, T> {
+    fn ids<Q>(&self, a: &Q, b: &Q) -> Option<(NodeID, NodeID)>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Some((*self.graph.node_id(a)?, *self.graph.node_id(b)?))
+    }
+
+    // エッジ a → b の削除を記録する．存在しないエッジや削除済みのエッジは無視される
+    pub fn remove_edge<Q>(&mut self, a: &Q, b: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ops.push(Op::RemoveEdge(self.ids(a, b)));
+    }
+
+    // その時点で a と b が連結かの問い合わせを記録し，`solve` の結果での位置を返す
+    pub fn query<Q>(&mut self, a: &Q, b: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ops.push(Op::Query(self.ids(a, b)));
+        self.query_count += 1;
+        self.query_count - 1
+    }
+
+    // 記録した順に問い合わせの答えを返す
+    pub fn solve(&self) -> Vec<bool> {
+        let core = &self.graph.core;
+        let mut index: HashMap<NodeID, usize> = HashMap::new();
+        for &id in core.nodes_dict.keys() {
+            let i = index.len();
+            index.insert(id, i);
+        }
+
+        let mut present: HashSet<(NodeID, NodeID)> = HashSet::new();
+        for (&id, n) in core.nodes_dict.iter() {
+            for &child in &n.children {
+                present.insert((id, child));
+            }
+        }
+
+        // 実際に効いた削除だけを残す
+        let mut effective = vec![false; self.ops.len()];
+        for (i, op) in self.ops.iter().enumerate() {
+            if let Op::RemoveEdge(Some(e)) = op {
+                effective[i] = present.remove(e);
+            }
+        }
+
+        let mut uf = UnionFind::new(index.len());
+        for (a, b) in present.iter() {
+            uf.union(index[a], index[b]);
+        }
+
+        let mut answers = vec![false; self.query_count];
+        let mut q = self.query_count;
+        for (i, op) in self.ops.iter().enumerate().rev() {
+            match op {
+                Op::RemoveEdge(Some((a, b))) if effective[i] => {
+                    uf.union(index[a], index[b]);
+                }
+                Op::Query(e) => {
+                    q -= 1;
+                    answers[q] = e.is_some_and(|(a, b)| uf.connected(index[&a], index[&b]));
+                }
+                _ => {}
+            }
+        }
+
+        answers
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // エッジの向きを無視した連結成分 (各成分はノードを追加した順)．成分の数がそのまま
+    // 互いに素な部分グラフの数になる
+    pub fn weakly_connected_components(&self) -> Vec<Vec<&T>> {
+        let nodes = self.nodes_by_id();
+
+        self.core
+            .weakly_connected_components()
+            .into_iter()
+            .map(|c| c.iter().map(|id| nodes[id]).collect())
+            .collect()
+    }
+
+    // エッジ削除の下での連結性問い合わせをまとめて処理する
+    pub fn offline_connectivity(&self) -> OfflineConnectivity<'_, T> {
+        OfflineConnectivity {
+            graph: self,
+            ops: Vec::new(),
+            query_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_weakly_connected_components() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"C", &"B"); // 向きは無視される
+        let _ = g.add_edge(&"D", &"D");
+
+        assert_eq!(
+            g.weakly_connected_components(),
+            vec![vec![&"A", &"B", &"C"], vec![&"D"], vec![&"E"]]
+        );
+        assert!(Graph::<u32>::new().weakly_connected_components().is_empty());
+    }
+
+    #[test]
+    fn test_offline_connectivity() {
+        // A - B - C - D と A - C
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"A", &"C");
+
+        let mut oc = g.offline_connectivity();
+        let q0 = oc.query(&"D", &"A"); // 向きは無視する
+        oc.remove_edge(&"C", &"D");
+        let q1 = oc.query(&"A", &"D");
+        let q2 = oc.query(&"A", &"C");
+        oc.remove_edge(&"A", &"C");
+        let q3 = oc.query(&"A", &"C");
+        oc.remove_edge(&"B", &"C");
+        oc.remove_edge(&"B", &"C"); // 二重の削除は無視
+        let q4 = oc.query(&"A", &"C");
+        let q5 = oc.query(&"A", &"E");
+        let q6 = oc.query(&"A", &"Z");
+
+        let answers = oc.solve();
+        assert_eq!(answers.len(), 7);
+        assert!(answers[q0]);
+        assert!(!answers[q1]);
+        assert!(answers[q2]);
+        assert!(answers[q3]);
+        assert!(!answers[q4]);
+        assert!(!answers[q5]);
+        assert!(!answers[q6]);
+    }
+
+    #[test]
+    fn test_offline_connectivity_parallel_directions() {
+        // A ⇄ B は片方を消しても連結のまま
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+
+        let mut oc = g.offline_connectivity();
+        oc.remove_edge(&"A", &"B");
+        oc.query(&"A", &"B");
+        oc.remove_edge(&"B", &"A");
+        oc.query(&"A", &"B");
+
+        assert_eq!(oc.solve(), vec![true, false]);
+    }
+}
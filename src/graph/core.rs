@@ -1,14 +1,34 @@
 use std::collections::{HashMap, HashSet};
 
+use super::{acyclic::TopoOrder, error::GraphError};
+
 pub type NodeID = usize;
 
+/// NodeID をそのまま扱う下位の層．NodeID の割り当てと使い回しは持ち主 (Graph や UsizeGraph) が
+/// 管理し，Graph は外に NodeID ではなく世代付きの NodeHandle を渡す
+#[derive(Debug, Clone)]
 pub struct GraphCore {
     pub nodes_dict: HashMap<NodeID, Node>,
+    // maintain_topological_order (add_edge_acyclic) で作るトポロジカル順．作った後はノードとエッジの追加に合わせて更新し，
+    // サイクルができたら捨てる
+    pub(crate) topo: Option<TopoOrder>,
+}
+
+// トポロジカル順は比べない
+impl PartialEq for GraphCore {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes_dict == other.nodes_dict
+    }
 }
 
+impl Eq for GraphCore {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Node {
     id: NodeID,
     pub children: HashSet<NodeID>,
+    // 逆向きの隣接 (このノードを子に持つノード)．GraphCore のエッジ操作で children と一緒に更新する
+    pub parents: HashSet<NodeID>,
 }
 
 impl Node {
@@ -16,6 +36,7 @@ impl Node {
         Self {
             id,
             children: HashSet::new(),
+            parents: HashSet::new(),
         }
     }
 
@@ -28,72 +49,150 @@ impl Node {
     }
 }
 
+impl Default for GraphCore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GraphCore {
     pub fn new() -> Self {
         Self {
             nodes_dict: HashMap::new(),
+            topo: None,
         }
     }
 
     // 使用するノードを登録する
-    pub fn add_node(&mut self, new_id: NodeID) -> Result<(), String> {
+    pub fn add_node(&mut self, new_id: NodeID) -> Result<(), GraphError> {
         let old_node = self.nodes_dict.insert(new_id, Node::new(new_id));
 
         match &old_node {
-            Some(n) => Err(format!("duplication: node {} is already added", n.id)),
-            None => Ok(()),
+            Some(n) => Err(GraphError::DuplicateNodeId { id: n.id }),
+            None => {
+                if let Some(topo) = &mut self.topo {
+                    topo.push(new_id);
+                }
+                Ok(())
+            }
         }
     }
 
     // すでにエッジが登録されている場合 false が返される (ただし，複数のエッジとして登録はされる)
-    pub fn add_edge(&mut self, from_id: NodeID, to_id: NodeID) -> Result<bool, String> {
+    pub fn add_edge(&mut self, from_id: NodeID, to_id: NodeID) -> Result<bool, GraphError> {
         let node = self.nodes_dict.get_mut(&from_id).unwrap(); // add_node メソッドを介してしか追加されずその際に Node は作られている
-        return Ok(node.add_edge(to_id));
+        let ret = node.add_edge(to_id);
+
+        if let Some(to) = self.nodes_dict.get_mut(&to_id) {
+            to.parents.insert(from_id);
+        }
+        if let Some(mut topo) = self.topo.take() {
+            if topo.insert_edge(self, from_id, to_id).is_ok() {
+                self.topo = Some(topo);
+            }
+        }
+        Ok(ret)
+    }
+
+    // エッジを削除する．削除した場合 true
+    pub fn remove_edge(&mut self, from_id: NodeID, to_id: NodeID) -> bool {
+        let ret = match self.nodes_dict.get_mut(&from_id) {
+            Some(node) => node.children.remove(&to_id),
+            None => false,
+        };
+
+        if let Some(to) = self.nodes_dict.get_mut(&to_id) {
+            to.parents.remove(&from_id);
+        }
+        ret
     }
 
+    // ノードとそれにつながるエッジを削除する．削除した場合 true．
+    // NodeID を使い回すかどうかは呼び出し側 (Graph) が決める
+    pub fn remove_node(&mut self, id: NodeID) -> bool {
+        let Some(n) = self.nodes_dict.remove(&id) else {
+            return false;
+        };
+        for child in &n.children {
+            if let Some(c) = self.nodes_dict.get_mut(child) {
+                c.parents.remove(&id);
+            }
+        }
+        for parent in &n.parents {
+            if let Some(p) = self.nodes_dict.get_mut(parent) {
+                p.children.remove(&id);
+            }
+        }
+        // ノードを除いてもトポロジカル順は崩れない
+        if let Some(topo) = &mut self.topo {
+            topo.remove(id);
+        }
+        true
+    }
+
+    // `start` から深さ優先でたどり，探索中のパスに戻るエッジがあればサイクルを `cycle` に入れる．
+    // 長い鎖でもスタックが溢れないよう，再帰せず明示的なスタックを使う
     fn has_cycle_dfs(
         &self,
-        node: NodeID,
+        start: NodeID,
         visited: &mut HashSet<NodeID>,
-        rec_stack: &mut Vec<NodeID>,
         cycle: &mut Vec<NodeID>,
+        skip_self_loops: bool,
     ) -> bool {
-        if let Some(pos) = rec_stack.iter().position(|&x| x == node) {
-            // サイクル発見: `rec_stack` からサイクル部分を取り出す
-            *cycle = rec_stack[pos..].to_vec();
-            cycle.push(node);
-            return true;
-        }
-
-        if visited.contains(&node) {
+        let Some(n) = self.nodes_dict.get(&start) else {
             return false;
-        }
+        };
+        visited.insert(start);
 
-        visited.insert(node);
-        rec_stack.push(node);
+        // 探索中のパス (ノードと，まだ見ていない子)
+        let mut stack = vec![(start, n.children.iter())];
+        let mut on_stack: HashSet<NodeID> = HashSet::from([start]);
 
-        if let Some(n) = self.nodes_dict.get(&node) {
-            for &neighbor in &n.children {
-                if self.has_cycle_dfs(neighbor, visited, rec_stack, cycle) {
-                    return true;
+        while let Some((node, children)) = stack.last_mut() {
+            let Some(&next) = children.next() else {
+                on_stack.remove(node);
+                stack.pop(); // 探索が終わったら戻す
+                continue;
+            };
+            if skip_self_loops && next == *node {
+                continue;
+            }
+
+            if on_stack.contains(&next) {
+                // サイクル発見: スタックからサイクル部分を取り出す
+                let pos = stack.iter().position(|&(x, _)| x == next).unwrap();
+                *cycle = stack[pos..].iter().map(|&(x, _)| x).collect();
+                cycle.push(next);
+                return true;
+            }
+            if visited.insert(next) {
+                if let Some(n) = self.nodes_dict.get(&next) {
+                    on_stack.insert(next);
+                    stack.push((next, n.children.iter()));
                 }
             }
         }
-
-        rec_stack.pop(); // 探索が終わったら戻す
         false
     }
 
     pub fn detect_cycle(&self) -> Option<Vec<NodeID>> {
+        self.detect_cycle_inner(false)
+    }
+
+    // 自己ループをサイクルとみなさない detect_cycle
+    pub fn detect_cycle_ignoring_self_loops(&self) -> Option<Vec<NodeID>> {
+        self.detect_cycle_inner(true)
+    }
+
+    fn detect_cycle_inner(&self, skip_self_loops: bool) -> Option<Vec<NodeID>> {
         let mut visited = HashSet::new();
-        let mut rec_stack = Vec::new();
         let mut cycle = Vec::new();
 
         for &node in self.nodes_dict.keys() {
-            if !visited.contains(&node) {
-                if self.has_cycle_dfs(node, &mut visited, &mut rec_stack, &mut cycle) {
-                    return Some(cycle);
-                }
+            if !visited.contains(&node)
+                && self.has_cycle_dfs(node, &mut visited, &mut cycle, skip_self_loops)
+            {
+                return Some(cycle);
             }
         }
         None
@@ -126,8 +225,9 @@ impl GraphCore {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
-    use super::Node;
+    use super::{GraphCore, Node};
 
     #[test]
     fn test_node_add_edge() {
@@ -150,4 +250,25 @@ mod tests {
             assert_eq!(n.add_edge(0), true);
         }
     }
+
+    #[test]
+    fn test_detect_cycle_long_chain() {
+        // 再帰だとスタックが溢れる長さの鎖
+        let n = 100_000;
+        let mut core = GraphCore::new();
+        for id in 0..n {
+            let _ = core.add_node(id);
+        }
+        for id in 1..n {
+            let _ = core.add_edge(id - 1, id);
+        }
+        assert_eq!(core.detect_cycle(), None);
+
+        let _ = core.add_edge(n - 1, n - 3);
+        // 探索の開始位置によってサイクルの始点は変わる
+        let cycle = core.detect_cycle().unwrap();
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.iter().all(|id| *id >= n - 3));
+    }
 }
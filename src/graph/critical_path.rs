@@ -0,0 +1,367 @@
+use std::{borrow::Borrow, cmp::Ordering, collections::HashMap, fmt::Debug, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    path::Path,
+    weight::Weight,
+};
+
+/// 1 つのノード (作業) の日程
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeTiming<'a, T, W = f64> {
+    pub node: &'a T,
+    pub earliest_start: W,
+    pub earliest_finish: W,
+    pub latest_start: W,
+    pub latest_finish: W,
+    // 全体の完了を遅らせずに遅らせられる時間 (latest_start - earliest_start)
+    pub slack: W,
+}
+
+/// `critical_path` の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath<'a, T, W = f64> {
+    // 全体の所要時間
+    pub duration: W,
+    // 余裕の無いノードをたどるパス．重みは全体の所要時間
+    pub path: Path<'a, T, W>,
+    // ノードごとの日程 (ノードの追加順)
+    pub timings: Vec<NodeTiming<'a, T, W>>,
+}
+
+impl<T: PartialEq, W> CriticalPath<'_, T, W> {
+    pub fn timing<Q>(&self, u: &Q) -> Option<&NodeTiming<'_, T, W>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.timings.iter().find(|t| t.node.borrow() == u)
+    }
+}
+
+// 差が W::epsilon() 以下か (浮動小数点数の丸め誤差を吸収する)
+fn is_near<W: Weight>(a: W, b: W) -> bool {
+    let (hi, lo) = match a.cmp_weight(&b) {
+        Ordering::Less => (b, a),
+        _ => (a, b),
+    };
+    hi.checked_sub(lo)
+        .is_some_and(|d| d.cmp_weight(&W::epsilon()) != Ordering::Greater)
+}
+
+fn max_weight<W: Weight>(a: W, b: W) -> W {
+    match a.cmp_weight(&b) {
+        Ordering::Less => b,
+        _ => a,
+    }
+}
+
+fn min_weight<W: Weight>(a: W, b: W) -> W {
+    match a.cmp_weight(&b) {
+        Ordering::Greater => b,
+        _ => a,
+    }
+}
+
+impl GraphCore {
+    // クリティカルパス法．ノード v の所要時間を duration(v)，エッジ u → v の待ち時間を lag(u, v) として
+    // 各ノードの (最早開始, 最遅開始) と，余裕の無いノードをたどるパスを求める．
+    // `order` はトポロジカル順であること．時刻を足して桁あふれしたら，そのエッジ
+    // (ノードの終了時刻なら (そのノード, そのノード)) をエラーとして返す
+    #[allow(clippy::type_complexity)]
+    pub fn critical_path_by<W, F, G>(
+        &self,
+        order: &[NodeID],
+        duration: F,
+        lag: G,
+    ) -> Result<(HashMap<NodeID, (W, W)>, Vec<NodeID>), (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID) -> W,
+        G: Fn(NodeID, NodeID) -> W,
+    {
+        let mut earliest: HashMap<NodeID, W> = HashMap::new();
+        let mut finish: HashMap<NodeID, W> = HashMap::new();
+        for &id in order {
+            let mut es = W::zero();
+            for &p in &self.nodes_dict[&id].parents {
+                let arrival = finish[&p].checked_add(lag(p, id)).ok_or((p, id))?;
+                es = max_weight(es, arrival);
+            }
+            earliest.insert(id, es);
+            finish.insert(id, es.checked_add(duration(id)).ok_or((id, id))?);
+        }
+        let total = order
+            .iter()
+            .map(|id| finish[id])
+            .fold(W::zero(), max_weight);
+
+        let mut latest: HashMap<NodeID, W> = HashMap::new();
+        for &id in order.iter().rev() {
+            let mut lf = total;
+            for &c in &self.nodes_dict[&id].children {
+                let departure = latest[&c].checked_sub(lag(id, c)).ok_or((id, c))?;
+                lf = min_weight(lf, departure);
+            }
+            latest.insert(id, lf.checked_sub(duration(id)).ok_or((id, id))?);
+        }
+
+        // 開始時刻 0 の余裕の無いノードから，ぎりぎりでつながる余裕の無い子をたどる
+        let critical = |id: &NodeID| is_near(latest[id], earliest[id]);
+        let mut path = Vec::new();
+        let mut cur = order
+            .iter()
+            .copied()
+            .filter(|id| is_near(earliest[id], W::zero()) && critical(id))
+            .min();
+        while let Some(id) = cur {
+            path.push(id);
+            cur = self.nodes_dict[&id]
+                .children
+                .iter()
+                .copied()
+                .filter(|c| {
+                    critical(c)
+                        && finish[&id]
+                            .checked_add(lag(id, *c))
+                            .is_some_and(|arrival| is_near(earliest[c], arrival))
+                })
+                .min();
+        }
+
+        let times = order
+            .iter()
+            .map(|&id| (id, (earliest[&id], latest[&id])))
+            .collect();
+        Ok((times, path))
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // エッジを作業，重みを所要時間とみなしたクリティカルパス (ノードは所要時間 0 のイベント)
+    pub fn critical_path(&self) -> Result<CriticalPath<'_, T>, GraphError> {
+        for (&(a, b), &w) in &self.weights {
+            if !(w.is_finite() && w >= 0.0) {
+                return Err(GraphError::InvalidArgument(format!(
+                    "duration of edge #{} -> #{} must be finite and non-negative, got {}",
+                    a, b, w
+                )));
+            }
+        }
+        self.critical_path_impl(|_| 0.0, |a, b| self.weight_of(a, b))
+    }
+
+    // ノードを作業，`duration` をその所要時間とみなしたクリティカルパス (エッジは順序の制約のみ)
+    pub fn critical_path_with_durations<F>(
+        &self,
+        duration: F,
+    ) -> Result<CriticalPath<'_, T>, GraphError>
+    where
+        F: Fn(&T) -> f64,
+    {
+        let nodes = self.nodes_by_id();
+        for (&id, u) in &nodes {
+            let d = duration(u);
+            if !(d.is_finite() && d >= 0.0) {
+                return Err(GraphError::InvalidArgument(format!(
+                    "duration of node #{} must be finite and non-negative, got {}",
+                    id, d
+                )));
+            }
+        }
+        self.critical_path_impl(|id| duration(nodes[&id]), |_, _| 0.0)
+    }
+
+    // ノードの所要時間を `duration`，エッジの待ち時間を `lag` で与えるクリティカルパス
+    // (u32 や Duration なども使える)．負の時間は InvalidArgument，時刻が桁あふれしたら WeightOverflow
+    pub fn critical_path_by<W, F, G>(
+        &self,
+        duration: F,
+        lag: G,
+    ) -> Result<CriticalPath<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(&T) -> W,
+        G: Fn(&T, &T) -> W,
+    {
+        let nodes = self.nodes_by_id();
+        self.critical_path_impl(|id| duration(nodes[&id]), |a, b| lag(nodes[&a], nodes[&b]))
+    }
+
+    fn critical_path_impl<W, F, G>(
+        &self,
+        duration: F,
+        lag: G,
+    ) -> Result<CriticalPath<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(NodeID) -> W,
+        G: Fn(NodeID, NodeID) -> W,
+    {
+        let Some(order) = self.core.topological_order() else {
+            return Err(GraphError::CycleDetected {
+                cycle: self.handles(&self.core.detect_cycle().unwrap()),
+            });
+        };
+
+        let negative = |w: &W| w.cmp_weight(&W::zero()) == Ordering::Less;
+        for &id in &order {
+            let d = duration(id);
+            if negative(&d) {
+                return Err(GraphError::InvalidArgument(format!(
+                    "duration of node #{} must be non-negative, got {:?}",
+                    id, d
+                )));
+            }
+            for &child in &self.core.nodes_dict[&id].children {
+                let l = lag(id, child);
+                if negative(&l) {
+                    return Err(GraphError::InvalidArgument(format!(
+                        "duration of edge #{} -> #{} must be non-negative, got {:?}",
+                        id, child, l
+                    )));
+                }
+            }
+        }
+
+        let overflow = |(a, b)| GraphError::WeightOverflow {
+            from: self.handle(a),
+            to: self.handle(b),
+        };
+        let (times, path) = self
+            .core
+            .critical_path_by(&order, &duration, &lag)
+            .map_err(overflow)?;
+
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = order.clone();
+        ids.sort_unstable();
+        let mut timings: Vec<NodeTiming<'_, T, W>> = Vec::with_capacity(ids.len());
+        for id in ids {
+            let (es, ls) = times[&id];
+            let d = duration(id);
+            timings.push(NodeTiming {
+                node: nodes[&id],
+                earliest_start: es,
+                earliest_finish: es.checked_add(d).ok_or_else(|| overflow((id, id)))?,
+                latest_start: ls,
+                latest_finish: ls.checked_add(d).ok_or_else(|| overflow((id, id)))?,
+                // 最遅開始は最早開始以上 (浮動小数点数では丸め誤差で少し負になりうる)
+                slack: ls.checked_sub(es).unwrap_or(W::zero()),
+            });
+        }
+        let total = timings
+            .iter()
+            .map(|t| t.earliest_finish)
+            .fold(W::zero(), max_weight);
+
+        Ok(CriticalPath {
+            duration: total,
+            path: Path::new(self.nodes_from_ids(&path), total),
+            timings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    #[test]
+    fn test_critical_path_with_durations() {
+        // 設計(3) → 実装(5) → テスト(2),  設計 → 文書(4) → テスト
+        let mut g = Graph::new();
+        for u in ["design", "impl", "docs", "test"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("design", "impl"),
+            ("impl", "test"),
+            ("design", "docs"),
+            ("docs", "test"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+        let duration = |u: &&str| match *u {
+            "design" => 3.0,
+            "impl" => 5.0,
+            "docs" => 4.0,
+            _ => 2.0,
+        };
+
+        let cp = g.critical_path_with_durations(duration).unwrap();
+        assert_eq!(cp.duration, 10.0);
+        assert_eq!(cp.path.nodes(), &[&"design", &"impl", &"test"]);
+
+        let docs = cp.timing(&"docs").unwrap();
+        assert_eq!(docs.earliest_start, 3.0);
+        assert_eq!(docs.latest_start, 4.0);
+        assert_eq!(docs.slack, 1.0);
+        assert_eq!(cp.timing(&"test").unwrap().latest_finish, 10.0);
+        assert!(g.critical_path_with_durations(|_| -1.0).is_err());
+    }
+
+    #[test]
+    fn test_critical_path_on_edges() {
+        let mut g = Graph::new();
+        for u in [1, 2, 3, 4] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&1, &2, 2.0);
+        let _ = g.add_weighted_edge(&2, &4, 2.0);
+        let _ = g.add_weighted_edge(&1, &3, 1.0);
+        let _ = g.add_weighted_edge(&3, &4, 1.0);
+
+        let cp = g.critical_path().unwrap();
+        assert_eq!(cp.duration, 4.0);
+        assert_eq!(cp.path.nodes(), &[&1, &2, &4]);
+        assert_eq!(cp.timing(&3).unwrap().slack, 2.0);
+
+        let _ = g.add_edge(&4, &1);
+        assert!(matches!(
+            g.critical_path(),
+            Err(GraphError::CycleDetected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_critical_path_by() {
+        let mut g = Graph::new();
+        for u in ["design", "impl", "docs", "test"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("design", "impl"),
+            ("impl", "test"),
+            ("design", "docs"),
+            ("docs", "test"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+        let days = |u: &&str| match *u {
+            "design" => 3u32,
+            "impl" => 5,
+            "docs" => 4,
+            _ => 2,
+        };
+
+        // 実装の後にレビュー待ちが 2 日ある
+        let lag = |a: &&str, _: &&str| if *a == "impl" { 2u32 } else { 0 };
+        let cp = g.critical_path_by(days, lag).unwrap();
+        assert_eq!(cp.duration, 12);
+        assert_eq!(cp.path.nodes(), &[&"design", &"impl", &"test"]);
+        assert_eq!(cp.path.weight(), 12);
+        assert_eq!(cp.timing(&"docs").unwrap().slack, 3);
+
+        assert!(matches!(
+            g.critical_path_by(|_| u32::MAX / 2, |_, _| 0),
+            Err(GraphError::WeightOverflow { .. })
+        ));
+        assert!(matches!(
+            g.critical_path_by(|_| -1i32, |_, _| 0),
+            Err(GraphError::InvalidArgument(_))
+        ));
+    }
+}
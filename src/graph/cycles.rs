@@ -0,0 +1,127 @@
+use std::{collections::HashSet, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // 辺数が `max_len` 以下の単純サイクルをすべて列挙する (閉じた形: 先頭 == 末尾)
+    // 各サイクルは含まれる最小の ID のノードから始まる形で一度だけ現れる
+    pub fn simple_cycles_up_to(&self, max_len: usize) -> Vec<Vec<NodeID>> {
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut cycles = Vec::new();
+        for &start in &ids {
+            let mut path = vec![start];
+            let mut on_path = HashSet::from([start]);
+            self.bounded_cycles_dfs(start, max_len, &mut path, &mut on_path, &mut cycles);
+        }
+
+        cycles.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        cycles
+    }
+
+    // 深さは max_len で抑えられるので再帰で書く
+    fn bounded_cycles_dfs(
+        &self,
+        start: NodeID,
+        max_len: usize,
+        path: &mut Vec<NodeID>,
+        on_path: &mut HashSet<NodeID>,
+        cycles: &mut Vec<Vec<NodeID>>,
+    ) {
+        let node = *path.last().unwrap();
+        let Some(n) = self.nodes_dict.get(&node) else {
+            return;
+        };
+
+        for &child in &n.children {
+            if child == start {
+                if path.len() > max_len {
+                    continue;
+                }
+                let mut cycle = path.clone();
+                cycle.push(start);
+                cycles.push(cycle);
+            } else if child > start && path.len() < max_len && on_path.insert(child) {
+                path.push(child);
+                self.bounded_cycles_dfs(start, max_len, path, on_path, cycles);
+                path.pop();
+                on_path.remove(&child);
+            }
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 長さ (辺数) が `max_len` 以下のサイクルをすべて返す．短い順に並ぶ．
+    // self_loop_policy が IgnoreInCycles なら自己ループ (長さ 1) は含めない
+    pub fn cycles_up_to(&self, max_len: usize) -> Vec<Vec<&T>> {
+        self.core
+            .simple_cycles_up_to(max_len)
+            .iter()
+            .filter(|c| c.len() > 2 || !self.ignores_self_loops())
+            .map(|c| self.nodes_from_ids(c))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"A"); // 長さ 1
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A"); // 長さ 2
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"A"); // 長さ 3
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"D", &"A"); // 長さ 4
+        g
+    }
+
+    #[test]
+    fn test_cycles_up_to() {
+        let g = sample();
+
+        assert_eq!(g.cycles_up_to(0), Vec::<Vec<&&str>>::new());
+        assert_eq!(g.cycles_up_to(1), vec![vec![&"A", &"A"]]);
+        assert_eq!(
+            g.cycles_up_to(3),
+            vec![
+                vec![&"A", &"A"],
+                vec![&"A", &"B", &"A"],
+                vec![&"A", &"B", &"C", &"A"],
+            ]
+        );
+        assert_eq!(g.cycles_up_to(4).len(), 4);
+        assert_eq!(g.cycles_up_to(10).len(), 4);
+    }
+
+    #[test]
+    fn test_cycles_up_to_counts_each_cycle_once() {
+        // 完全有向グラフ K4: 2-サイクル 6 個, 3-サイクル 8 個
+        let mut g = Graph::new();
+        for i in 0..4 {
+            let _ = g.add_node(i);
+        }
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    let _ = g.add_edge(&i, &j);
+                }
+            }
+        }
+
+        let cycles = g.cycles_up_to(3);
+        assert_eq!(cycles.iter().filter(|c| c.len() == 3).count(), 6);
+        assert_eq!(cycles.iter().filter(|c| c.len() == 4).count(), 8);
+    }
+}
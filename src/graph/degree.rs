@@ -0,0 +1,95 @@
+use std::{borrow::Borrow, hash::Hash};
+
+use super::{core::NodeID, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 入次数．多重グラフでは平行なエッジを本数だけ数える
+    fn in_degree_of(&self, id: NodeID) -> usize {
+        self.core.nodes_dict[&id]
+            .parents
+            .iter()
+            .map(|&p| self.multiplicity_of(p, id))
+            .sum()
+    }
+
+    fn out_degree_of(&self, id: NodeID) -> usize {
+        self.core.nodes_dict[&id]
+            .children
+            .iter()
+            .map(|&c| self.multiplicity_of(id, c))
+            .sum()
+    }
+
+    // 入次数 (自己ループは 1 と数え，平行なエッジは本数だけ数える)．無いノードは None
+    pub fn in_degree<Q>(&self, u: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &id = self.node_id(u)?;
+        Some(self.in_degree_of(id))
+    }
+
+    // 出次数 (自己ループは 1 と数え，平行なエッジは本数だけ数える)．無いノードは None
+    pub fn out_degree<Q>(&self, u: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &id = self.node_id(u)?;
+        Some(self.out_degree_of(id))
+    }
+
+    // すべてのノードの (ノード, 入次数, 出次数)．順序は決まっていない
+    pub fn degrees(&self) -> impl Iterator<Item = (&T, usize, usize)> {
+        self.id_dict
+            .iter()
+            .map(|(u, &id)| (u, self.in_degree_of(id), self.out_degree_of(id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_degrees() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"C");
+
+        assert_eq!(g.out_degree(&"A"), Some(2));
+        assert_eq!(g.in_degree(&"A"), Some(0));
+        assert_eq!(g.in_degree(&"C"), Some(3));
+        assert_eq!(g.out_degree(&"Z"), None);
+
+        let mut degrees: Vec<_> = g.degrees().collect();
+        degrees.sort();
+        assert_eq!(degrees, vec![(&"A", 0, 2), (&"B", 1, 1), (&"C", 3, 1)]);
+    }
+
+    #[test]
+    fn test_degrees_multigraph() {
+        let mut g = Graph::new_multigraph();
+        for u in ["A", "B"] {
+            let _ = g.add_node(u);
+        }
+        for _ in 0..3 {
+            let _ = g.add_edge(&"A", &"B");
+        }
+        let _ = g.add_edge(&"B", &"A");
+
+        assert_eq!(g.out_degree(&"A"), Some(3));
+        assert_eq!(g.in_degree(&"B"), Some(3));
+        let mut degrees: Vec<_> = g.degrees().collect();
+        degrees.sort();
+        assert_eq!(degrees, vec![(&"A", 1, 3), (&"B", 3, 1)]);
+        let stats = g.stats();
+        assert_eq!((stats.min_degree, stats.max_degree), (4, 4));
+    }
+}
@@ -0,0 +1,209 @@
+use std::io::{BufRead, BufReader, Read};
+
+use super::{error::GraphError, graph::Graph};
+
+// Makefile の依存ファイルの 1 つの規則 (継続行をつないだもの) をターゲットと前提に分ける．
+// `\ ` (空白)・`\#`・`$$` のエスケープを解く
+fn parse_rule(rule: &str) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut targets = Vec::new();
+    let mut prereqs = Vec::new();
+    let mut seen_colon = false;
+    let mut word = String::new();
+
+    let mut push = |word: &mut String, seen_colon: bool| {
+        if !word.is_empty() {
+            let w = std::mem::take(word);
+            if seen_colon {
+                prereqs.push(w);
+            } else {
+                targets.push(w);
+            }
+        }
+    };
+
+    let mut chars = rule.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some(' ') | Some('#')) => {
+                word.push(chars.next().unwrap());
+            }
+            '$' if chars.peek() == Some(&'$') => {
+                chars.next();
+                word.push('$');
+            }
+            '#' => break,
+            c if c.is_whitespace() => push(&mut word, seen_colon),
+            ':' if !seen_colon => {
+                // `C:\` のようなドライブ名は区切りとみなさない
+                if word.len() == 1 && matches!(chars.peek(), Some('\\') | Some('/')) {
+                    word.push(c);
+                } else {
+                    push(&mut word, seen_colon);
+                    seen_colon = true;
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    push(&mut word, seen_colon);
+
+    if !seen_colon && !targets.is_empty() {
+        return Err(format!(
+            "expected `target: prerequisites`, found {:?}",
+            rule
+        ));
+    }
+    Ok((targets, prereqs))
+}
+
+impl Graph<String> {
+    // ノードが無ければ追加する
+    pub(super) fn ensure_node(&mut self, u: &str) -> Result<(), GraphError> {
+        if !self.contains_node(u) {
+            self.add_node(u.to_string())?;
+        }
+        Ok(())
+    }
+
+    // Makefile 形式の依存ファイル (gcc -MD の `.d`，Ninja の depfile) からターゲット → 前提の
+    // グラフを作る．`\` で終わる行は次の行に続き，複数の規則があってもよい
+    pub fn from_depfile<R: Read>(reader: R) -> Result<Graph<String>, GraphError> {
+        let mut g = Graph::new();
+
+        let mut rule = String::new();
+        let mut start = 0;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            if rule.is_empty() {
+                start = i;
+            }
+            if let Some(head) = line.strip_suffix('\\') {
+                rule.push_str(head);
+                rule.push(' ');
+                continue;
+            }
+            rule.push_str(&line);
+
+            let (targets, prereqs) = parse_rule(&std::mem::take(&mut rule))
+                .map_err(|e| GraphError::Parse(format!("line {}: {}", start + 1, e)))?;
+            for t in &targets {
+                g.ensure_node(t)?;
+                for p in &prereqs {
+                    g.ensure_node(p)?;
+                    g.add_edge(t, p)?;
+                }
+            }
+        }
+        if !rule.is_empty() {
+            return Err(GraphError::Parse(format!(
+                "line {}: unterminated line continuation",
+                start + 1
+            )));
+        }
+
+        Ok(g)
+    }
+
+    // `ninja -t deps` の出力からターゲット → 依存先のグラフを作る．
+    // `target: #deps 2, deps mtime ... (VALID)` の行に字下げした依存先の行が続く
+    pub fn from_ninja_deps<R: Read>(reader: R) -> Result<Graph<String>, GraphError> {
+        let mut g = Graph::new();
+
+        let mut target: Option<String> = None;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            if line.trim().is_empty() {
+                target = None;
+                continue;
+            }
+
+            if line.starts_with(char::is_whitespace) {
+                let Some(t) = &target else {
+                    return Err(GraphError::Parse(format!(
+                        "line {}: dependency without a target",
+                        i + 1
+                    )));
+                };
+                let dep = line.trim();
+                g.ensure_node(dep)?;
+                g.add_edge(t.as_str(), dep)?;
+            } else {
+                let Some((t, _)) = line.split_once(": #deps") else {
+                    return Err(GraphError::Parse(format!(
+                        "line {}: expected `target: #deps ...`, found {:?}",
+                        i + 1,
+                        line
+                    )));
+                };
+                g.ensure_node(t)?;
+                target = Some(t.to_string());
+            }
+        }
+
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+    use super::parse_rule;
+
+    #[test]
+    fn test_from_depfile() {
+        let input = "\
+main.o: main.c util.h \\
+  config.h
+util.o util.d: util.c util.h # generated
+my\\ file.o: $$dir/a.c
+util.h:
+";
+        let g = Graph::from_depfile(input.as_bytes()).unwrap();
+
+        assert!(g.contains_edge("main.o", "config.h"));
+        assert!(g.contains_edge("util.d", "util.h"));
+        assert!(g.contains_edge("my file.o", "$dir/a.c"));
+        assert!(!g.contains_node("generated"));
+        assert_eq!(g.node_count(), 9);
+        assert_eq!(g.edge_count(), 8);
+        assert!(g.detect_cycle().is_none());
+
+        let cyclic = Graph::from_depfile("a: b\nb: a\n".as_bytes()).unwrap();
+        assert!(cyclic.topological_sort().is_err());
+
+        let err = Graph::from_depfile("a: b\nc d\n".as_bytes()).err().unwrap();
+        assert!(
+            matches!(&err, GraphError::Parse(msg) if msg.starts_with("line 2:")),
+            "{err}"
+        );
+        assert!(Graph::from_depfile("a: b \\".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_rule_drive_letter() {
+        assert_eq!(
+            parse_rule(r"out.o: C:\src\a.c"),
+            Ok((vec!["out.o".to_string()], vec![r"C:\src\a.c".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_from_ninja_deps() {
+        let input = "\
+obj/main.o: #deps 2, deps mtime 1700000000 (VALID)
+    ../src/main.c
+    ../src/util.h
+
+obj/util.o: #deps 1, deps mtime 1700000001 (STALE)
+    ../src/util.h
+
+";
+        let g = Graph::from_ninja_deps(input.as_bytes()).unwrap();
+        assert_eq!(g.node_count(), 4);
+        assert!(g.contains_edge("obj/main.o", "../src/util.h"));
+        assert!(g.contains_edge("obj/util.o", "../src/util.h"));
+
+        assert!(Graph::from_ninja_deps("    orphan.h\n".as_bytes()).is_err());
+        assert!(Graph::from_ninja_deps("obj/a.o\n".as_bytes()).is_err());
+    }
+}
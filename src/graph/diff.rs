@@ -0,0 +1,102 @@
+use std::hash::Hash;
+
+use super::{core::NodeID, graph::Graph};
+
+/// 2 つのグラフの差分．`added_*` は新しい方にだけ，`removed_*` は古い方にだけあるもの
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<'a, T> {
+    pub added_nodes: Vec<&'a T>,
+    pub removed_nodes: Vec<&'a T>,
+    pub added_edges: Vec<(&'a T, &'a T)>,
+    pub removed_edges: Vec<(&'a T, &'a T)>,
+}
+
+impl<'a, T> GraphDiff<'a, T> {
+    // 2 つのグラフのノードとエッジが一致するか
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // すべてのエッジ (始点，終点の ID の順)
+    fn sorted_edges(&self) -> Vec<(NodeID, NodeID)> {
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&c| (id, c)))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    // `self` (古い方) から `other` (新しい方) への差分．ノードは値で対応させ，
+    // それぞれのグラフでの追加順に並べる．重みとラベル，平行なエッジの本数は比べない
+    pub fn diff<'a>(&'a self, other: &'a Graph<T>) -> GraphDiff<'a, T> {
+        let only_in = |a: &'a Graph<T>, b: &Graph<T>| {
+            let nodes = a.nodes_by_id();
+            let mut ids: Vec<NodeID> = a.id_dict.values().copied().collect();
+            ids.sort_unstable();
+            let added_nodes: Vec<&T> = ids
+                .into_iter()
+                .map(|id| nodes[&id])
+                .filter(|u| !b.contains_node(*u))
+                .collect();
+            let added_edges: Vec<(&T, &T)> = a
+                .sorted_edges()
+                .into_iter()
+                .map(|(from, to)| (nodes[&from], nodes[&to]))
+                .filter(|(u, v)| !b.contains_edge(*u, *v))
+                .collect();
+            (added_nodes, added_edges)
+        };
+
+        let (added_nodes, added_edges) = only_in(other, self);
+        let (removed_nodes, removed_edges) = only_in(self, other);
+        GraphDiff {
+            added_nodes,
+            removed_nodes,
+            added_edges,
+            removed_edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_diff() {
+        let mut old = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = old.add_node(u);
+        }
+        let _ = old.add_edge(&"A", &"B");
+        let _ = old.add_edge(&"B", &"C");
+
+        let mut new = Graph::new();
+        for u in ["D", "C", "B", "A"] {
+            let _ = new.add_node(u);
+        }
+        let _ = new.add_weighted_edge(&"A", &"B", 3.0);
+        let _ = new.add_edge(&"C", &"D");
+        let _ = new.add_edge(&"A", &"C");
+
+        let d = old.diff(&new);
+        assert_eq!(d.added_nodes, vec![&"D"]);
+        assert!(d.removed_nodes.is_empty());
+        assert_eq!(d.added_edges, vec![(&"C", &"D"), (&"A", &"C")]);
+        assert_eq!(d.removed_edges, vec![(&"B", &"C")]);
+
+        let back = new.diff(&old);
+        assert_eq!(back.removed_nodes, d.added_nodes);
+        assert_eq!(back.added_edges, d.removed_edges);
+        assert!(!d.is_empty());
+        assert!(old.diff(&old).is_empty());
+    }
+}
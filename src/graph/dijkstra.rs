@@ -0,0 +1,489 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    path::Path,
+    weight::Weight,
+};
+
+// (始点からの距離, 最短経路木での親)
+pub type ShortestPathTree<W = f64> = (HashMap<NodeID, W>, HashMap<NodeID, NodeID>);
+
+// BinaryHeap を最小ヒープとして使うための (コスト, ノード) の組
+#[derive(PartialEq)]
+pub(crate) struct MinScored(pub f64, pub NodeID);
+
+impl Eq for MinScored {}
+
+impl PartialOrd for MinScored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MinScored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .total_cmp(&self.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+// MinScored の重みを Weight にしたもの
+pub(super) struct MinWeighted<W>(pub W, pub NodeID);
+
+impl<W: Weight> PartialEq for MinWeighted<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for MinWeighted<W> {}
+
+impl<W: Weight> PartialOrd for MinWeighted<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for MinWeighted<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .cmp_weight(&self.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+impl GraphCore {
+    // Dijkstra 法．`target` が与えられたらそこが確定した時点で打ち切る
+    // 重みは非負であること
+    pub fn dijkstra<F>(&self, start: NodeID, target: Option<NodeID>, weight: F) -> ShortestPathTree
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        self.dijkstra_by(start, target, weight).unwrap()
+    }
+
+    // 重みの型を選べる Dijkstra 法．距離を足して桁あふれした候補は短くないものとして飛ばし，
+    // 到達したのに距離を表せないノードが残ったら，そこへのエッジをエラーとして返す
+    // (`target` が確定したなら他のノードは問わない)
+    pub fn dijkstra_by<W, F>(
+        &self,
+        start: NodeID,
+        target: Option<NodeID>,
+        weight: F,
+    ) -> Result<ShortestPathTree<W>, (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut dist: HashMap<NodeID, W> = HashMap::new();
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut overflowed: HashMap<NodeID, (NodeID, NodeID)> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        if !self.nodes_dict.contains_key(&start) {
+            return Ok((dist, parent));
+        }
+        dist.insert(start, W::zero());
+        heap.push(MinWeighted(W::zero(), start));
+
+        while let Some(MinWeighted(d, node)) = heap.pop() {
+            if d.cmp_weight(&dist[&node]) == Ordering::Greater {
+                continue; // 古いエントリ
+            }
+            if Some(node) == target {
+                return Ok((dist, parent));
+            }
+
+            for child in self.children_of(node) {
+                let Some(nd) = d.checked_add(weight(node, child)) else {
+                    overflowed.entry(child).or_insert((node, child));
+                    continue;
+                };
+                if dist
+                    .get(&child)
+                    .is_none_or(|old| nd.cmp_weight(old) == Ordering::Less)
+                {
+                    dist.insert(child, nd);
+                    parent.insert(child, node);
+                    heap.push(MinWeighted(nd, child));
+                }
+            }
+        }
+
+        match unreached_overflow(&overflowed, |id| dist.contains_key(&id)) {
+            Some(edge) => Err(edge),
+            None => Ok((dist, parent)),
+        }
+    }
+
+    // `targets` までの最短距離．すべての目標が確定した時点で打ち切る (到達できないものは含まない)
+    pub fn dijkstra_to_targets<F>(
+        &self,
+        start: NodeID,
+        targets: &HashSet<NodeID>,
+        weight: F,
+    ) -> HashMap<NodeID, f64>
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        self.dijkstra_to_targets_by(start, targets, weight).unwrap()
+    }
+
+    // 重みの型を選べる dijkstra_to_targets．桁あふれの扱いは dijkstra_by と同じで，
+    // すべての目標が確定したなら他のノードは問わない
+    pub fn dijkstra_to_targets_by<W, F>(
+        &self,
+        start: NodeID,
+        targets: &HashSet<NodeID>,
+        weight: F,
+    ) -> Result<HashMap<NodeID, W>, (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut dist: HashMap<NodeID, W> = HashMap::new();
+        let mut settled: HashMap<NodeID, W> = HashMap::new();
+        let mut overflowed: HashMap<NodeID, (NodeID, NodeID)> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        if !self.nodes_dict.contains_key(&start) {
+            return Ok(settled);
+        }
+        dist.insert(start, W::zero());
+        heap.push(MinWeighted(W::zero(), start));
+
+        while let Some(MinWeighted(d, node)) = heap.pop() {
+            if d.cmp_weight(&dist[&node]) == Ordering::Greater {
+                continue; // 古いエントリ
+            }
+            if targets.contains(&node) {
+                settled.insert(node, d);
+                if settled.len() == targets.len() {
+                    return Ok(settled);
+                }
+            }
+
+            for child in self.children_of(node) {
+                let Some(nd) = d.checked_add(weight(node, child)) else {
+                    overflowed.entry(child).or_insert((node, child));
+                    continue;
+                };
+                if dist
+                    .get(&child)
+                    .is_none_or(|old| nd.cmp_weight(old) == Ordering::Less)
+                {
+                    dist.insert(child, nd);
+                    heap.push(MinWeighted(nd, child));
+                }
+            }
+        }
+
+        match unreached_overflow(&overflowed, |id| dist.contains_key(&id)) {
+            Some(edge) => Err(edge),
+            None => Ok(settled),
+        }
+    }
+}
+
+// 桁あふれした候補しか無かった (距離を表せない) ノードへのエッジ．どれを返すかは NodeID で決める
+pub(super) fn unreached_overflow<F>(
+    overflowed: &HashMap<NodeID, (NodeID, NodeID)>,
+    reached: F,
+) -> Option<(NodeID, NodeID)>
+where
+    F: Fn(NodeID) -> bool,
+{
+    overflowed
+        .iter()
+        .filter(|(&id, _)| !reached(id))
+        .map(|(_, &edge)| edge)
+        .min()
+}
+
+// 最短経路木の親をたどって `to` までの経路を復元する
+pub(crate) fn path_to(parent: &HashMap<NodeID, NodeID>, from: NodeID, to: NodeID) -> Vec<NodeID> {
+    let mut path = vec![to];
+    let mut cur = to;
+    while cur != from {
+        cur = parent[&cur];
+        path.push(cur);
+    }
+    path.reverse();
+    path
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 重み付きの最短経路．経路の重みが総コストになる
+    pub fn shortest_path_dijkstra<Q>(&self, from: &Q, to: &Q) -> Option<Path<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = *self.node_id(from)?;
+        let to_id = *self.node_id(to)?;
+
+        let (dist, parent) = self
+            .core
+            .dijkstra(from_id, Some(to_id), |a, b| self.weight_of(a, b));
+        if !dist.contains_key(&to_id) {
+            return None;
+        }
+
+        Some(self.path_from_ids(&path_to(&parent, from_id, to_id)))
+    }
+
+    // sources × targets の最短距離の行列 (OD 行列)．matrix[i][j] は sources[i] から targets[j] への
+    // 距離で，到達できなければ None．各 source からの探索はすべての target が確定した時点で打ち切る
+    pub fn distances_between<Q>(
+        &self,
+        sources: &[&Q],
+        targets: &[&Q],
+    ) -> Result<Vec<Vec<Option<f64>>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.distances_between_impl(sources, targets, |a, b| self.weight_of(a, b))
+    }
+
+    // 重みを `weight` で与える OD 行列．距離が桁あふれしたらエラー
+    pub fn distances_between_by<Q, W, F>(
+        &self,
+        sources: &[&Q],
+        targets: &[&Q],
+        weight: F,
+    ) -> Result<Vec<Vec<Option<W>>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let nodes = self.nodes_by_id();
+        self.distances_between_impl(sources, targets, |a, b| weight(nodes[&a], nodes[&b]))
+    }
+
+    fn distances_between_impl<Q, W, F>(
+        &self,
+        sources: &[&Q],
+        targets: &[&Q],
+        weight: F,
+    ) -> Result<Vec<Vec<Option<W>>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let lookup = |u: &Q, arg| {
+            self.node_id(u)
+                .copied()
+                .ok_or(GraphError::NodeNotFound { arg })
+        };
+        let source_ids = sources
+            .iter()
+            .map(|u| lookup(u, "sources"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_ids = targets
+            .iter()
+            .map(|u| lookup(u, "targets"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_set: HashSet<NodeID> = target_ids.iter().copied().collect();
+
+        source_ids
+            .iter()
+            .map(|&s| {
+                let dist = self
+                    .core
+                    .dijkstra_to_targets_by(s, &target_set, &weight)
+                    .map_err(|(a, b)| GraphError::WeightOverflow {
+                        from: self.handle(a),
+                        to: self.handle(b),
+                    })?;
+                Ok(target_ids.iter().map(|t| dist.get(t).copied()).collect())
+            })
+            .collect()
+    }
+
+    // 重みを `weight` で与える最短経路 (u32 や Duration などの重みも使える)．
+    // 到達できなければ Ok(None)，距離が桁あふれしたらエラー
+    pub fn shortest_path_by<Q, W, F>(
+        &self,
+        from: &Q,
+        to: &Q,
+        weight: F,
+    ) -> Result<Option<Path<'_, T, W>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let &from_id = self
+            .node_id(from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let &to_id = self
+            .node_id(to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+
+        let nodes = self.nodes_by_id();
+        let (dist, parent) = self
+            .core
+            .dijkstra_by(from_id, Some(to_id), |a, b| weight(nodes[&a], nodes[&b]))
+            .map_err(|(a, b)| GraphError::WeightOverflow {
+                from: self.handle(a),
+                to: self.handle(b),
+            })?;
+        let Some(&d) = dist.get(&to_id) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Path::new(
+            self.nodes_from_ids(&path_to(&parent, from_id, to_id)),
+            d,
+        )))
+    }
+
+    // `from` から到達できる各ノードへの最短距離
+    pub fn dijkstra_distances<Q>(&self, from: &Q) -> HashMap<&T, f64>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&from_id) = self.node_id(from) else {
+            return HashMap::new();
+        };
+        let nodes = self.nodes_by_id();
+
+        let (dist, _) = self
+            .core
+            .dijkstra(from_id, None, |a, b| self.weight_of(a, b));
+        dist.into_iter().map(|(id, d)| (nodes[&id], d)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 1.0);
+        let _ = g.add_weighted_edge(&"B", &"C", 2.0);
+        let _ = g.add_weighted_edge(&"A", &"C", 5.0);
+        let _ = g.add_weighted_edge(&"C", &"D", 1.0);
+        let _ = g.add_weighted_edge(&"B", &"D", 4.5);
+        g
+    }
+
+    #[test]
+    fn test_shortest_path_dijkstra() {
+        let g = sample();
+
+        let p = g.shortest_path_dijkstra(&"A", &"D").unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"B", &"C", &"D"]);
+        assert_eq!(p.weight(), 4.0);
+
+        // BFS の最短経路 (A → B → D か A → C → D) とは異なる
+        let bfs = g.shortest_path(&"A", &"D").unwrap();
+        assert_eq!(bfs.edge_count(), 2);
+        assert!(bfs.weight() > p.weight());
+
+        assert_eq!(g.shortest_path_dijkstra(&"A", &"A").unwrap().weight(), 0.0);
+        assert!(g.shortest_path_dijkstra(&"D", &"A").is_none());
+        assert!(g.shortest_path_dijkstra(&"A", &"E").is_none());
+    }
+
+    #[test]
+    fn test_dijkstra_distances() {
+        let g = sample();
+
+        let dist = g.dijkstra_distances(&"A");
+        assert_eq!(dist.len(), 4);
+        assert_eq!(dist[&"A"], 0.0);
+        assert_eq!(dist[&"B"], 1.0);
+        assert_eq!(dist[&"C"], 3.0);
+        assert_eq!(dist[&"D"], 4.0);
+        assert!(!dist.contains_key(&"E"));
+
+        assert!(g.dijkstra_distances(&"Z").is_empty());
+    }
+
+    #[test]
+    fn test_distances_between() {
+        let g = sample();
+
+        let matrix = g
+            .distances_between(&[&"A", &"B", &"E"], &[&"D", &"C", &"A"])
+            .unwrap();
+        assert_eq!(
+            matrix,
+            vec![
+                vec![Some(4.0), Some(3.0), Some(0.0)],
+                vec![Some(3.0), Some(2.0), None],
+                vec![None, None, None],
+            ]
+        );
+        assert!(g.distances_between(&[&"A"], &[&"Z"]).is_err());
+
+        // 重みを 100 倍した u8 (255 で頭打ち) では A → D の距離を表せない
+        let weight = |a: &&str, b: &&str| (g.edge_weight(a, b).unwrap() * 100.0) as u8;
+        let matrix = g
+            .distances_between_by(&[&"A", &"E"], &[&"B", &"C"], weight)
+            .unwrap();
+        assert_eq!(matrix, vec![vec![Some(100), Some(255)], vec![None, None]]);
+        assert!(matches!(
+            g.distances_between_by(&[&"A"], &[&"C", &"D"], weight),
+            Err(GraphError::WeightOverflow { from, to })
+                if from == NodeHandle::new(1, 0) && to == NodeHandle::new(3, 0)
+        ));
+    }
+
+    #[test]
+    fn test_shortest_path_by() {
+        let g = sample();
+
+        let p = g
+            .shortest_path_by(&"A", &"D", |a, b| {
+                Duration::from_millis(g.edge_weight(a, b).unwrap() as u64 * 10)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"B", &"C", &"D"]);
+        assert_eq!(p.weight(), Duration::from_millis(40));
+        assert_eq!(g.shortest_path_by(&"D", &"A", |_, _| 1u32), Ok(None));
+        assert!(g.shortest_path_by(&"A", &"X", |_, _| 1u32).is_err());
+
+        // 整数の重みは巻き戻らずにエラーになる
+        assert!(matches!(
+            g.shortest_path_by(&"A", &"D", |_, _| u32::MAX / 2 + 1),
+            Err(GraphError::WeightOverflow { from, .. }) if from.index() == 1
+        ));
+        // 最短でない経路 (A → B → D) が桁あふれしても最短距離 (A → C → D) が収まればよい
+        let weight = |a: &&str, b: &&str| match (*a, *b) {
+            ("B", "D") => u32::MAX,
+            _ => 1,
+        };
+        let p = g.shortest_path_by(&"A", &"D", weight).unwrap().unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"C", &"D"]);
+        assert_eq!(p.weight(), 2);
+    }
+}
@@ -0,0 +1,506 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    core::NodeID,
+    error::GraphError,
+    format::{dot_quote, node_label},
+    graph::Graph,
+    path::Path,
+};
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // Graphviz の DOT 形式で書き出す．ノードは Debug 表示をラベルにする
+    pub fn write_dot<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_dot_inner(w, None)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_dot(&mut buf).unwrap(); // Vec への書き込みは失敗しない
+        String::from_utf8(buf).unwrap()
+    }
+
+    // 経路 (あるいは検出したサイクル) を赤で強調した DOT を返す
+    pub fn to_dot_highlighted(&self, path: &Path<'_, T>) -> String {
+        let mut buf = Vec::new();
+        self.write_dot_inner(&mut buf, Some(path)).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn write_dot_inner<W: Write>(
+        &self,
+        w: &mut W,
+        highlight: Option<&Path<'_, T>>,
+    ) -> io::Result<()> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        writeln!(w, "digraph {{")?;
+        for id in &ids {
+            writeln!(w, "    {};", dot_quote(&node_label(nodes[id])))?;
+        }
+        for id in &ids {
+            let mut children = self.core.children_of(*id);
+            children.sort_unstable();
+
+            for child in children {
+                write!(
+                    w,
+                    "    {} -> {}",
+                    dot_quote(&node_label(nodes[id])),
+                    dot_quote(&node_label(nodes[&child]))
+                )?;
+                // ラベルがあれば label に，重みは weight に書く (ラベルが無ければ重みを label に)
+                let mut attrs = Vec::new();
+                let weight = self.weights.get(&(*id, child));
+                match (self.labels.get(&(*id, child)), weight) {
+                    (Some(label), _) => {
+                        attrs.push(format!("label={}", dot_quote(label)));
+                        if let Some(weight) = weight {
+                            attrs.push(format!("weight={}", dot_quote(&weight.to_string())));
+                        }
+                    }
+                    (None, Some(weight)) => {
+                        attrs.push(format!("label={}", dot_quote(&weight.to_string())));
+                    }
+                    (None, None) => {}
+                }
+                if let Some(ps) = self.provenance.get(&(*id, child)) {
+                    let tooltip: Vec<String> = ps.iter().map(|p| p.to_string()).collect();
+                    attrs.push(format!("tooltip={}", dot_quote(&tooltip.join("\n"))));
+                }
+
+                if attrs.is_empty() {
+                    writeln!(w, ";")?;
+                } else {
+                    writeln!(w, " [{}];", attrs.join(", "))?;
+                }
+            }
+        }
+        if let Some(path) = highlight {
+            write!(w, "{}", path.to_dot_highlight("red"))?;
+        }
+        writeln!(w, "}}")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Id(String),
+    EdgeOp,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equal,
+    Semi,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line_start = true;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            // 行頭の # はプリプロセッサ出力として読み飛ばす
+            '#' if line_start => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("unterminated comment".to_string());
+                }
+                i += 2;
+                continue;
+            }
+            _ => {}
+        }
+        line_start = false;
+
+        match c {
+            '{' => tokens.push(Token::LBrace),
+            '}' => tokens.push(Token::RBrace),
+            '[' => tokens.push(Token::LBracket),
+            ']' => tokens.push(Token::RBracket),
+            '=' => tokens.push(Token::Equal),
+            ';' => tokens.push(Token::Semi),
+            ',' => tokens.push(Token::Comma),
+            '-' if matches!(chars.get(i + 1), Some('>') | Some('-')) => {
+                tokens.push(Token::EdgeOp);
+                i += 1;
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        // dot_quote の逆 (`\"` と `\\` だけを戻す)
+                        Some('\\') if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                            s.push(chars[i + 1]);
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'\n') => i += 1, // 行継続
+                        Some(&c) => s.push(c),
+                    }
+                    i += 1;
+                }
+                tokens.push(Token::Id(s));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let start = i;
+                while i + 1 < chars.len()
+                    && (chars[i + 1].is_alphanumeric()
+                        || chars[i + 1] == '_'
+                        || chars[i + 1] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Id(chars[start..=i].iter().collect()));
+            }
+            c => return Err(format!("unexpected character {:?}", c)),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+struct DotParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl DotParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        self.pos += 1;
+        self.tokens.get(self.pos - 1)
+    }
+
+    fn expect_id(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Id(s)) => Ok(s.clone()),
+            t => Err(format!("expected identifier, found {:?}", t)),
+        }
+    }
+
+    // [a=b, c=d][e=f] の形の属性リスト (無ければ空)
+    fn attr_lists(&mut self) -> Result<Vec<(String, String)>, String> {
+        let mut attrs = Vec::new();
+        while self.peek() == Some(&Token::LBracket) {
+            self.next();
+            loop {
+                match self.peek() {
+                    Some(Token::RBracket) => {
+                        self.next();
+                        break;
+                    }
+                    Some(Token::Comma) | Some(Token::Semi) => {
+                        self.next();
+                    }
+                    _ => {
+                        let key = self.expect_id()?;
+                        if self.next() != Some(&Token::Equal) {
+                            return Err(format!("expected '=' after attribute {}", key));
+                        }
+                        attrs.push((key, self.expect_id()?));
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+fn parse_dot(input: &str) -> Result<Graph<String>, String> {
+    let mut p = DotParser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+
+    let mut keyword = p.expect_id()?.to_lowercase();
+    if keyword == "strict" {
+        keyword = p.expect_id()?.to_lowercase();
+    }
+    let directed = match keyword.as_str() {
+        "digraph" => true,
+        "graph" => false,
+        _ => {
+            return Err(format!(
+                "expected 'graph' or 'digraph', found {:?}",
+                keyword
+            ))
+        }
+    };
+    if let Some(Token::Id(_)) = p.peek() {
+        p.next(); // グラフ名
+    }
+    if p.next() != Some(&Token::LBrace) {
+        return Err("expected '{'".to_string());
+    }
+
+    let mut g = Graph::new();
+    loop {
+        let id = match p.next() {
+            Some(Token::RBrace) => break,
+            Some(Token::Semi) | Some(Token::Comma) => continue,
+            Some(Token::Id(id)) => id.clone(),
+            Some(Token::LBrace) => return Err("subgraphs are not supported".to_string()),
+            t => return Err(format!("unexpected token {:?}", t)),
+        };
+
+        if id == "subgraph" {
+            return Err("subgraphs are not supported".to_string());
+        }
+        if matches!(id.as_str(), "graph" | "node" | "edge") && p.peek() == Some(&Token::LBracket) {
+            p.attr_lists()?;
+            continue;
+        }
+        if p.peek() == Some(&Token::Equal) {
+            p.next();
+            p.expect_id()?;
+            continue;
+        }
+
+        let mut chain = vec![id];
+        while p.peek() == Some(&Token::EdgeOp) {
+            p.next();
+            chain.push(p.expect_id()?);
+        }
+        let attrs = p.attr_lists()?;
+
+        for u in &chain {
+            if !g.id_dict.contains_key(u) {
+                g.add_node(u.clone()).map_err(|e| g.describe_error(&e))?;
+            }
+        }
+
+        let attr = |key: &str| attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+        let weight = ["weight", "label"]
+            .iter()
+            .find_map(|key| attr(key).and_then(|v| v.parse::<f64>().ok()));
+        // 重みとして読まなかった label はエッジのラベル
+        let label = attr("label").filter(|_| attr("weight").is_some() || weight.is_none());
+        for w in chain.windows(2) {
+            let mut edges = vec![(&w[0], &w[1])];
+            if !directed {
+                edges.push((&w[1], &w[0]));
+            }
+            for (a, b) in edges {
+                match weight {
+                    Some(weight) => g.add_weighted_edge(a, b, weight),
+                    None => g.add_edge(a, b),
+                }
+                .map_err(|e| g.describe_error(&e))?;
+                if let Some(label) = label {
+                    g.add_edge_with_label(a, b, label)
+                        .map_err(|e| g.describe_error(&e))?;
+                }
+            }
+        }
+    }
+
+    if p.peek().is_some() {
+        return Err("unexpected input after '}'".to_string());
+    }
+    Ok(g)
+}
+
+impl Graph<String> {
+    // DOT 形式 (の一部) を読み込む．サブグラフ以外の文に対応し，ノードとエッジ以外の属性は無視する
+    // エッジの重みは weight 属性，無ければ数値の label 属性から読み，重みにしなかった label 属性はエッジのラベルにする．
+    // 無向グラフは両方向のエッジにする
+    pub fn from_dot(input: &str) -> Result<Graph<String>, GraphError> {
+        parse_dot(input).map_err(GraphError::Parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{graph::Graph, path::Path, provenance::Provenance};
+
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_weighted_edge(&"B", &"A", 2.5);
+        g
+    }
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            sample().to_dot(),
+            concat!(
+                "digraph {\n",
+                "    \"A\";\n",
+                "    \"B\";\n",
+                "    \"C\";\n",
+                "    \"A\" -> \"B\";\n",
+                "    \"A\" -> \"C\";\n",
+                "    \"B\" -> \"A\" [label=\"2.5\"];\n",
+                "}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_dot_edge_labels() {
+        let mut g = sample();
+        let _ = g.add_edge_with_label(&"A", &"B", "depends-on");
+        let _ = g.add_edge_with_label(&"B", &"A", "imports");
+
+        let dot = g.to_dot();
+        assert!(dot.contains("    \"A\" -> \"B\" [label=\"depends-on\"];\n"));
+        assert!(dot.contains("    \"B\" -> \"A\" [label=\"imports\", weight=\"2.5\"];\n"));
+
+        let loaded = Graph::from_dot(&dot).unwrap();
+        let s = |x: &str| x.to_string();
+        assert_eq!(loaded.edge_label(&s("A"), &s("B")), Some("depends-on"));
+        assert_eq!(loaded.edge_label(&s("B"), &s("A")), Some("imports"));
+        assert_eq!(loaded.edge_weight(&s("B"), &s("A")), Some(2.5));
+        assert_eq!(loaded.edge_label(&s("A"), &s("C")), None);
+    }
+
+    #[test]
+    fn test_to_dot_provenance_tooltip() {
+        let mut g = sample();
+        let _ = g.add_edge_with_provenance(&"A", &"C", Provenance::new("a.rs", 1, "use"));
+        let _ = g.add_edge_with_provenance(&"A", &"C", Provenance::new("a.rs", 7, "call"));
+
+        let dot = g.to_dot();
+        assert!(
+            dot.contains("    \"A\" -> \"C\" [tooltip=\"a.rs:1 (use)\na.rs:7 (call)\"];\n"),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn test_to_dot_non_string_labels() {
+        let mut g = Graph::new();
+        let _ = g.add_node((1, "x\"y"));
+        let dot = g.to_dot();
+        assert!(dot.contains(r#"    "(1, \"x\\\"y\")";"#), "{dot}");
+    }
+
+    #[test]
+    fn test_to_dot_highlighted() {
+        let g = sample();
+        let cycle = Path::new(g.detect_cycle().unwrap(), 0.0);
+
+        let dot = g.to_dot_highlighted(&cycle);
+        assert!(dot.contains("\"A\" -> \"B\" [color=\"red\", penwidth=2];"));
+        assert!(dot.contains("\"B\" -> \"A\" [color=\"red\", penwidth=2];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_from_dot() {
+        let input = r#"
+            // コメント
+            strict digraph deps {
+                graph [rankdir=LR];
+                node [shape=box]
+                a -> b -> c [color=red];
+                "lib.rs" -> a [weight=2.5]
+                c -> a; d
+                /* ブロック
+                   コメント */
+                e [label="E"]; rankdir = TB
+            }
+        "#;
+
+        let mut g = Graph::from_dot(input).unwrap();
+        let s = |x: &str| x.to_string();
+        assert_eq!(g.edge_weight(&s("a"), &s("b")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("b"), &s("c")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("lib.rs"), &s("a")), Some(2.5));
+        assert_eq!(g.edge_weight(&s("b"), &s("a")), None);
+        assert!(g.add_node(s("d")).is_err());
+        assert!(g.add_node(s("e")).is_err());
+
+        let mut cycle = g.detect_cycle().unwrap();
+        cycle.sort();
+        cycle.dedup();
+        assert_eq!(cycle, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_from_dot_undirected() {
+        let g = Graph::from_dot("graph { x -- y }").unwrap();
+        let s = |x: &str| x.to_string();
+        assert_eq!(g.edge_weight(&s("x"), &s("y")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("y"), &s("x")), Some(1.0));
+    }
+
+    #[test]
+    fn test_from_dot_round_trip() {
+        let g = sample();
+        let loaded = Graph::from_dot(&g.to_dot()).unwrap();
+        assert_eq!(loaded.to_dot(), g.to_dot());
+    }
+
+    #[test]
+    fn test_from_dot_round_trip_backslash() {
+        let mut g = Graph::new();
+        for u in [r"C:\src", r#"a\"b\"#, r"two\\slashes", "plain"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.add_edge(r"C:\src", r#"a\"b\"#);
+
+        let loaded = Graph::from_dot(&g.to_dot()).unwrap();
+        assert!(loaded.contains_edge(r"C:\src", r#"a\"b\"#));
+        assert!(loaded.contains_node("plain"));
+        assert!(loaded.contains_node(r"two\\slashes"));
+        assert_eq!(loaded.node_count(), 4);
+    }
+
+    #[test]
+    fn test_from_dot_errors() {
+        assert!(Graph::from_dot("").is_err());
+        assert!(Graph::from_dot("tree { a }").is_err());
+        assert!(Graph::from_dot("digraph { a -> }").is_err());
+        assert!(Graph::from_dot("digraph { subgraph s { a } }").is_err());
+        assert!(Graph::from_dot("digraph { \"a }").is_err());
+        assert!(Graph::from_dot("digraph { a } b").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    any::Any,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+use super::graph::Graph;
+
+/// 種類の異なるノード (File, Crate, Function など) を 1 つのグラフに入れるためのトレイト
+///
+/// `Any + Debug + Eq + Hash` を満たす型には自動で実装される．同値判定とハッシュは
+/// 型が異なれば別のノードとして扱う
+pub trait NodeValue: Any + Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn NodeValue) -> bool;
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<U: Any + Debug + Eq + Hash> NodeValue for U {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn NodeValue) -> bool {
+        other.as_any().downcast_ref::<U>() == Some(self)
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        self.type_id().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+impl PartialEq for dyn NodeValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+impl Eq for dyn NodeValue {}
+
+impl Hash for dyn NodeValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state)
+    }
+}
+
+impl dyn NodeValue {
+    pub fn is<U: Any>(&self) -> bool {
+        self.as_any().is::<U>()
+    }
+
+    pub fn downcast_ref<U: Any>(&self) -> Option<&U> {
+        self.as_any().downcast_ref::<U>()
+    }
+}
+
+/// 異種のノードを持つグラフ
+pub type DynGraph = Graph<Box<dyn NodeValue>>;
+
+// 値を DynGraph のノードにする
+pub fn dyn_node<U: NodeValue>(u: U) -> Box<dyn NodeValue> {
+    Box::new(u)
+}
+
+// 問い合わせの結果から型 U のノードだけを取り出す
+pub fn downcast_nodes<'a, U: Any>(
+    nodes: impl IntoIterator<Item = &'a Box<dyn NodeValue>>,
+) -> Vec<&'a U> {
+    nodes
+        .into_iter()
+        .filter_map(|u| u.downcast_ref::<U>())
+        .collect()
+}
+
+impl Graph<Box<dyn NodeValue>> {
+    // 型 U のノードをすべて返す
+    pub fn nodes_of_type<U: Any>(&self) -> Vec<&U> {
+        downcast_nodes(self.id_dict.keys())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downcast_nodes, dyn_node, DynGraph};
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct File(&'static str);
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Crate(&'static str);
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Function(&'static str);
+
+    #[test]
+    fn test_dyn_graph() {
+        let mut g = DynGraph::new();
+        assert!(g.add_node(dyn_node(Crate("core"))).is_ok());
+        assert!(g.add_node(dyn_node(File("lib.rs"))).is_ok());
+        assert!(g.add_node(dyn_node(File("main.rs"))).is_ok());
+        assert!(g.add_node(dyn_node(Function("main"))).is_ok());
+        // 中身が同じでも型が違えば別のノード
+        assert!(g.add_node(dyn_node(Function("core"))).is_ok());
+        assert!(g.add_node(dyn_node(File("lib.rs"))).is_err());
+
+        let _ = g.add_edge(&dyn_node(Crate("core")), &dyn_node(File("lib.rs")));
+        let _ = g.add_edge(&dyn_node(File("main.rs")), &dyn_node(Function("main")));
+        let _ = g.add_edge(&dyn_node(Function("main")), &dyn_node(Crate("core")));
+
+        let mut files = g.nodes_of_type::<File>();
+        files.sort_by_key(|f| f.0);
+        assert_eq!(files, vec![&File("lib.rs"), &File("main.rs")]);
+
+        let path = g
+            .shortest_path(&dyn_node(File("main.rs")), &dyn_node(File("lib.rs")))
+            .unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!(downcast_nodes::<Crate>(path.iter()), vec![&Crate("core")]);
+        assert!(path.nodes()[1].is::<Function>());
+        assert_eq!(
+            path.nodes()[1].downcast_ref::<Function>(),
+            Some(&Function("main"))
+        );
+        assert_eq!(path.nodes()[1].downcast_ref::<File>(), None);
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+// 塗りかけの辺彩色．ノードごとに色 → その色の辺の相手を持つ
+#[derive(Default)]
+struct Coloring {
+    at: HashMap<NodeID, HashMap<usize, NodeID>>,
+}
+
+impl Coloring {
+    fn is_free(&self, x: NodeID, c: usize) -> bool {
+        self.at.get(&x).is_none_or(|m| !m.contains_key(&c))
+    }
+
+    // x で使われていない最小の色
+    fn free(&self, x: NodeID) -> usize {
+        (0..).find(|&c| self.is_free(x, c)).unwrap()
+    }
+
+    fn color_of(&self, x: NodeID, y: NodeID) -> Option<usize> {
+        self.at
+            .get(&x)?
+            .iter()
+            .find(|(_, &z)| z == y)
+            .map(|(&c, _)| c)
+    }
+
+    fn set(&mut self, x: NodeID, y: NodeID, c: usize) {
+        self.at.entry(x).or_default().insert(c, y);
+        self.at.entry(y).or_default().insert(c, x);
+    }
+
+    fn unset(&mut self, x: NodeID, y: NodeID, c: usize) {
+        self.at.get_mut(&x).unwrap().remove(&c);
+        self.at.get_mut(&y).unwrap().remove(&c);
+    }
+
+    // `start` から色 a, b, a, ... の順にたどれる交互路の色を入れ替える
+    fn invert_path(&mut self, start: NodeID, a: usize, b: usize) {
+        let mut edges = Vec::new();
+        let (mut x, mut c) = (start, a);
+        while let Some(&y) = self.at.get(&x).and_then(|m| m.get(&c)) {
+            edges.push((x, y, c));
+            x = y;
+            c = if c == a { b } else { a };
+        }
+        for &(x, y, c) in &edges {
+            self.unset(x, y, c);
+        }
+        for (x, y, c) in edges {
+            self.set(x, y, if c == a { b } else { a });
+        }
+    }
+}
+
+impl GraphCore {
+    // 向きを無視した辺 (自己ループを除く) を (小さい ID, 大きい ID) の順に
+    fn undirected_edges(&self) -> Vec<(NodeID, NodeID)> {
+        let edges: HashSet<(NodeID, NodeID)> = self
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&c| (id.min(c), id.max(c))))
+            .filter(|(a, b)| a != b)
+            .collect();
+        let mut edges: Vec<(NodeID, NodeID)> = edges.into_iter().collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    // 向きを無視した辺彩色 (隣り合う辺は違う色)．二部グラフなら交互路の入れ替えで
+    // 最大次数 Δ 色，そうでなければ Misra–Gries の方法で Δ + 1 色以下で塗る．自己ループは無視する
+    pub fn edge_coloring(&self) -> HashMap<(NodeID, NodeID), usize> {
+        let edges = self.undirected_edges();
+        let mut loopless = self.clone();
+        for (&id, n) in loopless.nodes_dict.iter_mut() {
+            n.children.remove(&id);
+            n.parents.remove(&id);
+        }
+        let bipartite = loopless.two_coloring(&HashSet::new()).is_ok();
+
+        let mut coloring = Coloring::default();
+        for &(u, v) in &edges {
+            if bipartite {
+                let a = coloring.free(u);
+                let b = coloring.free(v);
+                if !coloring.is_free(v, a) {
+                    // v から a, b の交互路は u に届かないので，入れ替えると a が v でも空く
+                    coloring.invert_path(v, a, b);
+                }
+                coloring.set(u, v, a);
+            } else {
+                misra_gries_step(&mut coloring, u, v);
+            }
+        }
+
+        edges
+            .into_iter()
+            .map(|(u, v)| ((u, v), coloring.color_of(u, v).unwrap()))
+            .collect()
+    }
+}
+
+// Misra–Gries の 1 ステップ: 辺 u - v を塗る
+fn misra_gries_step(coloring: &mut Coloring, u: NodeID, v: NodeID) {
+    // u の極大な扇 [v, f1, f2, ...]: 辺 u - f(i+1) の色が f(i) で空いている
+    let mut fan = vec![v];
+    loop {
+        let last = *fan.last().unwrap();
+        let next = coloring
+            .at
+            .get(&u)
+            .into_iter()
+            .flatten()
+            .filter(|(&c, x)| coloring.is_free(last, c) && !fan.contains(x))
+            .min_by_key(|(&c, _)| c)
+            .map(|(_, &x)| x);
+        match next {
+            Some(x) => fan.push(x),
+            None => break,
+        }
+    }
+
+    let c = coloring.free(u);
+    let d = coloring.free(*fan.last().unwrap());
+    coloring.invert_path(u, d, c);
+
+    // d が空いていて，そこまでが扇のままのノード
+    let is_fan_prefix = |coloring: &Coloring, i: usize| {
+        (1..=i).all(|j| {
+            coloring
+                .color_of(u, fan[j])
+                .is_some_and(|col| coloring.is_free(fan[j - 1], col))
+        })
+    };
+    let w = (0..fan.len())
+        .find(|&i| coloring.is_free(fan[i], d) && is_fan_prefix(coloring, i))
+        .unwrap();
+
+    // 扇を回転させ，空いた u - fan[w] を d で塗る
+    let shifted: Vec<usize> = (0..w)
+        .map(|j| coloring.color_of(u, fan[j + 1]).unwrap())
+        .collect();
+    for (j, &col) in shifted.iter().enumerate() {
+        coloring.unset(u, fan[j + 1], col);
+    }
+    for (j, &col) in shifted.iter().enumerate() {
+        coloring.set(u, fan[j], col);
+    }
+    coloring.set(u, fan[w], d);
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 向きを無視した辺彩色を色ごとの辺の集合 (マッチング) として返す．総当たり戦の組み合わせなら
+    // 各色が 1 ラウンドになる．二部グラフは最大次数 Δ 色 (最適)，それ以外は Δ + 1 色以下．
+    // 両方向のエッジは 1 本として扱い，ID の小さい方を from にする．自己ループは含めない
+    pub fn edge_coloring(&self) -> Vec<Vec<(&T, &T)>> {
+        let colors = self.core.edge_coloring();
+        let nodes = self.nodes_by_id();
+
+        let mut edges: Vec<(&(NodeID, NodeID), &usize)> = colors.iter().collect();
+        edges.sort_unstable();
+        let count = colors.values().max().map_or(0, |&c| c + 1);
+        let mut ret: Vec<Vec<(&T, &T)>> = vec![Vec::new(); count];
+        for (&(a, b), &c) in edges {
+            ret[c].push((nodes[&a], nodes[&b]));
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::super::graph::Graph;
+
+    // すべての辺がちょうど 1 回ずつ現れ，同じ色の辺が端点を共有しないか
+    fn assert_proper(g: &Graph<u32>, classes: &[Vec<(&u32, &u32)>]) {
+        let mut seen = HashSet::new();
+        for class in classes {
+            let mut used = HashSet::new();
+            for &(a, b) in class {
+                assert!(used.insert(a) && used.insert(b), "{a} - {b}");
+                assert!(seen.insert((a, b)));
+            }
+        }
+        let expected: HashSet<(u32, u32)> = g
+            .edges()
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (*a.min(b), *a.max(b)))
+            .collect();
+        assert_eq!(seen.len(), expected.len());
+    }
+
+    #[test]
+    fn test_edge_coloring_bipartite() {
+        // K3,3 は 3 色
+        let mut g = Graph::new();
+        for u in 0..6 {
+            let _ = g.add_node(u);
+        }
+        for a in 0..3 {
+            for b in 3..6 {
+                let _ = g.add_edge(&a, &b);
+            }
+        }
+        let _ = g.add_edge(&4, &0); // 逆向きは同じ辺
+        let _ = g.add_edge(&1, &1); // 自己ループは無視
+
+        let classes = g.edge_coloring();
+        assert_eq!(classes.len(), 3);
+        assert_proper(&g, &classes);
+    }
+
+    #[test]
+    fn test_edge_coloring_vizing() {
+        // K5 (Δ = 4) とペテルセングラフ (Δ = 3) はどちらも Δ + 1 色が必要
+        let mut k5 = Graph::new();
+        for u in 0..5 {
+            let _ = k5.add_node(u);
+        }
+        for a in 0..5 {
+            for b in a + 1..5 {
+                let _ = k5.add_edge(&a, &b);
+            }
+        }
+        let classes = k5.edge_coloring();
+        assert_eq!(classes.len(), 5);
+        assert_proper(&k5, &classes);
+
+        let mut petersen = Graph::new();
+        for u in 0..10 {
+            let _ = petersen.add_node(u);
+        }
+        for i in 0..5 {
+            let _ = petersen.add_edge(&i, &((i + 1) % 5));
+            let _ = petersen.add_edge(&i, &(i + 5));
+            let _ = petersen.add_edge(&(i + 5), &((i + 2) % 5 + 5));
+        }
+        let classes = petersen.edge_coloring();
+        assert_eq!(classes.len(), 4);
+        assert_proper(&petersen, &classes);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 関係の種類 `label` 付きでエッジを追加する．すでにラベルがあれば上書きする
+    pub fn add_edge_with_label<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        label: &str,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ret = self.add_edge(u_from, u_to)?;
+
+        let key = (*self.node_id(u_from).unwrap(), *self.node_id(u_to).unwrap());
+        self.labels.insert(key, label.to_string());
+        Ok(ret)
+    }
+
+    // エッジのラベル．エッジが無いかラベルが付いていなければ None
+    pub fn edge_label<Q>(&self, u_from: &Q, u_to: &Q) -> Option<&str>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = self.node_id(u_from)?;
+        let to_id = self.node_id(u_to)?;
+        self.labels.get(&(*from_id, *to_id)).map(|l| l.as_str())
+    }
+
+    // ラベルが `label` のエッジ (ノードの追加順)
+    pub fn edges_with_label(&self, label: &str) -> Vec<(&T, &T)> {
+        let nodes = self.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .labels
+            .iter()
+            .filter(|(_, l)| l.as_str() == label)
+            .map(|(&key, _)| key)
+            .collect();
+        edges.sort_unstable();
+        edges.iter().map(|(a, b)| (nodes[a], nodes[b])).collect()
+    }
+
+    // `u` から出るエッジの行き先とラベル (ノードの追加順)
+    pub fn labeled_children<Q>(&self, u: &Q) -> Vec<(&T, Option<&str>)>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&id) = self.node_id(u) else {
+            return Vec::new();
+        };
+
+        let nodes = self.nodes_by_id();
+        let mut children = self.core.children_of(id);
+        children.sort_unstable();
+        children
+            .into_iter()
+            .map(|c| (nodes[&c], self.labels.get(&(id, c)).map(|l| l.as_str())))
+            .collect()
+    }
+
+    // ラベルが `label` のエッジだけをたどって `start` から到達できるノードを幅優先順に返す
+    // (`start` 自身を含む)．`start` が未登録なら空
+    pub fn reachable_via<Q>(&self, start: &Q, label: &str) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&start_id) = self.node_id(start) else {
+            return Vec::new();
+        };
+
+        let mut visited = HashSet::from([start_id]);
+        let mut queue = VecDeque::from([start_id]);
+        let mut order = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+
+            let mut children = self.core.children_of(id);
+            children.sort_unstable();
+            for c in children {
+                if self.labels.get(&(id, c)).is_some_and(|l| l == label) && visited.insert(c) {
+                    queue.push_back(c);
+                }
+            }
+        }
+        self.nodes_from_ids(&order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_edge_labels() {
+        let mut g = Graph::new();
+        for u in ["app", "lib", "util", "log"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge_with_label(&"app", &"lib", "depends-on");
+        let _ = g.add_edge_with_label(&"lib", &"util", "depends-on");
+        let _ = g.add_edge_with_label(&"app", &"log", "imports");
+        let _ = g.add_edge(&"util", &"log");
+        assert!(g.add_edge_with_label(&"app", &"x", "imports").is_err());
+
+        assert_eq!(g.edge_label(&"app", &"lib"), Some("depends-on"));
+        assert_eq!(g.edge_label(&"util", &"log"), None);
+        assert_eq!(
+            g.edges_with_label("depends-on"),
+            vec![(&"app", &"lib"), (&"lib", &"util")]
+        );
+        assert_eq!(
+            g.labeled_children(&"app"),
+            vec![(&"lib", Some("depends-on")), (&"log", Some("imports"))]
+        );
+        assert_eq!(
+            g.reachable_via(&"app", "depends-on"),
+            vec![&"app", &"lib", &"util"]
+        );
+
+        // ラベルは上書きされ，エッジを消せば消える
+        let _ = g.add_edge_with_label(&"app", &"log", "depends-on");
+        assert_eq!(g.reachable_via(&"app", "depends-on").len(), 4);
+        let _ = g.remove_edge(&"app", &"lib");
+        assert_eq!(g.edge_label(&"app", &"lib"), None);
+    }
+}
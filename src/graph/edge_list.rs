@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+};
+
+use super::{error::GraphError, graph::Graph, union_find::UnionFind};
+
+// 1 行を (from, to, 重み) に分ける．i は 0 始まりの行番号
+fn parse_edge_line(i: usize, line: &str) -> Result<(String, String, Option<f64>), String> {
+    let fields: Vec<&str> = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|f| f.trim())
+        .filter(|f| !f.is_empty())
+        .collect();
+    match fields.as_slice() {
+        [from, to] => Ok((from.to_string(), to.to_string(), None)),
+        [from, to, w] => {
+            let w = w
+                .parse::<f64>()
+                .map_err(|_| format!("line {}: invalid weight {:?}", i + 1, w))?;
+            Ok((from.to_string(), to.to_string(), Some(w)))
+        }
+        _ => Err(format!(
+            "line {}: expected `from to`, found {:?}",
+            i + 1,
+            line
+        )),
+    }
+}
+
+// エッジリストを 1 行ずつ読みながら素集合で連結成分 (向きは無視) を求め，
+// `node,component_id` の CSV を書き出す．隣接リストは持たないので巨大な入力にも使える．
+// ノードと成分の番号は入力に最初に現れた順．成分の数を返す
+pub fn write_component_labels<R: Read, W: Write>(
+    reader: R,
+    w: &mut W,
+) -> Result<usize, GraphError> {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut uf = UnionFind::new(0);
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (from, to, _) = parse_edge_line(i, line).map_err(GraphError::Parse)?;
+        let mut id = |u: String| match index.get(&u) {
+            Some(&x) => x,
+            None => {
+                let x = uf.push();
+                index.insert(u.clone(), x);
+                names.push(u);
+                x
+            }
+        };
+        let (a, b) = (id(from), id(to));
+        uf.union(a, b);
+    }
+    drop(index);
+
+    let mut labels: HashMap<usize, usize> = HashMap::new();
+    writeln!(w, "node,component_id")?;
+    for (x, name) in names.iter().enumerate() {
+        let root = uf.find(x);
+        let next = labels.len();
+        let label = *labels.entry(root).or_insert(next);
+        writeln!(w, "{},{}", name, label)?;
+    }
+    Ok(labels.len())
+}
+
+impl Graph<String> {
+    // `from to` (空白またはカンマ区切り) の行からグラフを作る
+    // 3 列目があれば重みとして読む．空行と # で始まる行は読み飛ばす
+    pub fn from_edge_list<R: Read>(reader: R) -> Result<Graph<String>, GraphError> {
+        let mut g = Graph::new();
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (from, to, weight) = parse_edge_line(i, line).map_err(GraphError::Parse)?;
+
+            for u in [&from, &to] {
+                if !g.id_dict.contains_key(u) {
+                    g.add_node(u.clone())?;
+                }
+            }
+            match weight {
+                Some(w) => g.add_weighted_edge(&from, &to, w)?,
+                None => g.add_edge(&from, &to)?,
+            };
+        }
+
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+    use super::write_component_labels;
+
+    #[test]
+    fn test_from_edge_list() {
+        let input = "# from to\n\na b\nb,c\n c , a \nc\td 2.5\n";
+        let g = Graph::from_edge_list(input.as_bytes()).unwrap();
+
+        let s = |x: &str| x.to_string();
+        assert_eq!(g.edge_weight(&s("a"), &s("b")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("b"), &s("c")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("c"), &s("a")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("c"), &s("d")), Some(2.5));
+        assert_eq!(g.detect_cycle().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_from_edge_list_errors() {
+        let err = Graph::from_edge_list("a b\nc\n".as_bytes()).err().unwrap();
+        assert!(
+            matches!(&err, GraphError::Parse(msg) if msg.starts_with("line 2:")),
+            "{err}"
+        );
+        assert!(Graph::from_edge_list("a b x".as_bytes()).is_err());
+        assert!(Graph::from_edge_list("a b 1 2".as_bytes()).is_err());
+        assert!(Graph::from_edge_list(&[0xff, b' ', b'a'][..]).is_err());
+    }
+
+    #[test]
+    fn test_write_component_labels() {
+        let input = "a b\nc d 2.0\n# comment\nb e\nf f\n";
+        let mut out = Vec::new();
+
+        assert_eq!(write_component_labels(input.as_bytes(), &mut out), Ok(3));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "node,component_id\na,0\nb,0\nc,1\nd,1\ne,0\nf,2\n"
+        );
+        assert!(write_component_labels("a\n".as_bytes(), &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+/// 追記だけで更新するエッジのログファイル
+///
+/// 1 行が 1 つの記録で，フィールドはタブ区切り．
+///
+/// - `+ from to weight`: エッジを追加する (既にあれば重みを上書きする)
+/// - `- from to`: エッジを削除する
+/// - `n name`: ノードを追加する
+///
+/// 記録は 1 回の書き込みで行ごと追記するので，書き込み中に読んでも改行で終わっていない
+/// 最後の行を無視すればそれまでの記録と一致するグラフが得られる
+pub struct EdgeLog {
+    path: PathBuf,
+    file: File,
+}
+
+// ノード名はタブと改行を含まない空でない文字列
+fn check_name(u: &str) -> Result<(), GraphError> {
+    if u.is_empty() || u.contains(['\t', '\n', '\r']) {
+        return Err(GraphError::InvalidArgument(format!(
+            "node name must be non-empty and contain no tabs or newlines: {:?}",
+            u
+        )));
+    }
+    Ok(())
+}
+
+impl EdgeLog {
+    // ログファイルを開く．無ければ空のログを作る
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<EdgeLog, GraphError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(EdgeLog { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write_record(&mut self, fields: &[&str]) -> Result<(), GraphError> {
+        let mut line = fields.join("\t");
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    // エッジ from → to を重み `weight` で追記する
+    pub fn append(&mut self, from: &str, to: &str, weight: f64) -> Result<(), GraphError> {
+        check_name(from)?;
+        check_name(to)?;
+        self.write_record(&["+", from, to, &weight.to_string()])
+    }
+
+    pub fn append_removal(&mut self, from: &str, to: &str) -> Result<(), GraphError> {
+        check_name(from)?;
+        check_name(to)?;
+        self.write_record(&["-", from, to])
+    }
+
+    // エッジの無いノードを記録する
+    pub fn append_node(&mut self, u: &str) -> Result<(), GraphError> {
+        check_name(u)?;
+        self.write_record(&["n", u])
+    }
+
+    // ログを先頭から再生したグラフ (読んだ時点のスナップショット)．
+    // 存在しないエッジの削除は無視する
+    pub fn load(&self) -> Result<Graph<String>, GraphError> {
+        let mut content = String::new();
+        File::open(&self.path)?.read_to_string(&mut content)?;
+
+        let mut g = Graph::new();
+        // 改行で終わっていない行は書き込み途中の記録なので読まない
+        for (i, line) in content.split_inclusive('\n').enumerate() {
+            let Some(line) = line.strip_suffix('\n') else {
+                break;
+            };
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields.as_slice() {
+                ["+", from, to, w] => {
+                    let w = w.parse::<f64>().map_err(|_| {
+                        GraphError::Parse(format!("line {}: invalid weight {:?}", i + 1, w))
+                    })?;
+                    g.ensure_node(from)?;
+                    g.ensure_node(to)?;
+                    g.add_weighted_edge(*from, *to, w)?;
+                }
+                ["-", from, to] => {
+                    if g.contains_edge(*from, *to) {
+                        g.remove_edge(*from, *to)?;
+                    }
+                }
+                ["n", u] => g.ensure_node(u)?,
+                _ => {
+                    return Err(GraphError::Parse(format!(
+                        "line {}: invalid record {:?}",
+                        i + 1,
+                        line
+                    )))
+                }
+            }
+        }
+        Ok(g)
+    }
+
+    // 今のグラフと同じになる最小限の記録 (ノードの追加順にエッジ，エッジの無いノード) に
+    // 書き直す．別のファイルに書いてから置き換えるので，途中で失敗しても元のログは残る．
+    // 書き直した後の記録の数を返す
+    pub fn compact(&mut self) -> Result<usize, GraphError> {
+        let g = self.load()?;
+        let nodes = g.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut out = String::new();
+        let mut records = 0;
+        for &id in &ids {
+            let mut children = g.core.children_of(id);
+            if children.is_empty() && g.core.nodes_dict[&id].parents.is_empty() {
+                out.push_str(&format!("n\t{}\n", nodes[&id]));
+                records += 1;
+            }
+            children.sort_unstable();
+            for child in children {
+                out.push_str(&format!(
+                    "+\t{}\t{}\t{}\n",
+                    nodes[&id],
+                    nodes[&child],
+                    g.weight_of(id, child)
+                ));
+                records += 1;
+            }
+        }
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".compact");
+        let tmp = PathBuf::from(tmp);
+        {
+            let mut f = File::create(&tmp)?;
+            f.write_all(out.as_bytes())?;
+            f.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)?;
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use super::super::error::GraphError;
+    use super::EdgeLog;
+
+    #[test]
+    fn test_edge_log() {
+        let path = std::env::temp_dir().join(format!("graph_edge_log_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut log = EdgeLog::open(&path).unwrap();
+        log.append("a", "b", 1.0).unwrap();
+        log.append("b", "c", 2.0).unwrap();
+        log.append("a", "b", 3.5).unwrap();
+        log.append("c", "d", 1.0).unwrap();
+        log.append_removal("c", "d").unwrap();
+        log.append_removal("x", "y").unwrap();
+        log.append_node("lonely").unwrap();
+        assert!(matches!(
+            log.append("a\tb", "c", 1.0),
+            Err(GraphError::InvalidArgument(_))
+        ));
+
+        // 別のハンドルで開き直しても同じ内容が読める
+        let g = EdgeLog::open(&path).unwrap().load().unwrap();
+        assert_eq!(g.edge_weight("a", "b"), Some(3.5));
+        assert!(!g.contains_edge("c", "d"));
+        assert!(g.contains_node("d"));
+        assert!(g.contains_node("lonely"));
+        assert_eq!(g.edge_count(), 2);
+
+        // 書き込み途中の行は読まない
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"+\tc\ta")
+            .unwrap();
+        assert_eq!(log.load().unwrap().edge_count(), 2);
+        // 続きが書かれれば読める
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"\t1\n")
+            .unwrap();
+        assert!(log.load().unwrap().contains_edge("c", "a"));
+
+        assert_eq!(log.compact(), Ok(5));
+        let compacted = log.load().unwrap();
+        assert_eq!(compacted.node_count(), 5);
+        assert_eq!(compacted.edge_weight("a", "b"), Some(3.5));
+        assert!(compacted.contains_node("d"));
+        assert!(compacted.contains_edge("c", "a"));
+
+        // 書き直した後も追記できる
+        log.append("d", "a", 1.0).unwrap();
+        let g = log.load().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(g.contains_edge("d", "a"));
+        assert_eq!(g.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_edge_log_invalid_record() {
+        let path = std::env::temp_dir().join(format!("graph_edge_log_bad_{}", std::process::id()));
+        fs::write(&path, "+\ta\tb\t1\n?\ta\n").unwrap();
+        let err = EdgeLog::open(&path).unwrap().load().err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(
+            matches!(&err, GraphError::Parse(msg) if msg.starts_with("line 2:")),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::{collections::BinaryHeap, fmt::Debug, hash::Hash};
+
+use super::{core::NodeID, dijkstra::MinScored, graph::Graph};
+
+/// グラフ編集距離で使う各操作のコスト
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EditCosts {
+    pub node_insertion: f64,
+    pub node_deletion: f64,
+    // 値の異なるノードへの置き換え
+    pub node_substitution: f64,
+    pub edge_insertion: f64,
+    pub edge_deletion: f64,
+    // 重みの異なるエッジへの置き換え
+    pub edge_substitution: f64,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        Self {
+            node_insertion: 1.0,
+            node_deletion: 1.0,
+            node_substitution: 1.0,
+            edge_insertion: 1.0,
+            edge_deletion: 1.0,
+            edge_substitution: 1.0,
+        }
+    }
+}
+
+// 探索で使う片方のグラフの情報 (ノードは ID 順に番号を振り直す)
+struct Side<'a, T> {
+    values: Vec<&'a T>,
+    adj: Vec<Vec<Option<f64>>>,
+    edges: Vec<(usize, usize)>,
+}
+
+// 探索の状態．self の先頭から assign.len() 個のノードの対応付け (None は削除) と，
+// 使用済みの other のノード，ここまでのコスト
+#[derive(Clone)]
+struct EditState {
+    assign: Vec<Option<usize>>,
+    used: Vec<bool>,
+    cost: f64,
+    // other の残りのノードの挿入まで済んだか
+    done: bool,
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    fn edit_side(&self) -> Side<'_, T> {
+        let mut ids: Vec<NodeID> = self.id_dict.values().copied().collect();
+        ids.sort_unstable();
+        let (values, adj) = self.adjacency_matrix();
+
+        let adj: Vec<Vec<Option<f64>>> = adj
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &e)| e.then(|| self.weight_of(ids[i], ids[j])))
+                    .collect()
+            })
+            .collect();
+        let edges = (0..ids.len())
+            .flat_map(|i| (0..ids.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| adj[i][j].is_some())
+            .collect();
+
+        Side { values, adj, edges }
+    }
+
+    // 単位コストでのグラフ編集距離
+    pub fn edit_distance(&self, other: &Graph<T>) -> f64 {
+        self.edit_distance_with(other, &EditCosts::default())
+    }
+
+    // self を other に変換する編集操作の最小コスト．self のノードを順に other のノード
+    // (または削除) に対応付ける A* 探索で厳密に求めるので，数十ノード程度までを想定している
+    pub fn edit_distance_with(&self, other: &Graph<T>, costs: &EditCosts) -> f64 {
+        let (a, b) = (self.edit_side(), other.edit_side());
+        let (n, m) = (a.values.len(), b.values.len());
+
+        let edge_cost = |e1: Option<f64>, e2: Option<f64>| match (e1, e2) {
+            (Some(w1), Some(w2)) if w1 != w2 => costs.edge_substitution,
+            (Some(_), None) => costs.edge_deletion,
+            (None, Some(_)) => costs.edge_insertion,
+            _ => 0.0,
+        };
+
+        // 未処理の部分の下界．残りのノード数とエッジ数の差の分だけは挿入か削除が必要
+        let heuristic = |k: usize, used: &[bool]| {
+            let n_rest = n - k;
+            let m_rest = used.iter().filter(|&&u| !u).count();
+            let e1 = a.edges.iter().filter(|&&(i, j)| i >= k || j >= k).count();
+            let e2 = b
+                .edges
+                .iter()
+                .filter(|&&(i, j)| !used[i] || !used[j])
+                .count();
+
+            let nodes = if n_rest > m_rest {
+                (n_rest - m_rest) as f64 * costs.node_deletion
+            } else {
+                (m_rest - n_rest) as f64 * costs.node_insertion
+            };
+            let edges = if e1 > e2 {
+                (e1 - e2) as f64 * costs.edge_deletion
+            } else {
+                (e2 - e1) as f64 * costs.edge_insertion
+            };
+            nodes + edges
+        };
+
+        // ヒープには状態の番号を入れる
+        let mut states = vec![EditState {
+            assign: Vec::new(),
+            used: vec![false; m],
+            cost: 0.0,
+            done: false,
+        }];
+        let mut heap = BinaryHeap::new();
+        heap.push(MinScored(heuristic(0, &states[0].used), 0));
+
+        while let Some(MinScored(_, s)) = heap.pop() {
+            let EditState {
+                assign,
+                used,
+                cost,
+                done,
+            } = states[s].clone();
+            if done {
+                return cost;
+            }
+
+            let i = assign.len();
+            if i == n {
+                // 残った other のノードとそれに接するエッジを挿入する
+                let rest = used.iter().filter(|&&u| !u).count() as f64 * costs.node_insertion
+                    + b.edges
+                        .iter()
+                        .filter(|&&(x, y)| !used[x] || !used[y])
+                        .count() as f64
+                        * costs.edge_insertion;
+                states.push(EditState {
+                    assign,
+                    used,
+                    cost: cost + rest,
+                    done: true,
+                });
+                heap.push(MinScored(cost + rest, states.len() - 1));
+                continue;
+            }
+
+            let targets = (0..m).filter(|&j| !used[j]).map(Some).chain([None]);
+            for t in targets {
+                let mut c = match t {
+                    Some(j) if a.values[i] != b.values[j] => costs.node_substitution,
+                    Some(_) => 0.0,
+                    None => costs.node_deletion,
+                };
+
+                // i と処理済みのノードの間のエッジ (自己ループを含む)
+                let mapped = |p: usize| if p == i { t } else { assign[p] };
+                for p in 0..=i {
+                    let pairs = if p == i {
+                        vec![(i, i)]
+                    } else {
+                        vec![(i, p), (p, i)]
+                    };
+                    for (x, y) in pairs {
+                        let e2 = match (mapped(x), mapped(y)) {
+                            (Some(u), Some(v)) => b.adj[u][v],
+                            _ => None,
+                        };
+                        c += edge_cost(a.adj[x][y], e2);
+                    }
+                }
+
+                let mut next_assign = assign.clone();
+                next_assign.push(t);
+                let mut next_used = used.clone();
+                if let Some(j) = t {
+                    next_used[j] = true;
+                }
+                let h = heuristic(i + 1, &next_used);
+                states.push(EditState {
+                    assign: next_assign,
+                    used: next_used,
+                    cost: cost + c,
+                    done: false,
+                });
+                heap.push(MinScored(cost + c + h, states.len() - 1));
+            }
+        }
+
+        unreachable!("完了した状態は必ずヒープに入る")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::EditCosts;
+
+    fn path(nodes: &[&'static str]) -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in nodes {
+            let _ = g.add_node(*u);
+        }
+        for pair in nodes.windows(2) {
+            let _ = g.add_edge(&pair[0], &pair[1]);
+        }
+        g
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let g = path(&["A", "B", "C"]);
+        assert_eq!(g.edit_distance(&path(&["A", "B", "C"])), 0.0);
+        // C → D の挿入とノード D の挿入
+        assert_eq!(g.edit_distance(&path(&["A", "B", "C", "D"])), 2.0);
+        // C を X に置き換える
+        assert_eq!(g.edit_distance(&path(&["A", "B", "X"])), 1.0);
+
+        let mut h = path(&["A", "B", "C"]);
+        let _ = h.add_edge(&"C", &"A");
+        let _ = h.add_weighted_edge(&"A", &"B", 3.0);
+        assert_eq!(g.edit_distance(&h), 2.0);
+    }
+
+    #[test]
+    fn test_edit_distance_with_costs() {
+        let g = path(&["A", "B"]);
+        let h = path(&["X", "Y"]);
+        let costs = EditCosts {
+            node_substitution: 5.0,
+            ..EditCosts::default()
+        };
+
+        // 置き換え (5 × 2) より削除と挿入 (ノード 4 + エッジ 2) の方が安い
+        assert_eq!(g.edit_distance_with(&h, &costs), 6.0);
+        assert_eq!(g.edit_distance(&h), 2.0);
+    }
+}
@@ -0,0 +1,146 @@
+use std::{error::Error, fmt};
+
+use super::{core::NodeID, handle::NodeHandle};
+
+/// グラフ操作のエラー．ノードは NodeHandle で持つので，ノードの型に Debug などは要らない
+/// (名前で表示したければ `Graph::describe_error` を使う)．ノードを削除した後もハンドルが
+/// 別のノードを指すことはない
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    // 追加しようとしたノードはすでに `node` として登録されている
+    NodeAlreadyExists {
+        node: NodeHandle,
+    },
+    // 引数 `arg` (from, to など) に渡されたノードが登録されていない
+    NodeNotFound {
+        arg: &'static str,
+    },
+    // 追加しようとしたエッジ from -> to はすでにある
+    EdgeExists {
+        from: NodeHandle,
+        to: NodeHandle,
+    },
+    // ハンドルの指すノードは削除されている (別のグラフのハンドルも含む)
+    StaleHandle {
+        handle: NodeHandle,
+    },
+    // GraphCore に同じ NodeID のノードを追加しようとした
+    DuplicateNodeId {
+        id: NodeID,
+    },
+    // マージ時に両方のグラフにあるエッジの重みが食い違った
+    ConflictingWeights {
+        from: NodeHandle,
+        to: NodeHandle,
+        existing: f64,
+        incoming: f64,
+    },
+    // DAG であることが必要な操作でサイクルが見つかった (先頭と末尾は同じノード)
+    CycleDetected {
+        cycle: Vec<NodeHandle>,
+    },
+    // 自己ループを禁止したグラフに自己ループを追加しようとした
+    SelfLoopForbidden {
+        node: NodeHandle,
+    },
+    // 経路の重みを足し合わせたときに桁あふれした (エッジ from -> to を足したところ)
+    WeightOverflow {
+        from: NodeHandle,
+        to: NodeHandle,
+    },
+    InvalidArgument(String),
+    // 入力形式 (DOT, GraphML, エッジリストなど) の読み込みに失敗した
+    Parse(String),
+    Io(String),
+}
+
+impl GraphError {
+    // ノードを `name` で文字列にして表示する
+    pub(crate) fn render<F: Fn(NodeHandle) -> String>(&self, name: F) -> String {
+        match self {
+            GraphError::NodeAlreadyExists { node } => {
+                format!("node {} is already added", name(*node))
+            }
+            GraphError::NodeNotFound { arg } => format!("node passed as `{}` is not added", arg),
+            GraphError::EdgeExists { from, to } => {
+                format!("edge {} -> {} is already added", name(*from), name(*to))
+            }
+            GraphError::StaleHandle { handle } => {
+                format!("node {} has been removed", handle)
+            }
+            GraphError::DuplicateNodeId { id } => {
+                format!("duplication: node {} is already added", id)
+            }
+            GraphError::ConflictingWeights {
+                from,
+                to,
+                existing,
+                incoming,
+            } => format!(
+                "conflicting weights for edge {} -> {}: {} vs {}",
+                name(*from),
+                name(*to),
+                existing,
+                incoming
+            ),
+            GraphError::CycleDetected { cycle } => {
+                let path: Vec<String> = cycle.iter().map(|&id| name(id)).collect();
+                format!("graph has a cycle: {}", path.join(" -> "))
+            }
+            GraphError::SelfLoopForbidden { node } => {
+                format!("self-loop on node {} is not allowed", name(*node))
+            }
+            GraphError::WeightOverflow { from, to } => {
+                format!("weight overflow at edge {} -> {}", name(*from), name(*to))
+            }
+            GraphError::InvalidArgument(msg) => format!("invalid argument: {}", msg),
+            GraphError::Parse(msg) => format!("parse error: {}", msg),
+            GraphError::Io(msg) => format!("I/O error: {}", msg),
+        }
+    }
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|h| h.to_string()))
+    }
+}
+
+impl Error for GraphError {}
+
+impl From<std::io::Error> for GraphError {
+    fn from(e: std::io::Error) -> Self {
+        GraphError::Io(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::handle::NodeHandle;
+    use super::GraphError;
+
+    #[test]
+    fn test_graph_error_display() {
+        assert_eq!(
+            GraphError::NodeNotFound { arg: "to" }.to_string(),
+            "node passed as `to` is not added"
+        );
+        assert_eq!(
+            GraphError::Parse("line 2: expected `from to`".to_string()).to_string(),
+            "parse error: line 2: expected `from to`"
+        );
+        assert_eq!(
+            GraphError::CycleDetected {
+                cycle: vec![
+                    NodeHandle::new(0, 0),
+                    NodeHandle::new(1, 2),
+                    NodeHandle::new(0, 0)
+                ]
+            }
+            .to_string(),
+            "graph has a cycle: #0 -> #1@2 -> #0"
+        );
+        let e: Box<dyn std::error::Error> = Box::new(GraphError::DuplicateNodeId { id: 3 });
+        assert_eq!(e.to_string(), "duplication: node 3 is already added");
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    borrow::Borrow,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt::Debug,
+    hash::Hash,
+    sync::{Condvar, Mutex},
+    thread,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+};
+
+/// 1 つのタスク (ノード) の実行結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskOutcome<R, E> {
+    Succeeded(R),
+    Failed(E),
+    // 依存先 (親) のどれかが失敗またはスキップされたので実行しなかった
+    Skipped,
+}
+
+/// `run_tasks` の結果．`outcomes` はノードの追加順
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport<'a, T, R, E> {
+    pub outcomes: Vec<(&'a T, TaskOutcome<R, E>)>,
+}
+
+impl<T: PartialEq, R, E> RunReport<'_, T, R, E> {
+    // すべてのタスクが成功したか
+    pub fn is_success(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_, o)| matches!(o, TaskOutcome::Succeeded(_)))
+    }
+
+    pub fn outcome<Q>(&self, u: &Q) -> Option<&TaskOutcome<R, E>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.outcomes
+            .iter()
+            .find(|(v, _)| (*v).borrow() == u)
+            .map(|(_, o)| o)
+    }
+
+    // 失敗したタスクとそのエラー
+    pub fn failures(&self) -> Vec<(&T, &E)> {
+        self.outcomes
+            .iter()
+            .filter_map(|(u, o)| match o {
+                TaskOutcome::Failed(e) => Some((*u, e)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// 実行できるタスクと依存関係の残りを管理する
+struct TaskQueue<R, E> {
+    waiting: HashMap<NodeID, usize>,
+    // 親が失敗またはスキップされたタスク
+    blocked: HashMap<NodeID, bool>,
+    ready: BinaryHeap<Reverse<NodeID>>,
+    outcomes: HashMap<NodeID, TaskOutcome<R, E>>,
+    running: usize,
+}
+
+impl<R, E> TaskQueue<R, E> {
+    fn new(core: &GraphCore) -> Self {
+        let waiting: HashMap<NodeID, usize> = core
+            .nodes_dict
+            .iter()
+            .map(|(&id, n)| (id, n.parents.len()))
+            .collect();
+        let ready = waiting
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&id, _)| Reverse(id))
+            .collect();
+        Self {
+            blocked: HashMap::new(),
+            waiting,
+            ready,
+            outcomes: HashMap::new(),
+            running: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.ready.is_empty() && self.running == 0
+    }
+
+    // 次に実行するタスク (ID の小さい順)
+    fn next(&mut self) -> Option<NodeID> {
+        let Reverse(id) = self.ready.pop()?;
+        self.running += 1;
+        Some(id)
+    }
+
+    // タスクの結果を記録し，依存するタスクを進める．失敗したら子孫をスキップにする
+    fn finish(&mut self, core: &GraphCore, id: NodeID, outcome: TaskOutcome<R, E>) {
+        self.running -= 1;
+        let mut stack = vec![(id, outcome)];
+        while let Some((id, outcome)) = stack.pop() {
+            let ok = matches!(outcome, TaskOutcome::Succeeded(_));
+            self.outcomes.insert(id, outcome);
+            for &child in &core.nodes_dict[&id].children {
+                *self.blocked.entry(child).or_default() |= !ok;
+                let d = self.waiting.get_mut(&child).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    if self.blocked[&child] {
+                        stack.push((child, TaskOutcome::Skipped));
+                    } else {
+                        self.ready.push(Reverse(child));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    fn report<R, E>(
+        &self,
+        mut outcomes: HashMap<NodeID, TaskOutcome<R, E>>,
+    ) -> RunReport<'_, T, R, E> {
+        let mut ids: Vec<NodeID> = outcomes.keys().copied().collect();
+        ids.sort_unstable();
+        let nodes = self.nodes_by_id();
+        RunReport {
+            outcomes: ids
+                .into_iter()
+                .map(|id| (nodes[&id], outcomes.remove(&id).unwrap()))
+                .collect(),
+        }
+    }
+
+    // エッジ u → v を「u が終わってから v を実行する」依存関係として，各ノードで `task` を
+    // 1 つずつ実行する．失敗したタスクの子孫は実行せず Skipped にする．サイクルがあればエラー
+    pub fn run_tasks<R, E, F>(&self, mut task: F) -> Result<RunReport<'_, T, R, E>, GraphError>
+    where
+        F: FnMut(&T) -> Result<R, E>,
+    {
+        if let Some(cycle) = self.core.detect_cycle() {
+            return Err(GraphError::CycleDetected {
+                cycle: self.handles(&cycle),
+            });
+        }
+
+        let nodes = self.nodes_by_id();
+        let mut queue = TaskQueue::new(&self.core);
+        while let Some(id) = queue.next() {
+            let outcome = match task(nodes[&id]) {
+                Ok(r) => TaskOutcome::Succeeded(r),
+                Err(e) => TaskOutcome::Failed(e),
+            };
+            queue.finish(&self.core, id, outcome);
+        }
+        Ok(self.report(queue.outcomes))
+    }
+
+    // run_tasks を `threads` 本のスレッドで並列に行う．依存関係の無いタスクは同時に実行されうる
+    pub fn run_tasks_parallel<R, E, F>(
+        &self,
+        threads: usize,
+        task: F,
+    ) -> Result<RunReport<'_, T, R, E>, GraphError>
+    where
+        T: Sync,
+        R: Send,
+        E: Send,
+        F: Fn(&T) -> Result<R, E> + Sync,
+    {
+        if threads == 0 {
+            return Err(GraphError::InvalidArgument(
+                "thread count must be positive".to_string(),
+            ));
+        }
+        if let Some(cycle) = self.core.detect_cycle() {
+            return Err(GraphError::CycleDetected {
+                cycle: self.handles(&cycle),
+            });
+        }
+
+        let nodes = self.nodes_by_id();
+        let queue = Mutex::new(TaskQueue::new(&self.core));
+        let changed = Condvar::new();
+
+        thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| loop {
+                    let id = {
+                        let mut q = queue.lock().unwrap();
+                        loop {
+                            if let Some(id) = q.next() {
+                                break id;
+                            }
+                            if q.is_done() {
+                                return;
+                            }
+                            q = changed.wait(q).unwrap();
+                        }
+                    };
+
+                    let outcome = match task(nodes[&id]) {
+                        Ok(r) => TaskOutcome::Succeeded(r),
+                        Err(e) => TaskOutcome::Failed(e),
+                    };
+                    queue.lock().unwrap().finish(&self.core, id, outcome);
+                    changed.notify_all();
+                });
+            }
+        });
+
+        Ok(self.report(queue.into_inner().unwrap().outcomes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::super::graph::Graph;
+    use super::TaskOutcome;
+
+    fn sample() -> Graph<&'static str> {
+        // fetch → build → test → deploy,  fetch → lint
+        let mut g = Graph::new();
+        for u in ["fetch", "build", "lint", "test", "deploy"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("fetch", "build"),
+            ("build", "test"),
+            ("test", "deploy"),
+            ("fetch", "lint"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+        g
+    }
+
+    #[test]
+    fn test_run_tasks() {
+        let g = sample();
+        let mut order = Vec::new();
+        let report = g
+            .run_tasks(|u| {
+                order.push(*u);
+                if *u == "test" {
+                    Err("2 tests failed")
+                } else {
+                    Ok(u.len())
+                }
+            })
+            .unwrap();
+
+        assert_eq!(order, vec!["fetch", "build", "lint", "test"]);
+        assert!(!report.is_success());
+        assert_eq!(report.outcome(&"build"), Some(&TaskOutcome::Succeeded(5)));
+        assert_eq!(report.outcome(&"deploy"), Some(&TaskOutcome::Skipped));
+        assert_eq!(report.failures(), vec![(&"test", &"2 tests failed")]);
+    }
+
+    #[test]
+    fn test_run_tasks_parallel() {
+        let g = sample();
+        let finished = Mutex::new(Vec::new());
+        let report = g
+            .run_tasks_parallel(3, |u| {
+                let mut f = finished.lock().unwrap();
+                f.push(*u);
+                Ok::<usize, ()>(f.len())
+            })
+            .unwrap();
+
+        assert!(report.is_success());
+        assert_eq!(report.outcomes.len(), 5);
+        // タスクは依存先が終わってから始まる
+        let f = finished.into_inner().unwrap();
+        let pos = |u: &str| f.iter().position(|x| *x == u).unwrap();
+        for (a, b) in g.edges() {
+            assert!(pos(a) < pos(b));
+        }
+
+        let mut cyclic = sample();
+        let _ = cyclic.add_edge(&"deploy", &"fetch");
+        assert!(cyclic.run_tasks_parallel(2, |_| Ok::<(), ()>(())).is_err());
+        assert!(g.run_tasks_parallel(0, |_| Ok::<(), ()>(())).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // 成分内のノードの並び (Eades–Lin–Smyth の貪欲法)．
+    // 湧き出しは前から，吸い込みは後ろから詰め，残りは (出次数 - 入次数) が最大のノードを前に置く
+    fn els_order(&self, members: &[NodeID]) -> Vec<NodeID> {
+        let mut remaining: HashSet<NodeID> = members.iter().copied().collect();
+        let out_degree = |id: NodeID, remaining: &HashSet<NodeID>| {
+            self.nodes_dict[&id]
+                .children
+                .iter()
+                .filter(|c| **c != id && remaining.contains(c))
+                .count()
+        };
+        let in_degree = |id: NodeID, remaining: &HashSet<NodeID>| {
+            self.nodes_dict[&id]
+                .parents
+                .iter()
+                .filter(|p| **p != id && remaining.contains(p))
+                .count()
+        };
+
+        let mut head = Vec::new();
+        let mut tail = Vec::new();
+        while !remaining.is_empty() {
+            let mut ids: Vec<NodeID> = remaining.iter().copied().collect();
+            ids.sort_unstable();
+
+            if let Some(&sink) = ids.iter().find(|&&id| out_degree(id, &remaining) == 0) {
+                tail.push(sink);
+                remaining.remove(&sink);
+            } else if let Some(&source) = ids.iter().find(|&&id| in_degree(id, &remaining) == 0) {
+                head.push(source);
+                remaining.remove(&source);
+            } else {
+                let &best = ids
+                    .iter()
+                    .max_by_key(|&&id| {
+                        let delta = out_degree(id, &remaining) as isize
+                            - in_degree(id, &remaining) as isize;
+                        (delta, std::cmp::Reverse(id))
+                    })
+                    .unwrap();
+                head.push(best);
+                remaining.remove(&best);
+            }
+        }
+
+        head.extend(tail.into_iter().rev());
+        head
+    }
+
+    // 取り除くと DAG になるエッジの集合 (feedback arc set) を発見的に求める．
+    // 強連結成分ごとに Eades–Lin–Smyth の並びを作って後ろ向きのエッジを集め，
+    // 戻してもサイクルができないエッジは戻す (どの 1 本を戻してもサイクルができる極小な集合になる)．
+    // 自己ループは常に含む．(from, to) の ID 順に返す
+    pub fn feedback_arc_set(&self) -> Vec<(NodeID, NodeID)> {
+        let mut fas: Vec<(NodeID, NodeID)> = Vec::new();
+        for c in self.strongly_connected_components() {
+            if !self.is_nontrivial_component(&c, false) {
+                continue;
+            }
+            let order = self.els_order(&c);
+            let position: HashMap<NodeID, usize> =
+                order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+            for &id in &order {
+                for &child in &self.nodes_dict[&id].children {
+                    if position.get(&child).is_some_and(|&p| p <= position[&id]) {
+                        fas.push((id, child));
+                    }
+                }
+            }
+        }
+        fas.sort_unstable();
+
+        // 取り除いた後のグラフ
+        let removed: HashSet<(NodeID, NodeID)> = fas.iter().copied().collect();
+        let mut adj: HashMap<NodeID, Vec<NodeID>> = self
+            .nodes_dict
+            .iter()
+            .map(|(&id, n)| {
+                let children = n
+                    .children
+                    .iter()
+                    .copied()
+                    .filter(|&c| !removed.contains(&(id, c)))
+                    .collect();
+                (id, children)
+            })
+            .collect();
+
+        fas.retain(|&(from, to)| {
+            if from == to || reaches(&adj, to, from) {
+                return true;
+            }
+            adj.get_mut(&from).unwrap().push(to);
+            false
+        });
+        fas
+    }
+}
+
+// `start` から `target` へ到達できるか
+fn reaches(adj: &HashMap<NodeID, Vec<NodeID>>, start: NodeID, target: NodeID) -> bool {
+    let mut visited = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if node == target {
+            return true;
+        }
+        for &child in &adj[&node] {
+            if visited.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
+    false
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 循環依存を解消するために取り除くとよいエッジの候補 (発見的な最小 feedback arc set)．
+    // 最小とは限らないが，どの 1 本を残してもサイクルが残る．エッジの追加順に返す．
+    // self_loop_policy が IgnoreInCycles なら自己ループは含めない
+    pub fn suggest_edges_to_break_cycles(&self) -> Vec<(&T, &T)> {
+        let nodes = self.nodes_by_id();
+        self.core
+            .feedback_arc_set()
+            .iter()
+            .filter(|(a, b)| a != b || !self.ignores_self_loops())
+            .map(|(a, b)| (nodes[a], nodes[b]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_suggest_edges_to_break_cycles() {
+        // A → B → C → A と B → C → D → B は共通の B → C を取り除けば両方壊れる
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "A"),
+            ("C", "D"),
+            ("D", "B"),
+            ("E", "E"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+
+        let fas: Vec<(&str, &str)> = g
+            .suggest_edges_to_break_cycles()
+            .iter()
+            .map(|(a, b)| (**a, **b))
+            .collect();
+        assert_eq!(fas, vec![("B", "C"), ("E", "E")]);
+
+        for (a, b) in fas {
+            let _ = g.remove_edge(&a, &b);
+        }
+        assert!(g.detect_cycle().is_none());
+    }
+
+    #[test]
+    fn test_suggest_edges_to_break_cycles_dag() {
+        let mut g = Graph::new();
+        let _ = g.add_node(1);
+        let _ = g.add_node(2);
+        let _ = g.add_edge(&1, &2);
+        assert!(g.suggest_edges_to_break_cycles().is_empty());
+    }
+}
@@ -0,0 +1,441 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    weight::Weight,
+};
+
+// 残余容量・流量が正か (W::epsilon() 以下は 0 とみなす)
+fn is_positive<W: Weight>(w: &W) -> bool {
+    w.cmp_weight(&W::epsilon()) == Ordering::Greater
+}
+
+impl GraphCore {
+    // Edmonds–Karp 法による最大流．容量は非負であること．
+    // (流量, エッジごとの流量 (正のもののみ)) を返す
+    pub fn max_flow<F>(
+        &self,
+        source: NodeID,
+        sink: NodeID,
+        capacity: F,
+    ) -> (f64, HashMap<(NodeID, NodeID), f64>)
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し引きは桁あふれでエラーにならない
+        self.max_flow_by(source, sink, capacity).unwrap()
+    }
+
+    // 容量の型を選べる最大流．流量を足して桁あふれしたら，そのエッジをエラーとして返す
+    #[allow(clippy::type_complexity)]
+    pub fn max_flow_by<W, F>(
+        &self,
+        source: NodeID,
+        sink: NodeID,
+        capacity: F,
+    ) -> Result<(W, HashMap<(NodeID, NodeID), W>), (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut flow: HashMap<(NodeID, NodeID), W> = HashMap::new();
+        let mut value = W::zero();
+        // 順方向の残余容量．流量は容量を超えないので符号なしでも負にならない
+        let forward_residual = |flow: &HashMap<(NodeID, NodeID), W>, u: NodeID, v: NodeID| {
+            let used = flow.get(&(u, v)).copied().unwrap_or(W::zero());
+            capacity(u, v).checked_sub(used).unwrap_or(W::zero())
+        };
+
+        loop {
+            // 残余グラフで BFS．辿り方 (直前のノード, 順方向か) を覚える
+            let mut came_from: HashMap<NodeID, (NodeID, bool)> = HashMap::new();
+            let mut queue = VecDeque::from([source]);
+            let mut visited = HashSet::from([source]);
+            'bfs: while let Some(u) = queue.pop_front() {
+                let n = &self.nodes_dict[&u];
+                let forward = n.children.iter().map(|&v| (v, true));
+                let backward = n.parents.iter().map(|&v| (v, false));
+                for (v, is_forward) in forward.chain(backward) {
+                    let residual = if is_forward {
+                        forward_residual(&flow, u, v)
+                    } else {
+                        flow.get(&(v, u)).copied().unwrap_or(W::zero())
+                    };
+                    if is_positive(&residual) && visited.insert(v) {
+                        came_from.insert(v, (u, is_forward));
+                        if v == sink {
+                            break 'bfs;
+                        }
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if !came_from.contains_key(&sink) {
+                break;
+            }
+
+            // 増加路の上の最小の残余容量だけ流す
+            let mut steps = Vec::new();
+            let mut v = sink;
+            while v != source {
+                let (u, is_forward) = came_from[&v];
+                steps.push((u, v, is_forward));
+                v = u;
+            }
+            let delta = steps
+                .iter()
+                .map(|&(u, v, is_forward)| {
+                    if is_forward {
+                        forward_residual(&flow, u, v)
+                    } else {
+                        flow[&(v, u)]
+                    }
+                })
+                .reduce(|a, b| match a.cmp_weight(&b) {
+                    Ordering::Greater => b,
+                    _ => a,
+                })
+                .unwrap(); // source != sink なので増加路にはエッジがある
+                           // 流量の合計が桁あふれしたら source から出るエッジのせいにする
+            let first = (source, steps.last().unwrap().1);
+            for (u, v, is_forward) in steps {
+                if is_forward {
+                    let f = flow.entry((u, v)).or_insert(W::zero());
+                    *f = f.checked_add(delta).ok_or((u, v))?;
+                } else {
+                    let f = flow.get_mut(&(v, u)).unwrap();
+                    *f = f.checked_sub(delta).ok_or((v, u))?;
+                }
+            }
+            value = value.checked_add(delta).ok_or(first)?;
+        }
+
+        flow.retain(|_, f| is_positive(f));
+        Ok((value, flow))
+    }
+
+    // 最大流の残余グラフで `source` から到達できるノード (最小カットの source 側)
+    pub fn min_cut_side<W, F>(
+        &self,
+        source: NodeID,
+        flow: &HashMap<(NodeID, NodeID), W>,
+        capacity: F,
+    ) -> HashSet<NodeID>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut visited = HashSet::from([source]);
+        let mut stack = vec![source];
+        while let Some(u) = stack.pop() {
+            let n = &self.nodes_dict[&u];
+            for &v in &n.children {
+                let used = flow.get(&(u, v)).copied().unwrap_or(W::zero());
+                let residual = capacity(u, v).checked_sub(used).unwrap_or(W::zero());
+                if is_positive(&residual) && visited.insert(v) {
+                    stack.push(v);
+                }
+            }
+            for &v in &n.parents {
+                if flow.get(&(v, u)).is_some_and(is_positive) && visited.insert(v) {
+                    stack.push(v);
+                }
+            }
+        }
+        visited
+    }
+}
+
+/// `max_flow` の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow<'a, T, W = f64> {
+    pub value: W,
+    pub source: &'a T,
+    pub sink: &'a T,
+    // 流量が正のエッジ (from, to, 流量)．エッジの追加順
+    pub edge_flows: Vec<(&'a T, &'a T, W)>,
+}
+
+/// 流れをパスとサイクルに分けたもの．各要素は (ノードの列, 流量) で，サイクルは閉じた形
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowDecomposition<'a, T, W = f64> {
+    pub paths: Vec<(Vec<&'a T>, W)>,
+    pub cycles: Vec<(Vec<&'a T>, W)>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash, W: Weight> MaxFlow<'a, T, W> {
+    // 流れを source → sink のパスとサイクルの重ね合わせに分ける．
+    // パスの流量の和は `value` に等しく，パスとサイクルの数は合わせて流れのあるエッジの数以下になる
+    pub fn decompose_flow(&self) -> FlowDecomposition<'a, T, W> {
+        // エッジの追加順を保った残りの流量
+        let mut remaining: Vec<(&'a T, &'a T, W)> = self.edge_flows.clone();
+        let mut paths = Vec::new();
+        let mut cycles = Vec::new();
+
+        // `start` から流れのあるエッジをたどり，sink に着くかサイクルができるまで進む．
+        // 通ったエッジの番号の列を返す
+        let walk = |remaining: &[(&'a T, &'a T, W)], start: &'a T| {
+            let mut nodes = vec![start];
+            let mut edges: Vec<usize> = Vec::new();
+            loop {
+                let u = *nodes.last().unwrap();
+                if u == self.sink && !edges.is_empty() {
+                    return (nodes, edges, false);
+                }
+                let i = remaining
+                    .iter()
+                    .position(|(a, _, f)| *a == u && is_positive(f))
+                    .unwrap();
+                let v = remaining[i].1;
+                edges.push(i);
+                if let Some(pos) = nodes.iter().position(|&x| x == v) {
+                    // サイクルの部分だけを残す
+                    nodes.push(v);
+                    return (nodes[pos..].to_vec(), edges[pos..].to_vec(), true);
+                }
+                nodes.push(v);
+            }
+        };
+        let subtract = |remaining: &mut [(&'a T, &'a T, W)], edges: &[usize]| {
+            let amount = edges
+                .iter()
+                .map(|&i| remaining[i].2)
+                .reduce(|a, b| match a.cmp_weight(&b) {
+                    Ordering::Greater => b,
+                    _ => a,
+                })
+                .unwrap(); // walk は少なくとも 1 本のエッジを通る
+            for &i in edges {
+                // amount は通ったエッジの流量の最小値なので負にならない
+                remaining[i].2 = remaining[i].2.checked_sub(amount).unwrap_or(W::zero());
+            }
+            amount
+        };
+
+        while remaining
+            .iter()
+            .any(|(a, _, f)| *a == self.source && is_positive(f))
+        {
+            let (nodes, edges, is_cycle) = walk(&remaining, self.source);
+            let amount = subtract(&mut remaining, &edges);
+            if is_cycle {
+                cycles.push((nodes, amount));
+            } else {
+                paths.push((nodes, amount));
+            }
+        }
+        // source から流れ出さない残りは循環している
+        while let Some(&(a, _, _)) = remaining.iter().find(|(_, _, f)| is_positive(f)) {
+            let (nodes, edges, _) = walk(&remaining, a);
+            let amount = subtract(&mut remaining, &edges);
+            cycles.push((nodes, amount));
+        }
+
+        FlowDecomposition { paths, cycles }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 容量として使う重みがすべて非負か
+    pub(super) fn check_capacities(&self) -> Result<(), GraphError> {
+        match self.weights.iter().find(|(_, w)| w.is_nan() || **w < 0.0) {
+            Some((&(a, b), w)) => Err(GraphError::InvalidArgument(format!(
+                "capacity of edge #{} -> #{} must be non-negative, got {}",
+                a, b, w
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // `capacity` で与えた容量がすべて非負か
+    pub(super) fn check_capacities_by<W, F>(&self, capacity: F) -> Result<(), GraphError>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        for (&a, n) in &self.core.nodes_dict {
+            for &b in &n.children {
+                let c = capacity(a, b);
+                if c.cmp_weight(&W::zero()) == Ordering::Less {
+                    return Err(GraphError::InvalidArgument(format!(
+                        "capacity of edge #{} -> #{} must be non-negative, got {:?}",
+                        a, b, c
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 最大流の始点と終点の NodeID．同じならエラー
+    fn flow_ends<Q>(&self, source: &Q, sink: &Q) -> Result<(NodeID, NodeID), GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &s = self
+            .node_id(source)
+            .ok_or(GraphError::NodeNotFound { arg: "source" })?;
+        let &t = self
+            .node_id(sink)
+            .ok_or(GraphError::NodeNotFound { arg: "sink" })?;
+        if s == t {
+            return Err(GraphError::InvalidArgument(format!(
+                "source and sink must differ: #{}",
+                s
+            )));
+        }
+        Ok((s, t))
+    }
+
+    // エッジの重みを容量とした `source` から `sink` への最大流．
+    // 負の重みがあったり source と sink が同じだったりするとエラー
+    pub fn max_flow<Q>(&self, source: &Q, sink: &Q) -> Result<MaxFlow<'_, T>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (s, t) = self.flow_ends(source, sink)?;
+        self.check_capacities()?;
+        self.max_flow_impl(s, t, |a, b| self.weight_of(a, b))
+    }
+
+    // 容量を `capacity` で与える最大流 (u32 や u64 などの容量も使える)．
+    // 負の容量は InvalidArgument，流量が桁あふれしたら WeightOverflow
+    pub fn max_flow_by<Q, W, F>(
+        &self,
+        source: &Q,
+        sink: &Q,
+        capacity: F,
+    ) -> Result<MaxFlow<'_, T, W>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let (s, t) = self.flow_ends(source, sink)?;
+        let nodes = self.nodes_by_id();
+        let capacity = |a: NodeID, b: NodeID| capacity(nodes[&a], nodes[&b]);
+        self.check_capacities_by(capacity)?;
+        self.max_flow_impl(s, t, capacity)
+    }
+
+    fn max_flow_impl<W, F>(
+        &self,
+        s: NodeID,
+        t: NodeID,
+        capacity: F,
+    ) -> Result<MaxFlow<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let (value, flow) =
+            self.core
+                .max_flow_by(s, t, capacity)
+                .map_err(|(a, b)| GraphError::WeightOverflow {
+                    from: self.handle(a),
+                    to: self.handle(b),
+                })?;
+        let nodes = self.nodes_by_id();
+        let mut edges: Vec<((NodeID, NodeID), W)> = flow.into_iter().collect();
+        edges.sort_unstable_by_key(|(e, _)| *e);
+
+        Ok(MaxFlow {
+            value,
+            source: nodes[&s],
+            sink: nodes[&t],
+            edge_flows: edges
+                .into_iter()
+                .map(|((a, b), f)| (nodes[&a], nodes[&b], f))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    fn sample() -> Graph<&'static str> {
+        // S → A → T と S → B → T，A → B で迂回できる
+        let mut g = Graph::new();
+        for u in ["S", "A", "B", "T"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"S", &"A", 3.0);
+        let _ = g.add_weighted_edge(&"S", &"B", 2.0);
+        let _ = g.add_weighted_edge(&"A", &"T", 2.0);
+        let _ = g.add_weighted_edge(&"A", &"B", 1.0);
+        let _ = g.add_weighted_edge(&"B", &"T", 3.0);
+        g
+    }
+
+    #[test]
+    fn test_max_flow() {
+        let g = sample();
+        let flow = g.max_flow(&"S", &"T").unwrap();
+        assert_eq!(flow.value, 5.0);
+        assert_eq!(flow.edge_flows.len(), 5);
+
+        assert_eq!(g.max_flow(&"T", &"S").unwrap().value, 0.0);
+        assert!(matches!(
+            g.max_flow(&"S", &"S"),
+            Err(GraphError::InvalidArgument(_))
+        ));
+        assert!(g.max_flow(&"S", &"Z").is_err());
+    }
+
+    #[test]
+    fn test_decompose_flow() {
+        let g = sample();
+        let d = g.max_flow(&"S", &"T").unwrap().decompose_flow();
+
+        assert!(d.cycles.is_empty());
+        assert_eq!(d.paths.iter().map(|(_, f)| f).sum::<f64>(), 5.0);
+        for (path, _) in &d.paths {
+            assert_eq!(path.first(), Some(&&"S"));
+            assert_eq!(path.last(), Some(&&"T"));
+        }
+        assert!(d.paths.contains(&(vec![&"S", &"A", &"B", &"T"], 1.0)));
+
+        // 循環している流れはサイクルとして分ける
+        let mut cyclic = super::MaxFlow {
+            value: 0.0,
+            source: &"S",
+            sink: &"T",
+            edge_flows: vec![(&"A", &"B", 2.0), (&"B", &"A", 2.0)],
+        }
+        .decompose_flow();
+        assert_eq!(cyclic.cycles.pop(), Some((vec![&"A", &"B", &"A"], 2.0)));
+    }
+
+    #[test]
+    fn test_max_flow_by() {
+        let g = sample();
+        let capacity = |a: &&str, b: &&str| g.edge_weight(a, b).unwrap() as u32;
+        let flow = g.max_flow_by(&"S", &"T", capacity).unwrap();
+        assert_eq!(flow.value, 5u32);
+        let d = flow.decompose_flow();
+        assert_eq!(d.paths.iter().map(|(_, f)| f).sum::<u32>(), 5);
+
+        // S から出る容量の和が u8 に収まらない
+        assert!(matches!(
+            g.max_flow_by(&"S", &"T", |_, _| 200u8),
+            Err(GraphError::WeightOverflow { .. })
+        ));
+        assert!(matches!(
+            g.max_flow_by(&"S", &"T", |_, _| -1i32),
+            Err(GraphError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    weight::Weight,
+};
+
+impl GraphCore {
+    // Floyd-Warshall 法．ID の昇順に並べたノードと，その順の距離行列を返す (到達不能は INFINITY)
+    pub fn floyd_warshall<F>(&self, weight: F) -> (Vec<NodeID>, Vec<Vec<f64>>)
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        let (ids, dist) = self.floyd_warshall_by(weight).unwrap();
+        let dist = dist
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|d| d.unwrap_or(f64::INFINITY))
+                    .collect()
+            })
+            .collect();
+        (ids, dist)
+    }
+
+    // 重みの型を選べる Floyd-Warshall 法 (到達不能は None)．負閉路が見つかった時点で
+    // 打ち切るので，そのときは対角成分のどれかが負になる．距離を足して上に桁あふれした候補は
+    // 短くないものとして飛ばす．下に桁あふれしたか，到達できるのに距離を表せない組が残ったら，
+    // その距離の (始点, 終点) をエラーとして返す
+    #[allow(clippy::type_complexity)]
+    pub fn floyd_warshall_by<W, F>(
+        &self,
+        weight: F,
+    ) -> Result<(Vec<NodeID>, Vec<Vec<Option<W>>>), (NodeID, NodeID)>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        let index: HashMap<NodeID, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let shorter =
+            |nd: W, old: Option<W>| old.is_none_or(|d| nd.cmp_weight(&d) == Ordering::Less);
+
+        let n = ids.len();
+        let mut overflowed: HashSet<(usize, usize)> = HashSet::new();
+        let mut dist: Vec<Vec<Option<W>>> = vec![vec![None; n]; n];
+        for (i, &id) in ids.iter().enumerate() {
+            dist[i][i] = Some(W::zero());
+            for child in self.children_of(id) {
+                let j = index[&child];
+                let w = weight(id, child);
+                if shorter(w, dist[i][j]) {
+                    dist[i][j] = Some(w);
+                }
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                let Some(dik) = dist[i][k] else {
+                    continue;
+                };
+                for j in 0..n {
+                    let Some(dkj) = dist[k][j] else {
+                        continue;
+                    };
+                    let Some(nd) = dik.checked_add(dkj) else {
+                        if dkj.cmp_weight(&W::zero()) == Ordering::Less {
+                            return Err((ids[i], ids[j]));
+                        }
+                        overflowed.insert((i, j));
+                        continue;
+                    };
+                    if shorter(nd, dist[i][j]) {
+                        dist[i][j] = Some(nd);
+                    }
+                }
+                // 負閉路の上では距離がいくらでも小さくなり，整数だと桁あふれしうる
+                if dist[i][i].is_some_and(|d| d.cmp_weight(&W::zero()) == Ordering::Less) {
+                    return Ok((ids, dist));
+                }
+            }
+        }
+
+        match overflowed
+            .iter()
+            .filter(|&&(i, j)| dist[i][j].is_none())
+            .min()
+        {
+            Some(&(i, j)) => Err((ids[i], ids[j])),
+            None => Ok((ids, dist)),
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 全点対の最短距離．`ret[&a][&b]` が a から b への距離で，到達できない組は含まれない
+    // 負閉路がある場合は Bellman-Ford と同じくそのサイクルを返す
+    #[allow(clippy::type_complexity)]
+    pub fn all_pairs_shortest_paths(&self) -> Result<HashMap<&T, HashMap<&T, f64>>, Vec<&T>> {
+        let (ids, dist) = self.core.floyd_warshall(|a, b| self.weight_of(a, b));
+
+        if let Some(i) = (0..ids.len()).find(|&i| dist[i][i] < 0.0) {
+            if let Err(cycle) = self.core.bellman_ford(ids[i], |a, b| self.weight_of(a, b)) {
+                return Err(self.nodes_from_ids(&cycle));
+            }
+        }
+
+        let nodes = self.nodes_by_id();
+        Ok(ids
+            .iter()
+            .enumerate()
+            .map(|(i, from)| {
+                let row = ids
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| dist[i][j] < f64::INFINITY)
+                    .map(|(j, to)| (nodes[to], dist[i][j]))
+                    .collect();
+                (nodes[from], row)
+            })
+            .collect())
+    }
+
+    // 重みを `weight` で与える全点対の最短距離 (i64 や Duration などの重みも使える)．
+    // 負閉路があれば CycleDetected，距離が桁あふれしたら WeightOverflow
+    #[allow(clippy::type_complexity)]
+    pub fn all_pairs_shortest_paths_by<W, F>(
+        &self,
+        weight: F,
+    ) -> Result<HashMap<&T, HashMap<&T, W>>, GraphError>
+    where
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let nodes = self.nodes_by_id();
+        let weight = |a: NodeID, b: NodeID| weight(nodes[&a], nodes[&b]);
+        let overflow = |(a, b)| GraphError::WeightOverflow {
+            from: self.handle(a),
+            to: self.handle(b),
+        };
+        let (ids, dist) = self.core.floyd_warshall_by(weight).map_err(overflow)?;
+
+        let negative =
+            |d: &Option<W>| d.is_some_and(|d| d.cmp_weight(&W::zero()) == Ordering::Less);
+        if let Some(i) = (0..ids.len()).find(|&i| negative(&dist[i][i])) {
+            if let Err(cycle) = self
+                .core
+                .bellman_ford_by(ids[i], weight)
+                .map_err(overflow)?
+            {
+                return Err(GraphError::CycleDetected {
+                    cycle: self.handles(&cycle),
+                });
+            }
+        }
+
+        Ok(ids
+            .iter()
+            .enumerate()
+            .map(|(i, from)| {
+                let row = ids
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(j, to)| dist[i][j].map(|d| (nodes[to], d)))
+                    .collect();
+                (nodes[from], row)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_all_pairs_shortest_paths() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 3.0);
+        let _ = g.add_weighted_edge(&"B", &"C", -1.0);
+        let _ = g.add_weighted_edge(&"A", &"C", 4.0);
+        let _ = g.add_weighted_edge(&"C", &"A", 2.0);
+
+        let dist = g.all_pairs_shortest_paths().unwrap();
+        assert_eq!(dist[&"A"][&"A"], 0.0);
+        assert_eq!(dist[&"A"][&"C"], 2.0);
+        assert_eq!(dist[&"B"][&"A"], 1.0);
+        assert_eq!(dist[&"C"][&"B"], 5.0);
+        assert!(!dist[&"A"].contains_key(&"D"));
+        assert_eq!(dist[&"D"].len(), 1);
+
+        // Dijkstra と一致する (負の重みが無い始点について)
+        for (to, d) in g.dijkstra_distances(&"C") {
+            assert_eq!(dist[&"C"][to], d);
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_shortest_paths_negative_cycle() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 1.0);
+        let _ = g.add_weighted_edge(&"B", &"C", -1.0);
+        let _ = g.add_weighted_edge(&"C", &"B", -1.0);
+
+        let cycle = g.all_pairs_shortest_paths().unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.contains(&&"B") && cycle.contains(&&"C"));
+    }
+
+    #[test]
+    fn test_all_pairs_shortest_paths_by() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"A", &"C");
+
+        let dist = g.all_pairs_shortest_paths_by(|_, _| 100u8).unwrap();
+        assert_eq!(dist[&"A"][&"C"], 100);
+        assert_eq!(dist[&"B"].len(), 2);
+
+        // A → B → C の 2 本の和は u8 に収まらないが，最短の A → C は収まる
+        let dist = g.all_pairs_shortest_paths_by(|_, _| 200u8).unwrap();
+        assert_eq!(dist[&"A"][&"C"], 200);
+        assert_eq!(dist[&"A"][&"B"], 200);
+
+        // 最短距離そのものが収まらなければエラー
+        let _ = g.remove_edge(&"A", &"C");
+        assert_eq!(
+            g.all_pairs_shortest_paths_by(|_, _| 200u8),
+            Err(GraphError::WeightOverflow {
+                from: NodeHandle::new(0, 0),
+                to: NodeHandle::new(2, 0)
+            })
+        );
+        assert_eq!(
+            g.all_pairs_shortest_paths_by(|_, _| 100u8).unwrap()[&"A"][&"C"],
+            200
+        );
+
+        let _ = g.add_edge(&"C", &"A");
+        assert!(matches!(
+            g.all_pairs_shortest_paths_by(|_, _| -1i64),
+            Err(GraphError::CycleDetected { cycle }) if cycle.first() == cycle.last()
+        ));
+    }
+}
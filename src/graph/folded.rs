@@ -0,0 +1,172 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, BufReader, Read},
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, path::Path};
+
+/// 折りたたまれたスタック (`main;parse;lex 42` の形式．perf script の出力を
+/// stackcollapse-perf などで変換したもの) を集計したプロファイル
+///
+/// グラフのノードは関数名で，エッジ 呼び出し元 → 呼び出し先 の重みはその呼び出しを含む
+/// サンプル数．再帰呼び出しがあるとサイクル (自己ループ) になる
+pub struct StackProfile {
+    graph: Graph<String>,
+    // スタック (根から葉へ) ごとのサンプル数
+    stacks: HashMap<Vec<NodeID>, u64>,
+    total: u64,
+}
+
+impl StackProfile {
+    // 1 行 1 スタックで読む．フレームは ';' 区切りで，最後の空白の後ろがサンプル数．
+    // 空行と # で始まる行は読み飛ばす
+    pub fn from_folded<R: Read>(reader: R) -> Result<StackProfile, GraphError> {
+        let mut g = Graph::new();
+        let mut stacks: HashMap<Vec<NodeID>, u64> = HashMap::new();
+        let mut total = 0;
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parsed = line
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(stack, count)| Some((stack.trim(), count.parse::<u64>().ok()?)));
+            let Some((stack, count)) = parsed else {
+                return Err(GraphError::Parse(format!(
+                    "line {}: expected `frame;frame;... count`, found {:?}",
+                    i + 1,
+                    line
+                )));
+            };
+
+            let mut ids = Vec::new();
+            for frame in stack.split(';') {
+                if !g.contains_node(frame) {
+                    g.add_node(frame.to_string())?;
+                }
+                ids.push(g.id_dict[frame]);
+            }
+            // 同じスタックの中で繰り返し現れるエッジ (再帰) は 1 回だけ数える
+            let edges: HashSet<(NodeID, NodeID)> = ids.windows(2).map(|w| (w[0], w[1])).collect();
+            for (a, b) in edges {
+                let _ = g.core.add_edge(a, b);
+                *g.weights.entry((a, b)).or_insert(0.0) += count as f64;
+            }
+
+            *stacks.entry(ids).or_default() += count;
+            total += count;
+        }
+
+        Ok(StackProfile {
+            graph: g,
+            stacks,
+            total,
+        })
+    }
+
+    // 関数の呼び出し関係 (重みはサンプル数)
+    pub fn graph(&self) -> &Graph<String> {
+        &self.graph
+    }
+
+    pub fn total_samples(&self) -> u64 {
+        self.total
+    }
+
+    // `frame` がスタックの先端 (実行中) だったサンプル数
+    pub fn self_samples(&self, frame: &str) -> u64 {
+        let Some(&id) = self.graph.node_id(frame) else {
+            return 0;
+        };
+        self.stacks
+            .iter()
+            .filter(|(s, _)| s.last() == Some(&id))
+            .map(|(_, c)| c)
+            .sum()
+    }
+
+    // `frame` がスタックのどこかにあったサンプル数
+    pub fn inclusive_samples(&self, frame: &str) -> u64 {
+        let Some(&id) = self.graph.node_id(frame) else {
+            return 0;
+        };
+        self.stacks
+            .iter()
+            .filter(|(s, _)| s.contains(&id))
+            .map(|(_, c)| c)
+            .sum()
+    }
+
+    // サンプル数の多いスタックの上位 `k` 個．経路の重みはサンプル数で，
+    // 同数ならスタックの短い方・名前順の早い方を先にする
+    pub fn hot_paths(&self, k: usize) -> Vec<Path<'_, String>> {
+        let mut stacks: Vec<(Vec<&String>, u64)> = self
+            .stacks
+            .iter()
+            .map(|(s, &c)| (self.graph.nodes_from_ids(s), c))
+            .collect();
+        stacks.sort_unstable_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then(a.0.len().cmp(&b.0.len()))
+                .then(a.0.cmp(&b.0))
+        });
+
+        stacks
+            .into_iter()
+            .take(k)
+            .map(|(nodes, c)| Path::new(nodes, c as f64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::GraphError;
+    use super::StackProfile;
+
+    #[test]
+    fn test_stack_profile() {
+        let folded = "\
+main;parse;lex 30
+main;eval;eval;lookup 50
+# comment
+
+main;eval 15
+main;parse;lex 5
+main;eval;alloc [heap] 1
+";
+        let p = StackProfile::from_folded(folded.as_bytes()).unwrap();
+
+        assert_eq!(p.total_samples(), 101);
+        assert_eq!(p.graph().edge_weight("main", "eval"), Some(66.0));
+        assert_eq!(p.graph().edge_weight("eval", "eval"), Some(50.0));
+        assert_eq!(p.graph().edge_weight("parse", "lex"), Some(35.0));
+        assert_eq!(p.self_samples("eval"), 15);
+        assert_eq!(p.inclusive_samples("eval"), 66);
+        assert_eq!(p.self_samples("alloc [heap]"), 1);
+        assert_eq!(p.inclusive_samples("x"), 0);
+
+        let hot = p.hot_paths(2);
+        assert_eq!(hot.len(), 2);
+        assert_eq!(hot[0].nodes(), &[&"main", &"eval", &"eval", &"lookup"]);
+        assert_eq!(hot[0].weight(), 50.0);
+        assert_eq!(hot[1].nodes(), &[&"main", &"parse", &"lex"]);
+        assert_eq!(hot[1].weight(), 35.0);
+        assert_eq!(p.hot_paths(10).len(), 4);
+    }
+
+    #[test]
+    fn test_stack_profile_errors() {
+        let err = StackProfile::from_folded("main;a 3\nmain;b\n".as_bytes())
+            .err()
+            .unwrap();
+        assert!(
+            matches!(&err, GraphError::Parse(msg) if msg.starts_with("line 2:")),
+            "{err}"
+        );
+    }
+}
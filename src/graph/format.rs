@@ -0,0 +1,103 @@
+use std::fmt::Debug;
+
+// ノードの表示用ラベル．文字列の Debug 出力は前後の引用符を外して使う
+pub(crate) fn node_label<T: Debug>(u: &T) -> String {
+    let s = format!("{:?}", u);
+
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        unescape_debug(&s[1..s.len() - 1])
+    } else {
+        s
+    }
+}
+
+// 文字列の Debug 出力のエスケープ (`\"`, `\\`, `\n`, `\u{..}` など) を 1 回の走査で戻す
+fn unescape_debug(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            ret.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => ret.push('\n'),
+            Some('r') => ret.push('\r'),
+            Some('t') => ret.push('\t'),
+            Some('0') => ret.push('\0'),
+            Some('u') => {
+                // \u{XXXX}
+                let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    ret.push(c);
+                }
+            }
+            Some(c) => ret.push(c),
+            None => ret.push('\\'),
+        }
+    }
+    ret
+}
+
+// DOT の quoted ID として使える形にする
+pub(crate) fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// JSON の文字列リテラルにする
+pub(crate) fn json_quote(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+// XML の属性値・テキストとして使える形にする
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dot_quote, json_quote, node_label};
+
+    #[test]
+    fn test_node_label() {
+        assert_eq!(node_label(&"A"), "A");
+        assert_eq!(node_label(&String::from("a\"b")), "a\"b");
+        assert_eq!(node_label(&String::from("a\\\"b\\")), "a\\\"b\\");
+        assert_eq!(
+            node_label(&String::from("tab\tnl\n\u{7}")),
+            "tab\tnl\n\u{7}"
+        );
+        assert_eq!(node_label(&1), "1");
+        assert_eq!(node_label(&(1, 2)), "(1, 2)");
+    }
+
+    #[test]
+    fn test_dot_quote() {
+        assert_eq!(dot_quote("A"), "\"A\"");
+        assert_eq!(dot_quote("a\"b"), "\"a\\\"b\"");
+    }
+
+    #[test]
+    fn test_json_quote() {
+        assert_eq!(json_quote("A"), "\"A\"");
+        assert_eq!(json_quote("a\"b\n"), "\"a\\\"b\\n\"");
+        assert_eq!(json_quote("\u{1}"), "\"\\u0001\"");
+    }
+}
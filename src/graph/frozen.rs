@@ -0,0 +1,265 @@
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use super::{core::NodeID, graph::Graph};
+
+/// 変更できない代わりにコンパクトなグラフ (CSR 形式)．
+/// `with_edge_filter` で Bloom フィルタを付けると，存在しないエッジの問い合わせで隣接配列を見ずに済む
+pub struct FrozenGraph<'a, T: PartialEq + Eq + Hash> {
+    graph: &'a Graph<T>,
+    nodes: Vec<&'a T>,
+    // NodeID → 何番目のノードか
+    index: HashMap<NodeID, usize>,
+    // ノード i の子は targets[offsets[i]..offsets[i + 1]] (昇順)
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    filter: Option<EdgeFilter>,
+}
+
+// エッジ (i, j) の Bloom フィルタ．偽陽性はあるが偽陰性は無い
+struct EdgeFilter {
+    bits: Vec<u64>,
+    hashes: u32,
+}
+
+impl EdgeFilter {
+    fn new(edge_count: usize, bits_per_edge: usize) -> Self {
+        let len = (edge_count * bits_per_edge).max(64).div_ceil(64);
+        // 最適なハッシュ数は bits_per_edge * ln 2
+        let hashes = ((bits_per_edge as f64 * std::f64::consts::LN_2).round() as u32).clamp(1, 16);
+        Self {
+            bits: vec![0; len],
+            hashes,
+        }
+    }
+
+    // ダブルハッシングで k 個のビット位置を作る
+    fn positions(&self, from: usize, to: usize) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::new();
+        (from, to).hash(&mut hasher);
+        let h = hasher.finish();
+        let (h1, h2) = (h & 0xffff_ffff, (h >> 32) | 1);
+        let m = self.bits.len() as u64 * 64;
+
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % m) as usize)
+    }
+
+    fn insert(&mut self, from: usize, to: usize) {
+        let positions: Vec<usize> = self.positions(from, to).collect();
+        for p in positions {
+            self.bits[p / 64] |= 1 << (p % 64);
+        }
+    }
+
+    fn may_contain(&self, from: usize, to: usize) -> bool {
+        self.positions(from, to)
+            .all(|p| self.bits[p / 64] & (1 << (p % 64)) != 0)
+    }
+}
+
+impl<'a, T: PartialEq + Eq + Hash> FrozenGraph<'a, T> {
+    // エッジの Bloom フィルタを作る．bits_per_edge が大きいほど偽陽性が減る (10 で約 1%)
+    pub fn with_edge_filter(mut self, bits_per_edge: usize) -> Self {
+        let mut filter = EdgeFilter::new(self.targets.len(), bits_per_edge.max(1));
+        for i in 0..self.nodes.len() {
+            for &j in &self.targets[self.offsets[i]..self.offsets[i + 1]] {
+                filter.insert(i, j);
+            }
+        }
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn has_edge_filter(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    // ノード (別名でもよい) が何番目か
+    fn position<Q>(&self, u: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.node_id(u).map(|id| self.index[id])
+    }
+
+    pub fn contains_node<Q>(&self, u: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.position(u).is_some()
+    }
+
+    pub fn contains_edge<Q>(&self, u_from: &Q, u_to: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (Some(i), Some(j)) = (self.position(u_from), self.position(u_to)) else {
+            return false;
+        };
+        if let Some(filter) = &self.filter {
+            if !filter.may_contain(i, j) {
+                return false;
+            }
+        }
+        self.targets[self.offsets[i]..self.offsets[i + 1]]
+            .binary_search(&j)
+            .is_ok()
+    }
+
+    // 子ノード．ノードが無ければ None
+    pub fn children<Q>(&self, u: &Q) -> Option<Vec<&'a T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.position(u)?;
+        Some(
+            self.targets[self.offsets[i]..self.offsets[i + 1]]
+                .iter()
+                .map(|&j| self.nodes[j])
+                .collect(),
+        )
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 現在のグラフから読み取り専用の FrozenGraph を作る
+    pub fn freeze(&self) -> FrozenGraph<'_, T> {
+        let mut ids: Vec<NodeID> = self.id_dict.values().copied().collect();
+        ids.sort_unstable();
+        let position: HashMap<NodeID, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let nodes = self.nodes_from_ids(&ids);
+
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::new();
+        offsets.push(0);
+        for id in &ids {
+            let mut row: Vec<usize> = self.core.nodes_dict[id]
+                .children
+                .iter()
+                .map(|child| position[child])
+                .collect();
+            row.sort_unstable();
+            targets.extend(row);
+            offsets.push(targets.len());
+        }
+
+        FrozenGraph {
+            graph: self,
+            nodes,
+            index: position,
+            offsets,
+            targets,
+            filter: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<u32> {
+        let mut g = Graph::new();
+        for i in 0..50 {
+            let _ = g.add_node(i);
+        }
+        for i in 0..50 {
+            let _ = g.add_edge(&i, &((i * 7 + 3) % 50));
+            let _ = g.add_edge(&i, &((i + 1) % 50));
+        }
+        g
+    }
+
+    #[test]
+    fn test_freeze() {
+        let g = sample();
+        let f = g.freeze();
+
+        assert_eq!(f.node_count(), 50);
+        assert_eq!(f.edge_count(), 98); // 8 → 9 と 33 → 34 は重複する
+        assert!(f.contains_node(&10));
+        assert!(!f.contains_node(&50));
+        assert!(f.contains_edge(&1, &2));
+        assert!(!f.contains_edge(&2, &1));
+        assert!(!f.contains_edge(&1, &99));
+        assert_eq!(f.children(&0), Some(vec![&1, &3]));
+        assert_eq!(f.children(&99), None);
+    }
+
+    #[test]
+    fn test_freeze_borrowed_keys() {
+        let mut g = Graph::new();
+        for u in ["a", "b"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.add_edge("a", "b");
+        let _ = g.alias("a", "A");
+
+        // String のノードを &str や別名で引ける
+        let f = g.freeze();
+        assert!(f.contains_node("b"));
+        assert!(f.contains_edge("A", "b"));
+        assert_eq!(f.children("a"), Some(vec![&"b".to_string()]));
+    }
+
+    #[test]
+    fn test_edge_filter_agrees_with_adjacency() {
+        let g = sample();
+        let plain = g.freeze();
+        let filtered = g.freeze().with_edge_filter(10);
+        assert!(filtered.has_edge_filter());
+
+        for a in 0..50 {
+            for b in 0..50 {
+                assert_eq!(plain.contains_edge(&a, &b), filtered.contains_edge(&a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_filter_rejects_before_adjacency() {
+        let g = sample();
+        let mut f = g.freeze().with_edge_filter(10);
+
+        // 隣接配列を「すべてのエッジがある」ように書き換える．フィルタで弾かれた問い合わせは
+        // 隣接配列を見ないので false のままになる
+        let n = f.node_count();
+        f.targets = (0..n).flat_map(|_| 0..n).collect();
+        f.offsets = (0..=n).map(|i| i * n).collect();
+
+        let filter = f.filter.as_ref().unwrap();
+        let mut rejected = 0;
+        for a in 0..50u32 {
+            for b in 0..50u32 {
+                if g.contains_edge(&a, &b) {
+                    assert!(f.contains_edge(&a, &b));
+                    continue;
+                }
+                let (i, j) = (f.position(&a).unwrap(), f.position(&b).unwrap());
+                if !filter.may_contain(i, j) {
+                    assert!(!f.contains_edge(&a, &b));
+                    rejected += 1;
+                }
+            }
+        }
+        // 存在しない 2402 本のうち偽陽性 (約 1%) 以外はフィルタで弾かれる
+        assert!(rejected > 2300, "{rejected}");
+        assert!(!f.contains_edge(&2, &1));
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    core::NodeID,
+    format::{node_label, xml_escape},
+    graph::Graph,
+};
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // Gephi で読める GEXF 1.3 形式で書き出す．ノードの id は追加順の番号，label は Debug 表示．
+    // エッジはラベルが付いていれば label を持つ
+    pub fn write_gexf<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let index: HashMap<NodeID, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(w, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+        writeln!(w, r#"  <graph mode="static" defaultedgetype="directed">"#)?;
+        writeln!(w, "    <nodes>")?;
+        for id in &ids {
+            writeln!(
+                w,
+                r#"      <node id="{}" label="{}"/>"#,
+                index[id],
+                xml_escape(&node_label(nodes[id]))
+            )?;
+        }
+        writeln!(w, "    </nodes>")?;
+        writeln!(w, "    <edges>")?;
+        let mut edge_id = 0;
+        for id in &ids {
+            let mut children = self.core.children_of(*id);
+            children.sort_unstable();
+
+            for child in children {
+                let label = match self.labels.get(&(*id, child)) {
+                    Some(l) => format!(r#" label="{}""#, xml_escape(l)),
+                    None => String::new(),
+                };
+                writeln!(
+                    w,
+                    r#"      <edge id="{}" source="{}" target="{}" weight="{}"{}/>"#,
+                    edge_id,
+                    index[id],
+                    index[&child],
+                    self.weight_of(*id, child),
+                    label
+                )?;
+                edge_id += 1;
+            }
+        }
+        writeln!(w, "    </edges>")?;
+        writeln!(w, "  </graph>")?;
+        writeln!(w, "</gexf>")
+    }
+
+    pub fn to_gexf(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_gexf(&mut buf).unwrap(); // Vec への書き込みは失敗しない
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_to_gexf() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("<B>");
+        let _ = g.add_weighted_edge(&"A", &"<B>", 2.5);
+        let _ = g.add_edge(&"<B>", &"A");
+        let _ = g.add_node("C");
+        let _ = g.add_edge_with_label(&"A", &"C", "uses \"<T>\"");
+
+        let gexf = g.to_gexf();
+        assert!(gexf.contains(r#"<node id="0" label="A"/>"#));
+        assert!(gexf.contains(r#"<node id="1" label="&lt;B&gt;"/>"#));
+        assert!(gexf.contains(r#"<edge id="0" source="0" target="1" weight="2.5"/>"#));
+        assert!(gexf.contains(
+            r#"<edge id="1" source="0" target="2" weight="1" label="uses &quot;&lt;T&gt;&quot;"/>"#
+        ));
+        assert!(gexf.contains(r#"<edge id="2" source="1" target="0" weight="1"/>"#));
+        assert!(gexf.trim_end().ends_with("</gexf>"));
+    }
+}
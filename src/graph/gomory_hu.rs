@@ -0,0 +1,206 @@
+use std::{borrow::Borrow, cmp::Ordering, collections::HashMap, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, weight::Weight};
+
+/// すべてのノード対の最小カットの値を表す木 (Gusfield の等価流木)
+///
+/// 2 ノード間の最小カットの値は，木の上でその 2 ノードを結ぶパスの最小の辺の重みになる
+pub struct GomoryHuTree<'a, T: PartialEq + Eq + Hash, W = f64> {
+    // 木の辺 (子, 親, カットの値)．子の追加順
+    pub edges: Vec<(&'a T, &'a T, W)>,
+    graph: &'a Graph<T>,
+    // NodeID → 木の上の番号
+    index: HashMap<NodeID, usize>,
+    // 各ノードの親の番号と辺の重み (根は None)．親の番号は常に自分より小さい
+    parent: Vec<Option<(usize, W)>>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash, W: Weight> GomoryHuTree<'a, T, W> {
+    // `u` と `v` を分ける最小カットの値．同じノードや無いノードは None
+    pub fn min_cut_value<Q>(&self, u: &Q, v: &Q) -> Option<W>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (mut a, mut b) = (
+            self.index[self.graph.node_id(u)?],
+            self.index[self.graph.node_id(v)?],
+        );
+        if a == b {
+            return None;
+        }
+
+        // 番号の大きい方を親へ上げていくと共通の祖先で出会う
+        let mut ret: Option<W> = None;
+        while a != b {
+            if a < b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            let (p, w) = self.parent[a].unwrap();
+            if ret.is_none_or(|r| w.cmp_weight(&r) == Ordering::Less) {
+                ret = Some(w);
+            }
+            a = p;
+        }
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // エッジの向きを無視し (容量は両方向の重みの和)，Gusfield の方法で n - 1 回の最大流から
+    // 最小カットの木を作る．重みが負だとエラー
+    pub fn gomory_hu_tree(&self) -> Result<GomoryHuTree<'_, T>, GraphError> {
+        self.check_capacities()?;
+        self.gomory_hu_tree_impl(|a, b| self.weight_of(a, b))
+    }
+
+    // 容量を `capacity` で与える Gomory–Hu 木 (u32 や u64 などの容量も使える)．
+    // 負の容量は InvalidArgument，容量の和や流量が桁あふれしたら WeightOverflow
+    pub fn gomory_hu_tree_by<W, F>(&self, capacity: F) -> Result<GomoryHuTree<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let nodes = self.nodes_by_id();
+        let capacity = |a: NodeID, b: NodeID| capacity(nodes[&a], nodes[&b]);
+        self.check_capacities_by(capacity)?;
+        self.gomory_hu_tree_impl(capacity)
+    }
+
+    fn gomory_hu_tree_impl<W, F>(&self, capacity: F) -> Result<GomoryHuTree<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let overflow = |(a, b)| GraphError::WeightOverflow {
+            from: self.handle(a),
+            to: self.handle(b),
+        };
+
+        let mut ids: Vec<NodeID> = self.core.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+
+        // 両方向にエッジを張ったグラフ．容量は両方向の和
+        let mut sym = self.core.clone();
+        let mut sym_capacity: HashMap<(NodeID, NodeID), W> = HashMap::new();
+        for (&id, n) in &self.core.nodes_dict {
+            for &child in &n.children {
+                let _ = sym.add_edge(child, id);
+                for e in [(id, child), (child, id)] {
+                    let c = sym_capacity.entry(e).or_insert(W::zero());
+                    *c = c
+                        .checked_add(capacity(id, child))
+                        .ok_or((id, child))
+                        .map_err(overflow)?;
+                }
+            }
+        }
+        let capacity = |a: NodeID, b: NodeID| sym_capacity[&(a, b)];
+
+        let n = ids.len();
+        let mut parent: Vec<usize> = vec![0; n];
+        let mut value: Vec<W> = vec![W::zero(); n];
+        for s in 1..n {
+            let t = parent[s];
+            let (f, flow) = sym
+                .max_flow_by(ids[s], ids[t], capacity)
+                .map_err(overflow)?;
+            let side = sym.min_cut_side(ids[s], &flow, capacity);
+            value[s] = f;
+            for i in s + 1..n {
+                if parent[i] == t && side.contains(&ids[i]) {
+                    parent[i] = s;
+                }
+            }
+        }
+
+        let nodes = self.nodes_by_id();
+        Ok(GomoryHuTree {
+            edges: (1..n)
+                .map(|i| (nodes[&ids[i]], nodes[&ids[parent[i]]], value[i]))
+                .collect(),
+            graph: self,
+            index: ids.iter().enumerate().map(|(i, &id)| (id, i)).collect(),
+            parent: (0..n)
+                .map(|i| (i > 0).then(|| (parent[i], value[i])))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    #[test]
+    fn test_gomory_hu_tree() {
+        // 2 つの三角形 {A, B, C}, {D, E, F} を C - D (重み 1) でつなぐ
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "A"),
+            ("D", "E"),
+            ("E", "F"),
+            ("F", "D"),
+        ] {
+            let _ = g.add_weighted_edge(&a, &b, 3.0);
+        }
+        let _ = g.add_weighted_edge(&"C", &"D", 1.0);
+        // A → B を 2 に上書きし，B → A も足すので A - B の容量は 4
+        let _ = g.add_weighted_edge(&"A", &"B", 2.0);
+        let _ = g.add_weighted_edge(&"B", &"A", 2.0);
+
+        let tree = g.gomory_hu_tree().unwrap();
+        assert_eq!(tree.edges.len(), 5);
+
+        // 各ノード対について最大流と一致する
+        let nodes = ["A", "B", "C", "D", "E", "F"];
+        for u in &nodes {
+            for v in &nodes {
+                if u == v {
+                    assert_eq!(tree.min_cut_value(u, v), None);
+                    continue;
+                }
+                let expected = {
+                    let mut sym = g.clone();
+                    for (a, b) in g.edges() {
+                        let w = g.edge_weight(a, b).unwrap() + g.edge_weight(b, a).unwrap_or(0.0);
+                        let _ = sym.add_weighted_edge(a, b, w);
+                        let _ = sym.add_weighted_edge(b, a, w);
+                    }
+                    sym.max_flow(u, v).unwrap().value
+                };
+                assert_eq!(tree.min_cut_value(u, v), Some(expected), "{u} {v}");
+            }
+        }
+        assert_eq!(tree.min_cut_value(&"A", &"F"), Some(1.0));
+        assert_eq!(tree.min_cut_value(&"A", &"B"), Some(7.0));
+    }
+
+    #[test]
+    fn test_gomory_hu_tree_by() {
+        // A - B - C の一本道．A → B の容量 5，B → C の容量 3 (u32)
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let capacity = |a: &&str, _: &&str| if *a == "A" { 5u32 } else { 3 };
+
+        let tree = g.gomory_hu_tree_by(capacity).unwrap();
+        assert_eq!(tree.min_cut_value(&"A", &"B"), Some(5));
+        assert_eq!(tree.min_cut_value(&"A", &"C"), Some(3));
+
+        // 両方向のエッジの容量の和が u8 に収まらない
+        let _ = g.add_edge(&"B", &"A");
+        assert!(matches!(
+            g.gomory_hu_tree_by(|_, _| 200u8),
+            Err(GraphError::WeightOverflow { .. })
+        ));
+    }
+}
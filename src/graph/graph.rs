@@ -1,117 +1,376 @@
 use std::{
-    collections::{HashMap, HashSet},
-    fmt::Debug,
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::{self, Debug},
     hash::Hash,
 };
 
-use super::core::{GraphCore, NodeID};
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    handle::NodeHandle,
+    provenance::Provenance,
+    self_loop::SelfLoopPolicy,
+};
 
-pub struct Graph<T: PartialEq + Eq + Hash + Debug> {
-    id_counter: usize,
-    id_dict: HashMap<T, NodeID>,
-    core: GraphCore,
+#[derive(Clone)]
+pub struct Graph<T: PartialEq + Eq + Hash> {
+    // 次に割り当てる新しい NodeID．削除したノードの NodeID は free_ids から使い回すので，
+    // 外には NodeID ではなく世代付きの NodeHandle を渡す
+    pub(super) id_counter: usize,
+    // NodeID ごとの世代．ノードを削除するたびに進む
+    pub(super) generations: Vec<u32>,
+    // 削除したノードの (使い回せる) NodeID
+    pub(super) free_ids: Vec<NodeID>,
+    pub(super) id_dict: HashMap<T, NodeID>,
+    // 別名 → 実体のノードの ID (alias で登録したもの)
+    pub(super) aliases: HashMap<T, NodeID>,
+    pub(super) core: GraphCore,
+    // 重み付きで追加されたエッジの重み (それ以外のエッジの重みは 1)
+    pub(super) weights: HashMap<(NodeID, NodeID), f64>,
+    // エッジの出所 (add_edge_with_provenance のたびに追記される)
+    pub(super) provenance: HashMap<(NodeID, NodeID), Vec<Provenance>>,
+    // エッジの関係の種類 ("depends-on", "imports" など)
+    pub(super) labels: HashMap<(NodeID, NodeID), String>,
+    // 多重グラフとして平行なエッジを数えるか (new_multigraph で作ったとき)
+    pub(super) multigraph: bool,
+    // 多重グラフで 2 本以上あるエッジの本数
+    pub(super) multiplicity: HashMap<(NodeID, NodeID), usize>,
+    pub(super) self_loop_policy: SelfLoopPolicy,
 }
 
-impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+// ノードとエッジ (重み付き) を追加順に表示する
+impl<T: PartialEq + Eq + Hash + Debug> Debug for Graph<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .collect();
+        edges.sort_unstable();
+
+        f.debug_struct("Graph")
+            .field("nodes", &ids.iter().map(|id| nodes[id]).collect::<Vec<_>>())
+            .field(
+                "edges",
+                &edges
+                    .iter()
+                    .map(|&(a, b)| (nodes[&a], nodes[&b], self.weight_of(a, b)))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+// 構造としての比較．ノードの集合，エッジの集合，エッジの重みとラベルが等しければ等しい
+// (NodeID の振り方とエッジの出所は比べない)
+impl<T: PartialEq + Eq + Hash> PartialEq for Graph<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.node_count() != other.node_count() || self.edge_count() != other.edge_count() {
+            return false;
+        }
+        if !self.id_dict.keys().all(|u| other.id_dict.contains_key(u)) {
+            return false;
+        }
+
+        let nodes = self.nodes_by_id();
+        self.core.nodes_dict.iter().all(|(&id, n)| {
+            n.children.iter().all(|&child| {
+                other.edge_weight(nodes[&id], nodes[&child]) == Some(self.weight_of(id, child))
+                    && other.edge_label(nodes[&id], nodes[&child])
+                        == self.labels.get(&(id, child)).map(|l| l.as_str())
+                    && other.edge_multiplicity(nodes[&id], nodes[&child])
+                        == self.multiplicity_of(id, child)
+            })
+        })
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Default for Graph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
     pub fn new() -> Self {
         Self {
             id_counter: 0,
+            generations: Vec::new(),
+            free_ids: Vec::new(),
             id_dict: HashMap::new(),
+            aliases: HashMap::new(),
             core: GraphCore::new(),
+            weights: HashMap::new(),
+            provenance: HashMap::new(),
+            labels: HashMap::new(),
+            multigraph: false,
+            multiplicity: HashMap::new(),
+            self_loop_policy: SelfLoopPolicy::Allow,
         }
     }
 
-    pub fn get_node_by_id(&self, id: &NodeID) -> Option<&T> {
-        let mut ret: Option<&T> = None;
-        for (k, v) in self.id_dict.iter() {
-            if v == id {
-                if ret.is_none() {
-                    ret = Some(k);
-                } else {
-                    panic!("NodeID duplication")
-                }
-            }
+    // ノードの ID．別名でも引ける
+    pub(super) fn node_id<Q>(&self, u: &Q) -> Option<&NodeID>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.id_dict.get(u).or_else(|| self.aliases.get(u))
+    }
+
+    // NodeID から元のノードを引く表
+    pub(super) fn nodes_by_id(&self) -> HashMap<NodeID, &T> {
+        self.id_dict.iter().map(|(k, &v)| (v, k)).collect()
+    }
+
+    // 登録されているノード (順序は決まっていない)
+    pub fn nodes(&self) -> impl Iterator<Item = &T> {
+        self.id_dict.keys()
+    }
+
+    // 登録されているエッジ (from, to) (順序は決まっていない)．多重グラフでは平行なエッジの本数だけ返す
+    pub fn edges(&self) -> impl Iterator<Item = (&T, &T)> {
+        let nodes = self.nodes_by_id();
+        let edges: Vec<(&T, &T)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(id, n)| n.children.iter().map(move |child| (id, child)))
+            .flat_map(|(id, child)| {
+                std::iter::repeat_n((nodes[id], nodes[child]), self.multiplicity_of(*id, *child))
+            })
+            .collect();
+        edges.into_iter()
+    }
+
+    pub fn contains_node<Q>(&self, u: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.node_id(u).is_some()
+    }
+
+    // どちらかのノードが無い場合も false
+    pub fn contains_edge<Q>(&self, u_from: &Q, u_to: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.node_id(u_from), self.node_id(u_to)) {
+            (Some(from_id), Some(to_id)) => self.core.nodes_dict[from_id].children.contains(to_id),
+            _ => false,
         }
-        ret
     }
 
-    // 使用するノードを登録する
-    pub fn add_node(&mut self, u: T) -> Result<(), String> {
-        if self.id_dict.contains_key(&u) {
-            return Err(format!("node {:#?} is already added", u));
+    // 使用するノードを登録し，そのハンドルを返す．削除したノードの NodeID があれば使い回すので，
+    // 「ノードの追加順」に並べる処理ではそのノードは削除したノードの位置に入る
+    pub fn add_node(&mut self, u: T) -> Result<NodeHandle, GraphError> {
+        if let Some(&node) = self.node_id(&u) {
+            return Err(GraphError::NodeAlreadyExists {
+                node: self.handle(node),
+            });
         }
 
-        let new_id = self.id_counter;
-        self.id_counter += 1;
+        let new_id = match self.free_ids.pop() {
+            Some(id) => id,
+            None => {
+                self.id_counter += 1;
+                self.generations.push(0);
+                self.id_counter - 1
+            }
+        };
         self.id_dict.insert(u, new_id);
 
-        self.core.add_node(new_id)
+        self.core.add_node(new_id)?;
+        Ok(self.handle(new_id))
     }
 
     // すでにエッジが登録されている場合 false が返される (ただし，複数のエッジとして登録はされる)
-    pub fn add_edge(&mut self, u_from: &T, u_to: &T) -> Result<bool, String> {
+    pub fn add_edge<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         let from_id = *self
-            .id_dict
-            .get(&u_from)
-            .ok_or(format!("node {:#?} is not added", u_from))?;
+            .node_id(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
         let to_id = *self
-            .id_dict
-            .get(&u_to)
-            .ok_or(format!("node {:#?} is not added", u_to))?;
+            .node_id(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+        self.add_edge_by_id(from_id, to_id)
+    }
+
+    // add_edge の本体．エッジを追加する処理は自己ループの扱いと平行なエッジの本数を
+    // 揃えるためにすべてここを通す
+    pub(super) fn add_edge_by_id(
+        &mut self,
+        from_id: NodeID,
+        to_id: NodeID,
+    ) -> Result<bool, GraphError> {
+        self.check_self_loop(from_id, to_id)?;
+
+        let ret = self.core.add_edge(from_id, to_id)?;
+        if ret && self.multigraph {
+            *self.multiplicity.entry((from_id, to_id)).or_insert(1) += 1;
+        }
+        Ok(ret)
+    }
 
-        return self.core.add_edge(from_id, to_id);
+    // add_edge と同じだが，すでにエッジがあれば (多重グラフでも) 何もせず EdgeExists
+    pub fn try_add_edge<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<(), GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = *self
+            .node_id(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let to_id = *self
+            .node_id(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+        if self.core.nodes_dict[&from_id].children.contains(&to_id) {
+            return Err(GraphError::EdgeExists {
+                from: self.handle(from_id),
+                to: self.handle(to_id),
+            });
+        }
+        self.add_edge(u_from, u_to).map(|_| ())
     }
 
-    pub fn detect_cycle(&self) -> Option<Vec<&T>> {
-        let inner_ret = self.core.detect_cycle();
+    // 重み付きのエッジを追加する．既にエッジがある場合は重みを上書きして true を返す
+    pub fn add_weighted_edge<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        weight: f64,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ret = self.add_edge(u_from, u_to)?;
 
-        match inner_ret {
-            Some(v) => {
-                let mut ret = Vec::<&T>::new();
+        let from_id = *self.node_id(u_from).unwrap();
+        let to_id = *self.node_id(u_to).unwrap();
+        self.weights.insert((from_id, to_id), weight);
 
-                for i in v.iter() {
-                    ret.push(self.get_node_by_id(i).unwrap())
-                }
+        Ok(ret)
+    }
+
+    // エッジを削除する．エッジが無かった場合 false が返される．
+    // 多重グラフで平行なエッジがあれば 1 本だけ減らす
+    pub fn remove_edge<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = *self
+            .node_id(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let to_id = *self
+            .node_id(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+
+        Ok(self.remove_one_edge_by_id(from_id, to_id))
+    }
 
-                Some(ret)
+    // 平行なエッジを 1 本だけ削除する．最後の 1 本なら付随する情報も削除する
+    pub(super) fn remove_one_edge_by_id(&mut self, from_id: NodeID, to_id: NodeID) -> bool {
+        if let Some(m) = self.multiplicity.get_mut(&(from_id, to_id)) {
+            *m -= 1;
+            if *m == 1 {
+                self.multiplicity.remove(&(from_id, to_id));
             }
-            None => None,
+            return true;
         }
+        self.remove_edge_by_id(from_id, to_id)
+    }
+
+    // エッジとそれに付随する情報 (重みなど) を削除する (平行なエッジもすべて)
+    pub(super) fn remove_edge_by_id(&mut self, from_id: NodeID, to_id: NodeID) -> bool {
+        self.weights.remove(&(from_id, to_id));
+        self.multiplicity.remove(&(from_id, to_id));
+        self.provenance.remove(&(from_id, to_id));
+        self.labels.remove(&(from_id, to_id));
+        self.core.remove_edge(from_id, to_id)
     }
 
-    pub fn traverse<F>(&self, start: &T, mut f: F)
+    // エッジの重み．エッジが無ければ None
+    pub fn edge_weight<Q>(&self, u_from: &Q, u_to: &Q) -> Option<f64>
     where
-        F: FnMut(&T),
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
     {
-        if let Some(&start_id) = self.id_dict.get(start) {
-            let mut visited = HashSet::new();
-            self.traverse_recursive(start_id, &mut visited, &mut f);
+        let from_id = *self.node_id(u_from)?;
+        let to_id = *self.node_id(u_to)?;
+
+        if !self.core.nodes_dict[&from_id].children.contains(&to_id) {
+            return None;
         }
+        Some(self.weight_of(from_id, to_id))
+    }
+
+    pub(super) fn weight_of(&self, from_id: NodeID, to_id: NodeID) -> f64 {
+        self.weights.get(&(from_id, to_id)).copied().unwrap_or(1.0)
+    }
+
+    // サイクルを 1 つ返す．自己ループの扱いは self_loop_policy に従う
+    pub fn detect_cycle(&self) -> Option<Vec<&T>> {
+        let inner_ret = match self.self_loop_policy {
+            SelfLoopPolicy::IgnoreInCycles => self.core.detect_cycle_ignoring_self_loops(),
+            _ => self.core.detect_cycle(),
+        };
+
+        inner_ret.map(|v| self.nodes_from_ids(&v))
+    }
+
+    // 自己ループも含めてサイクルを 1 つ返す．DAG であることが必要な処理はこちらで調べる
+    pub(super) fn find_cycle(&self) -> Option<Vec<&T>> {
+        self.core.detect_cycle().map(|v| self.nodes_from_ids(&v))
     }
 
-    fn traverse_recursive<F>(&self, node_id: NodeID, visited: &mut HashSet<NodeID>, f: &mut F)
+    // NodeID の列 (サイクルなど) を元のノードの列に変換する
+    pub(super) fn nodes_from_ids(&self, ids: &[NodeID]) -> Vec<&T> {
+        let nodes = self.nodes_by_id();
+        ids.iter().map(|id| nodes[id]).collect()
+    }
+
+    // `start` から到達できるノードを深さ優先でたどり，各ノードで `f` を実行する．
+    // 探索は GraphCore::traverse (再帰しない) に任せる
+    pub fn traverse<Q, F>(&self, start: &Q, mut f: F)
     where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
         F: FnMut(&T),
     {
-        if visited.contains(&node_id) {
-            return;
-        }
-        visited.insert(node_id);
-
-        if let Some(node_value) = self.get_node_by_id(&node_id) {
-            f(node_value);
+        if let Some(&start_id) = self.node_id(start) {
+            let nodes = self.nodes_by_id();
+            self.core.traverse(start_id, |id| f(nodes[&id]));
         }
+    }
+}
 
-        if let Some(node) = self.core.nodes_dict.get(&node_id) {
-            for &child_id in &node.children {
-                self.traverse_recursive(child_id, visited, f);
-            }
-        }
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // エラーのメッセージをハンドルではなくノードの Debug 表示で作る
+    // (削除されたノードはハンドルのまま)
+    pub fn describe_error(&self, e: &GraphError) -> String {
+        e.render(|h| match self.get_node(h) {
+            Ok(u) => format!("{:?}", u),
+            Err(_) => h.to_string(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::{error::GraphError, handle::NodeHandle};
     use super::Graph;
 
     #[test]
@@ -119,16 +378,91 @@ mod tests {
         {
             // standard case
             let mut g = Graph::new();
-            assert_eq!(g.add_node(0), Ok(()))
+            assert_eq!(g.add_node(0), Ok(NodeHandle::new(0, 0)))
         }
         {
             // node duplication
             let mut g = Graph::new();
             let _ = g.add_node(0);
-            assert_eq!(g.add_node(0).is_err(), true);
+            assert!(matches!(
+                g.add_node(0),
+                Err(GraphError::NodeAlreadyExists { .. })
+            ));
         }
     }
 
+    #[test]
+    fn test_graph_add_weighted_edge() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_node("C");
+        assert_eq!(g.add_weighted_edge(&"A", &"B", 2.5), Ok(false));
+        assert_eq!(g.add_edge(&"B", &"C"), Ok(false));
+
+        assert_eq!(g.edge_weight(&"A", &"B"), Some(2.5));
+        assert_eq!(g.edge_weight(&"B", &"C"), Some(1.0)); // 重みなしのエッジ
+        assert_eq!(g.edge_weight(&"A", &"C"), None);
+
+        // 重みの上書き
+        assert_eq!(g.add_weighted_edge(&"A", &"B", 4.0), Ok(true));
+        assert_eq!(g.edge_weight(&"A", &"B"), Some(4.0));
+
+        assert_eq!(
+            g.add_weighted_edge(&"A", &"Z", 1.0),
+            Err(GraphError::NodeNotFound { arg: "to" })
+        );
+    }
+
+    #[test]
+    fn test_graph_remove_edge() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_weighted_edge(&"A", &"B", 3.0);
+
+        assert_eq!(g.remove_edge(&"A", &"B"), Ok(true));
+        assert_eq!(g.remove_edge(&"A", &"B"), Ok(false));
+        assert_eq!(g.edge_weight(&"A", &"B"), None);
+        assert!(g.remove_edge(&"A", &"Z").is_err());
+
+        // 削除後に追加し直した場合は重みなしに戻る
+        let _ = g.add_edge(&"A", &"B");
+        assert_eq!(g.edge_weight(&"A", &"B"), Some(1.0));
+    }
+
+    #[test]
+    fn test_graph_contains() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_edge(&"A", &"B");
+
+        assert!(g.contains_node(&"A"));
+        assert!(!g.contains_node(&"Z"));
+        assert!(g.contains_edge(&"A", &"B"));
+        assert!(!g.contains_edge(&"B", &"A"));
+        assert!(!g.contains_edge(&"A", &"Z"));
+    }
+
+    #[test]
+    fn test_graph_nodes_and_edges() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_node("C");
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"B");
+
+        let mut nodes: Vec<_> = g.nodes().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec![&"A", &"B", &"C"]);
+
+        let mut edges: Vec<_> = g.edges().collect();
+        edges.sort();
+        assert_eq!(edges, vec![(&"A", &"B"), (&"B", &"B")]);
+    }
+
     #[test]
     fn test_detect_cycle_no_cycle() {
         let mut g = Graph::new();
@@ -199,4 +533,138 @@ mod tests {
         assert!(cycle.len() >= 3);
         assert_eq!(cycle.first(), cycle.last());
     }
+
+    #[test]
+    fn test_graph_clone_debug_eq() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_weighted_edge(&"A", &"B", 2.0);
+
+        let mut h = g.clone();
+        assert_eq!(g, h);
+        assert_eq!(
+            format!("{:?}", g),
+            "Graph { nodes: [\"A\", \"B\"], edges: [(\"A\", \"B\", 2.0)] }"
+        );
+
+        // 追加順が違っても構造が同じなら等しい
+        let mut reordered = Graph::new();
+        let _ = reordered.add_node("B");
+        let _ = reordered.add_node("A");
+        let _ = reordered.add_weighted_edge(&"A", &"B", 2.0);
+        assert_eq!(g, reordered);
+
+        let _ = h.add_edge(&"B", &"A");
+        assert_ne!(g, h);
+        let _ = reordered.add_weighted_edge(&"A", &"B", 3.0);
+        assert_ne!(g, reordered);
+    }
+
+    #[test]
+    fn test_traverse_long_chain() {
+        let n = 100_000;
+        let mut g = Graph::new();
+        for u in 0..n {
+            let _ = g.add_node(u);
+        }
+        for u in 1..n {
+            let _ = g.add_edge(&(u - 1), &u);
+        }
+
+        let mut count = 0;
+        g.traverse(&0, |_| count += 1);
+        assert_eq!(count, n);
+
+        let mut visited = Vec::new();
+        g.traverse(&(n - 2), |u| visited.push(*u));
+        assert_eq!(visited, vec![n - 2, n - 1]);
+    }
+
+    #[test]
+    fn test_borrowed_lookup() {
+        // Graph<String> を &str で引ける
+        let mut g: Graph<String> = Graph::new();
+        for u in ["app", "lib", "util"] {
+            let _ = g.add_node(u.to_string());
+        }
+        assert_eq!(g.add_edge("app", "lib"), Ok(false));
+        let _ = g.add_weighted_edge("lib", "util", 2.0);
+        assert!(g.contains_node("app"));
+        assert!(g.contains_edge("app", "lib"));
+        assert_eq!(g.edge_weight("lib", "util"), Some(2.0));
+        assert!(matches!(
+            g.add_edge("app", "x"),
+            Err(GraphError::NodeNotFound { arg: "to" })
+        ));
+
+        let mut visited = Vec::new();
+        g.traverse("lib", |u| visited.push(u.clone()));
+        assert_eq!(visited, vec!["lib", "util"]);
+        assert_eq!(g.remove_edge("app", "lib"), Ok(true));
+        assert!(!g.contains_edge("app", "lib"));
+    }
+
+    #[test]
+    fn test_opaque_nodes() {
+        // Debug を実装しないハンドル
+        #[derive(PartialEq, Eq, Hash, Clone, Copy)]
+        struct Handle(u32);
+
+        let mut g = Graph::new();
+        for i in 0..3 {
+            let _ = g.add_node(Handle(i));
+        }
+        let _ = g.add_edge(&Handle(0), &Handle(1));
+        let _ = g.add_edge(&Handle(1), &Handle(2));
+        assert!(g.detect_cycle().is_none());
+        assert_eq!(
+            g.add_node(Handle(1)),
+            Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(1, 0)
+            })
+        );
+        assert_eq!(
+            g.add_edge(&Handle(7), &Handle(0)),
+            Err(GraphError::NodeNotFound { arg: "from" })
+        );
+
+        let _ = g.add_edge(&Handle(2), &Handle(0));
+        assert_eq!(g.detect_cycle().map(|c| c.len()), Some(4));
+    }
+
+    #[test]
+    fn test_describe_error() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let err = g.add_node("A").unwrap_err();
+        assert_eq!(err.to_string(), "node #0 is already added");
+        assert_eq!(g.describe_error(&err), "node \"A\" is already added");
+    }
+
+    #[test]
+    fn test_try_add_edge() {
+        let mut g = Graph::new_multigraph();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        assert_eq!(g.try_add_edge(&"A", &"B"), Ok(()));
+        let err = g.try_add_edge(&"A", &"B").unwrap_err();
+        assert_eq!(
+            err,
+            GraphError::EdgeExists {
+                from: NodeHandle::new(0, 0),
+                to: NodeHandle::new(1, 0)
+            }
+        );
+        assert_eq!(
+            g.describe_error(&err),
+            "edge \"A\" -> \"B\" is already added"
+        );
+        // 平行なエッジとしても数えない
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(
+            g.try_add_edge(&"A", &"C"),
+            Err(GraphError::NodeNotFound { arg: "to" })
+        );
+    }
 }
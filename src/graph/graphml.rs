@@ -0,0 +1,324 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    core::NodeID,
+    error::GraphError,
+    format::{node_label, xml_escape},
+    graph::Graph,
+};
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // GraphML 形式で書き出す．ノードの id は Debug 表示のラベル，重み付きのエッジは weight，
+    // ラベル付きのエッジは label のデータを持つ
+    pub fn write_graphml<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            w,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        writeln!(
+            w,
+            r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#
+        )?;
+        writeln!(
+            w,
+            r#"  <key id="label" for="edge" attr.name="label" attr.type="string"/>"#
+        )?;
+        writeln!(w, r#"  <graph id="G" edgedefault="directed">"#)?;
+        for id in &ids {
+            writeln!(
+                w,
+                r#"    <node id="{}"/>"#,
+                xml_escape(&node_label(nodes[id]))
+            )?;
+        }
+        for id in &ids {
+            let mut children = self.core.children_of(*id);
+            children.sort_unstable();
+
+            for child in children {
+                let (source, target) = (
+                    xml_escape(&node_label(nodes[id])),
+                    xml_escape(&node_label(nodes[&child])),
+                );
+                let mut data = String::new();
+                if let Some(weight) = self.weights.get(&(*id, child)) {
+                    data += &format!(r#"<data key="weight">{}</data>"#, weight);
+                }
+                if let Some(label) = self.labels.get(&(*id, child)) {
+                    data += &format!(r#"<data key="label">{}</data>"#, xml_escape(label));
+                }
+                if data.is_empty() {
+                    writeln!(w, r#"    <edge source="{}" target="{}"/>"#, source, target)?;
+                } else {
+                    writeln!(
+                        w,
+                        r#"    <edge source="{}" target="{}">{}</edge>"#,
+                        source, target, data
+                    )?;
+                }
+            }
+        }
+        writeln!(w, "  </graph>")?;
+        writeln!(w, "</graphml>")
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_graphml(&mut buf).unwrap(); // Vec への書き込みは失敗しない
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+#[derive(Debug)]
+enum XmlItem {
+    Open(String, HashMap<String, String>, bool), // (タグ名, 属性, 自己終了か)
+    Close(String),
+    Text(String),
+}
+
+fn parse_xml(input: &str) -> Result<Vec<XmlItem>, String> {
+    let mut items = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix("<!--") {
+            let end = r.find("-->").ok_or("unterminated comment")?;
+            rest = &r[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or("unterminated declaration")?;
+            rest = &rest[end + 1..];
+        } else if let Some(r) = rest.strip_prefix("</") {
+            let end = r.find('>').ok_or("unterminated tag")?;
+            items.push(XmlItem::Close(r[..end].trim().to_string()));
+            rest = &r[end + 1..];
+        } else if let Some(r) = rest.strip_prefix('<') {
+            let end = r.find('>').ok_or("unterminated tag")?;
+            let mut body = r[..end].trim();
+            let self_closing = body.ends_with('/');
+            if self_closing {
+                body = body[..body.len() - 1].trim();
+            }
+
+            let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+            let name = body[..name_end].to_string();
+            let mut attrs = HashMap::new();
+            let mut a = body[name_end..].trim_start();
+            while !a.is_empty() {
+                let eq = a
+                    .find('=')
+                    .ok_or(format!("malformed attribute in <{}>", name))?;
+                let key = a[..eq].trim().to_string();
+                let v = a[eq + 1..].trim_start();
+                let quote = v.chars().next().filter(|c| *c == '"' || *c == '\'');
+                let quote = quote.ok_or(format!("unquoted attribute {} in <{}>", key, name))?;
+                let close = v[1..]
+                    .find(quote)
+                    .ok_or(format!("unterminated attribute {} in <{}>", key, name))?;
+                attrs.insert(key, xml_unescape(&v[1..close + 1]));
+                a = v[close + 2..].trim_start();
+            }
+
+            items.push(XmlItem::Open(name, attrs, self_closing));
+            rest = &r[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                items.push(XmlItem::Text(xml_unescape(text)));
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    Ok(items)
+}
+
+// data 要素の key が宣言されたキー `key` か．キーが宣言されていなければ (key の無い data も) 当てはまらない
+fn is_key(attrs: &HashMap<String, String>, key: &Option<String>) -> bool {
+    key.as_ref().is_some_and(|k| attrs.get("key") == Some(k))
+}
+
+fn parse_graphml(input: &str) -> Result<Graph<String>, String> {
+    let items = parse_xml(input)?;
+
+    let mut weight_key = None;
+    let mut label_key = None;
+    let mut directed = true;
+    for item in &items {
+        match item {
+            XmlItem::Open(name, attrs, _)
+                if name == "key" && attrs.get("for").map(|s| s.as_str()) != Some("node") =>
+            {
+                match attrs.get("attr.name").map(|s| s.as_str()) {
+                    Some("weight") => weight_key = attrs.get("id").cloned(),
+                    Some("label") => label_key = attrs.get("id").cloned(),
+                    _ => {}
+                }
+            }
+            XmlItem::Open(name, attrs, _) if name == "graph" => {
+                directed = attrs.get("edgedefault").map(|s| s.as_str()) != Some("undirected");
+            }
+            _ => {}
+        }
+    }
+
+    let mut g = Graph::new();
+    let mut i = 0;
+    while i < items.len() {
+        match &items[i] {
+            XmlItem::Open(name, attrs, _) if name == "node" => {
+                let id = attrs.get("id").ok_or("node without id")?;
+                if !g.id_dict.contains_key(id) {
+                    g.add_node(id.clone()).map_err(|e| g.describe_error(&e))?;
+                }
+            }
+            XmlItem::Open(name, attrs, self_closing) if name == "edge" => {
+                let source = attrs.get("source").ok_or("edge without source")?.clone();
+                let target = attrs.get("target").ok_or("edge without target")?.clone();
+                let directed = match attrs.get("directed").map(|s| s.as_str()) {
+                    Some("true") => true,
+                    Some("false") => false,
+                    _ => directed,
+                };
+
+                // </edge> までの data から重みとラベルを探す
+                let mut weight = None;
+                let mut label = None;
+                if !self_closing {
+                    while i + 1 < items.len() {
+                        i += 1;
+                        match &items[i] {
+                            XmlItem::Close(name) if name == "edge" => break,
+                            XmlItem::Open(name, attrs, false)
+                                if name == "data" && is_key(attrs, &weight_key) =>
+                            {
+                                if let Some(XmlItem::Text(text)) = items.get(i + 1) {
+                                    let w = text
+                                        .parse::<f64>()
+                                        .map_err(|_| format!("invalid weight {:?}", text))?;
+                                    weight = Some(w);
+                                }
+                            }
+                            XmlItem::Open(name, attrs, false)
+                                if name == "data" && is_key(attrs, &label_key) =>
+                            {
+                                if let Some(XmlItem::Text(text)) = items.get(i + 1) {
+                                    label = Some(text.clone());
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+
+                for u in [&source, &target] {
+                    if !g.id_dict.contains_key(u) {
+                        g.add_node(u.clone()).map_err(|e| g.describe_error(&e))?;
+                    }
+                }
+                let mut edges = vec![(&source, &target)];
+                if !directed {
+                    edges.push((&target, &source));
+                }
+                for (a, b) in edges {
+                    match weight {
+                        Some(w) => g.add_weighted_edge(a, b, w),
+                        None => g.add_edge(a, b),
+                    }
+                    .map_err(|e| g.describe_error(&e))?;
+                    if let Some(label) = &label {
+                        g.add_edge_with_label(a, b, label)
+                            .map_err(|e| g.describe_error(&e))?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Ok(g)
+}
+
+impl Graph<String> {
+    // GraphML を読み込む．ノードの id をノードにし，attr.name="weight" のエッジ属性を重みとして読む
+    pub fn from_graphml(input: &str) -> Result<Graph<String>, GraphError> {
+        parse_graphml(input).map_err(GraphError::Parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_to_graphml() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B&C");
+        let _ = g.add_edge(&"A", &"B&C");
+        let _ = g.add_weighted_edge(&"B&C", &"A", 0.5);
+        let _ = g.add_edge_with_label(&"B&C", &"A", "uses");
+
+        let xml = g.to_graphml();
+        assert!(xml.contains(r#"<node id="B&amp;C"/>"#));
+        assert!(xml.contains(r#"<edge source="A" target="B&amp;C"/>"#));
+        assert!(xml.contains(
+            r#"<edge source="B&amp;C" target="A"><data key="weight">0.5</data><data key="label">uses</data></edge>"#
+        ));
+
+        let loaded = Graph::from_graphml(&xml).unwrap();
+        assert_eq!(loaded.to_graphml(), xml);
+    }
+
+    #[test]
+    fn test_from_graphml() {
+        let xml = r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="weight" attr.type="double"/>
+              <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+              <graph edgedefault="undirected">
+                <!-- yEd などの出力を想定 -->
+                <node id="x"><data key="d0">9</data></node>
+                <node id="y"/>
+                <edge source="x" target="y"><data key="d1">3</data></edge>
+                <edge source="y" target="z" directed="true"/>
+              </graph>
+            </graphml>"#;
+
+        let g = Graph::from_graphml(xml).unwrap();
+        let s = |x: &str| x.to_string();
+        assert_eq!(g.edge_weight(&s("x"), &s("y")), Some(3.0));
+        assert_eq!(g.edge_weight(&s("y"), &s("x")), Some(3.0));
+        assert_eq!(g.edge_weight(&s("y"), &s("z")), Some(1.0));
+        assert_eq!(g.edge_weight(&s("z"), &s("y")), None);
+
+        // weight のキーが宣言されていなければ key の無い data は重みとして読まない
+        let xml = r#"<graphml><graph>
+              <edge source="a" target="b"><data>note</data></edge>
+            </graph></graphml>"#;
+        let g = Graph::from_graphml(xml).unwrap();
+        assert_eq!(g.edge_weight(&s("a"), &s("b")), Some(1.0));
+
+        assert!(Graph::from_graphml("<graphml><node/></graphml>").is_err());
+        assert!(Graph::from_graphml("<graphml><node id=x/></graphml>").is_err());
+    }
+}
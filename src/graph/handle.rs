@@ -0,0 +1,141 @@
+use std::{borrow::Borrow, fmt, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+/// `Graph::add_node` が返すノードのハンドル
+///
+/// NodeID (ノードを置くスロットの番号) と世代の組．ノードを削除するとその NodeID は後で
+/// 追加するノードに使い回されるが，世代が進むので古いハンドルは `Graph::get_node` などで
+/// StaleHandle になり，新しいノードを指すことはない
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeHandle {
+    index: NodeID,
+    generation: u32,
+}
+
+impl NodeHandle {
+    pub(crate) fn new(index: NodeID, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    pub fn index(&self) -> NodeID {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+// 最初の世代は "#3"，使い回したスロットは "#3@1" のように世代も付ける
+impl fmt::Display for NodeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.generation {
+            0 => write!(f, "#{}", self.index),
+            g => write!(f, "#{}@{}", self.index, g),
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 今の世代のハンドル
+    pub(super) fn handle(&self, id: NodeID) -> NodeHandle {
+        NodeHandle::new(id, self.generations[id])
+    }
+
+    pub(super) fn handles(&self, ids: &[NodeID]) -> Vec<NodeHandle> {
+        ids.iter().map(|&id| self.handle(id)).collect()
+    }
+
+    // ハンドルが今あるノードを指していればその NodeID．削除されていれば StaleHandle
+    pub(super) fn check_handle(&self, h: NodeHandle) -> Result<NodeID, GraphError> {
+        if self.generations.get(h.index) == Some(&h.generation)
+            && self.core.nodes_dict.contains_key(&h.index)
+        {
+            Ok(h.index)
+        } else {
+            Err(GraphError::StaleHandle { handle: h })
+        }
+    }
+
+    // ノード (別名でもよい) のハンドル
+    pub fn handle_of<Q>(&self, u: &Q) -> Option<NodeHandle>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.node_id(u).map(|&id| self.handle(id))
+    }
+
+    // ハンドルの指すノード．削除されたノードのハンドルなら StaleHandle
+    pub fn get_node(&self, h: NodeHandle) -> Result<&T, GraphError> {
+        let id = self.check_handle(h)?;
+        Ok(self.id_dict.iter().find(|(_, &v)| v == id).unwrap().0) // check_handle で確かめた
+    }
+
+    // ノードとそれにつながるエッジ (重みなども)，ノードの別名を削除する．
+    // 削除したノードのハンドルはそれ以降 StaleHandle になる
+    pub fn remove_node<Q>(&mut self, u: &Q) -> Result<(), GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = *self
+            .node_id(u)
+            .ok_or(GraphError::NodeNotFound { arg: "u" })?;
+
+        let touches = |&(a, b): &(NodeID, NodeID)| a == id || b == id;
+        self.weights.retain(|k, _| !touches(k));
+        self.provenance.retain(|k, _| !touches(k));
+        self.labels.retain(|k, _| !touches(k));
+        self.multiplicity.retain(|k, _| !touches(k));
+        self.aliases.retain(|_, v| *v != id);
+        self.id_dict.retain(|_, v| *v != id);
+        self.core.remove_node(id);
+
+        self.generations[id] += 1;
+        self.free_ids.push(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    #[test]
+    fn test_stale_handle() {
+        let mut g = Graph::new();
+        let a = g.add_node("A").unwrap();
+        let b = g.add_node("B").unwrap();
+        let _ = g.add_weighted_edge(&"A", &"B", 2.0);
+        assert_eq!(g.get_node(a), Ok(&"A"));
+        assert_eq!(g.handle_of(&"B"), Some(b));
+
+        assert_eq!(g.remove_node(&"A"), Ok(()));
+        assert_eq!(g.get_node(a), Err(GraphError::StaleHandle { handle: a }));
+        assert!(g.predecessors(&"B").is_empty());
+
+        // A の NodeID を使い回しても古いハンドルは C を指さない
+        let c = g.add_node("C").unwrap();
+        assert_eq!(c.index(), a.index());
+        assert_eq!(c.generation(), 1);
+        assert_eq!(g.get_node(a), Err(GraphError::StaleHandle { handle: a }));
+        assert_eq!(g.get_node(c), Ok(&"C"));
+        assert!(!g.contains_edge(&"C", &"B"));
+        assert_eq!(g.edge_count(), 0);
+
+        let err = g.add_node("C").unwrap_err();
+        assert_eq!(err, GraphError::NodeAlreadyExists { node: c });
+        assert_eq!(err.to_string(), "node #0@1 is already added");
+        assert_eq!(g.describe_error(&err), "node \"C\" is already added");
+        assert_eq!(
+            g.describe_error(&GraphError::StaleHandle { handle: a }),
+            "node #0 has been removed"
+        );
+        assert_eq!(
+            g.remove_node(&"A"),
+            Err(GraphError::NodeNotFound { arg: "u" })
+        );
+    }
+}
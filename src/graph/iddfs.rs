@@ -0,0 +1,139 @@
+use std::{borrow::Borrow, collections::HashSet, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    path::Path,
+};
+
+impl GraphCore {
+    // 深さ `limit` までの深さ優先探索 (再帰しない)．探索中のパス上のノードには戻らない．
+    // 見つからなければ Err で，深さの制限で打ち切ったノードがあったかを返す
+    fn depth_limited<F>(&self, start: NodeID, goal: &F, limit: usize) -> Result<Vec<NodeID>, bool>
+    where
+        F: Fn(NodeID) -> bool,
+    {
+        let sorted_children = |id: NodeID| {
+            let mut children = self.children_of(id);
+            children.sort_unstable();
+            children.into_iter()
+        };
+
+        if goal(start) {
+            return Ok(vec![start]);
+        }
+        if limit == 0 {
+            return Err(!self.children_of(start).is_empty());
+        }
+        let mut cut_off = false;
+        let mut path = vec![start];
+        let mut on_path = HashSet::from([start]);
+        let mut stack = vec![sorted_children(start)];
+        while let Some(children) = stack.last_mut() {
+            let Some(child) = children.find(|c| !on_path.contains(c)) else {
+                stack.pop();
+                on_path.remove(&path.pop().unwrap());
+                continue;
+            };
+            if goal(child) {
+                path.push(child);
+                return Ok(path);
+            }
+            // child の深さは path.len()
+            if path.len() == limit {
+                cut_off |= !self.children_of(child).is_empty();
+                continue;
+            }
+            path.push(child);
+            on_path.insert(child);
+            stack.push(sorted_children(child));
+        }
+        Err(cut_off)
+    }
+
+    // 反復深化深さ優先探索．`goal` を満たすノードへの最も浅いパス (エッジ `max_depth` 本まで)．
+    // 使うメモリは深さに比例するだけなので，幅優先探索の待ち行列が膨らむ深い探索に使う
+    pub fn iddfs<F>(&self, start: NodeID, goal: F, max_depth: usize) -> Option<Vec<NodeID>>
+    where
+        F: Fn(NodeID) -> bool,
+    {
+        for limit in 0..=max_depth {
+            match self.depth_limited(start, &goal, limit) {
+                Ok(path) => return Some(path),
+                // 制限で打ち切ったノードが無ければ深くしても同じ
+                Err(false) => return None,
+                Err(true) => {}
+            }
+        }
+        None
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // `start` から `goal` を満たすノードへの，エッジの本数が最小のパス (`max_depth` 本まで)．
+    // 同じ深さなら子をノードの追加順にたどって先に見つかった方．経路の重みはエッジの重みの合計
+    pub fn iddfs<Q, F>(&self, start: &Q, goal: F, max_depth: usize) -> Option<Path<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: Fn(&T) -> bool,
+    {
+        let start_id = *self.node_id(start)?;
+        let nodes = self.nodes_by_id();
+
+        let ids = self
+            .core
+            .iddfs(start_id, |id| goal(nodes[&id]), max_depth)?;
+        Some(self.path_from_ids(&ids))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_iddfs() {
+        // A → B → C → D → E と A → F → E，E → A
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F", "G"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "D"),
+            ("D", "E"),
+            ("A", "F"),
+            ("F", "E"),
+            ("E", "A"),
+        ] {
+            let _ = g.add_edge(&a, &b);
+        }
+
+        let p = g.iddfs(&"A", |u| *u == "E", 10).unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"F", &"E"]);
+        assert_eq!(p.weight(), 2.0);
+        assert_eq!(g.iddfs(&"A", |u| *u == "D", 3).unwrap().edge_count(), 3);
+        assert!(g.iddfs(&"A", |u| *u == "D", 2).is_none());
+        assert_eq!(g.iddfs(&"A", |u| *u == "A", 0).unwrap().nodes(), &[&"A"]);
+        // サイクルがあっても到達できないノードの探索は終わる
+        assert!(g.iddfs(&"A", |u| *u == "G", usize::MAX).is_none());
+        assert!(g.iddfs(&"Z", |_| true, 5).is_none());
+    }
+
+    #[test]
+    fn test_iddfs_deep() {
+        // 深い一本道でも再帰しないのでスタックはあふれない
+        let n = 1_000;
+        let mut g = Graph::new();
+        for i in 0..n {
+            let _ = g.add_node(i);
+        }
+        for i in 1..n {
+            let _ = g.add_edge(&(i - 1), &i);
+        }
+        let p = g.iddfs(&0, |&u| u == n - 1, n).unwrap();
+        assert_eq!(p.edge_count(), n - 1);
+    }
+}
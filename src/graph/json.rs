@@ -0,0 +1,679 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    core::NodeID,
+    error::GraphError,
+    format::{json_quote, node_label},
+    graph::Graph,
+    provenance::Provenance,
+    self_loop::SelfLoopPolicy,
+};
+
+/// `write_json` と `write_binary` が書き出す文書のバージョン (2 つの形式で共通)．
+/// 形式を変えたら上げ，古いバージョンから 1 つ新しいバージョンへの変換を `MIGRATIONS` に足す
+pub const FORMAT_VERSION: u64 = 2;
+
+// 形式の名前 (ヘッダの "format")
+const FORMAT_NAME: &str = "graph_analyses";
+
+// MIGRATIONS[i] はバージョン i + 1 の文書をバージョン i + 2 に変換する
+type Migration = fn(JsonValue) -> Result<JsonValue, String>;
+const MIGRATIONS: [Migration; (FORMAT_VERSION - 1) as usize] = [v1_to_v2];
+
+// v2 で多重グラフか，自己ループの扱いと別名を持つようになった．v1 の文書はどれも既定のまま
+fn v1_to_v2(doc: JsonValue) -> Result<JsonValue, String> {
+    let JsonValue::Object(mut fields) = doc else {
+        return Err("document must be an object".to_string());
+    };
+    fields.push(("multigraph".to_string(), JsonValue::Bool(false)));
+    fields.push((
+        "self_loops".to_string(),
+        JsonValue::String(self_loop_name(SelfLoopPolicy::Allow).to_string()),
+    ));
+    fields.push(("aliases".to_string(), JsonValue::Array(Vec::new())));
+    Ok(JsonValue::Object(fields))
+}
+
+fn self_loop_name(policy: SelfLoopPolicy) -> &'static str {
+    match policy {
+        SelfLoopPolicy::Allow => "allow",
+        SelfLoopPolicy::Forbid => "forbid",
+        SelfLoopPolicy::IgnoreInCycles => "ignore_in_cycles",
+    }
+}
+
+// JSON の値．バイナリ形式も同じ文書をこの形で読み書きする
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    // JSON のテキストとして書き出す．数は有限であること (inf や NaN は文字列にしておく)
+    fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self {
+            JsonValue::Null => write!(w, "null"),
+            JsonValue::Bool(b) => write!(w, "{}", b),
+            JsonValue::Number(n) => write!(w, "{}", n),
+            JsonValue::String(s) => write!(w, "{}", json_quote(s)),
+            JsonValue::Array(items) => {
+                write!(w, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(w, ",")?;
+                    }
+                    item.write_to(w)?;
+                }
+                write!(w, "]")
+            }
+            JsonValue::Object(fields) => {
+                write!(w, "{{")?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(w, ",")?;
+                    }
+                    write!(w, "{}:", json_quote(k))?;
+                    v.write_to(w)?;
+                }
+                write!(w, "}}")
+            }
+        }
+    }
+}
+
+// 再帰下降の JSON パーサ
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => Err(format!("expected {:?} at {}, found {:?}", expected, i, c)),
+            None => Err(format!("expected {:?}, found end of input", expected)),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, String> {
+        for expected in word.chars() {
+            match self.chars.next() {
+                Some((_, c)) if c == expected => {}
+                _ => return Err(format!("invalid literal, expected `{}`", word)),
+            }
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err("unexpected end of input".to_string()),
+            Some((_, 'n')) => self.keyword("null", JsonValue::Null),
+            Some((_, 't')) => self.keyword("true", JsonValue::Bool(true)),
+            Some((_, 'f')) => self.keyword("false", JsonValue::Bool(false)),
+            Some((_, '"')) => self.string().map(JsonValue::String),
+            Some((_, '[')) => {
+                self.chars.next();
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+                    return Ok(JsonValue::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some((_, ',')) => {}
+                        Some((_, ']')) => return Ok(JsonValue::Array(items)),
+                        _ => return Err("expected `,` or `]` in array".to_string()),
+                    }
+                }
+            }
+            Some((_, '{')) => {
+                self.chars.next();
+                let mut fields = Vec::new();
+                self.skip_whitespace();
+                if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+                    return Ok(JsonValue::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    fields.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some((_, ',')) => {}
+                        Some((_, '}')) => return Ok(JsonValue::Object(fields)),
+                        _ => return Err("expected `,` or `}` in object".to_string()),
+                    }
+                }
+            }
+            Some((i, c)) if c == '-' || c.is_ascii_digit() => {
+                let mut s = String::new();
+                while let Some((_, c)) = self
+                    .chars
+                    .next_if(|(_, c)| c.is_ascii_digit() || "+-.eE".contains(*c))
+                {
+                    s.push(c);
+                }
+                s.parse::<f64>()
+                    .map(JsonValue::Number)
+                    .map_err(|_| format!("invalid number {:?} at {}", s, i))
+            }
+            Some((i, c)) => Err(format!("unexpected {:?} at {}", c, i)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut ret = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("unterminated string".to_string()),
+                Some((_, '"')) => return Ok(ret),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, '"')) => ret.push('"'),
+                    Some((_, '\\')) => ret.push('\\'),
+                    Some((_, '/')) => ret.push('/'),
+                    Some((_, 'b')) => ret.push('\u{8}'),
+                    Some((_, 'f')) => ret.push('\u{c}'),
+                    Some((_, 'n')) => ret.push('\n'),
+                    Some((_, 'r')) => ret.push('\r'),
+                    Some((_, 't')) => ret.push('\t'),
+                    Some((_, 'u')) => {
+                        let mut code = self.hex4()?;
+                        // サロゲートペア
+                        if (0xd800..0xdc00).contains(&code) {
+                            self.expect('\\')?;
+                            self.expect('u')?;
+                            let low = self.hex4()?;
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00));
+                        }
+                        ret.push(
+                            char::from_u32(code)
+                                .ok_or_else(|| format!("invalid code point {:x}", code))?,
+                        );
+                    }
+                    _ => return Err("invalid escape in string".to_string()),
+                },
+                Some((_, c)) => ret.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .chars
+                .next()
+                .and_then(|(_, c)| c.to_digit(16))
+                .ok_or("invalid \\u escape")?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+}
+
+fn parse_json(input: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser {
+        chars: input.char_indices().peekable(),
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        Some((i, _)) => Err(format!("trailing characters at {}", i)),
+        None => Ok(value),
+    }
+}
+
+// JSON の文書のヘッダ ("format" と "version") を確かめてバージョンを返す
+fn json_header(doc: &JsonValue) -> Result<u64, String> {
+    if doc.get("format").and_then(|f| f.as_str()) != Some(FORMAT_NAME) {
+        return Err(format!(
+            "missing header: \"format\" must be {:?}",
+            FORMAT_NAME
+        ));
+    }
+    match doc.get("version") {
+        Some(&JsonValue::Number(v)) if v >= 1.0 && v.fract() == 0.0 => Ok(v as u64),
+        _ => Err("missing header: \"version\" must be a positive integer".to_string()),
+    }
+}
+
+// このビルドで読めるバージョンか
+pub(super) fn check_version(version: u64) -> Result<(), String> {
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(format!(
+            "unsupported version {} (this build reads up to {})",
+            version, FORMAT_VERSION
+        ));
+    }
+    Ok(())
+}
+
+// バージョン `version` の文書を今のバージョンに変換する
+pub(super) fn migrate(version: u64, doc: JsonValue) -> Result<JsonValue, String> {
+    check_version(version)?;
+
+    let mut doc = doc;
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        doc = migration(doc)?;
+    }
+    Ok(doc)
+}
+
+// 今のバージョンの文書からグラフを作る
+pub(super) fn graph_from_document(doc: &JsonValue) -> Result<Graph<String>, String> {
+    let mut g = match doc.get("multigraph") {
+        Some(JsonValue::Bool(true)) => Graph::new_multigraph(),
+        Some(JsonValue::Bool(false)) => Graph::new(),
+        _ => return Err("\"multigraph\" must be a boolean".to_string()),
+    };
+
+    let nodes = doc
+        .get("nodes")
+        .and_then(|n| n.as_array())
+        .ok_or("\"nodes\" must be an array")?;
+    for node in nodes {
+        let u = node.as_str().ok_or("node must be a string")?;
+        g.add_node(u.to_string())
+            .map_err(|e| g.describe_error(&e))?;
+    }
+
+    let aliases = doc
+        .get("aliases")
+        .and_then(|a| a.as_array())
+        .ok_or("\"aliases\" must be an array")?;
+    for alias in aliases {
+        let field = |key: &str| {
+            alias
+                .get(key)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("alias must have a string {:?}", key))
+        };
+        g.alias(field("node")?, field("alias")?)
+            .map_err(|e| g.describe_error(&e))?;
+    }
+
+    let edges = doc
+        .get("edges")
+        .and_then(|e| e.as_array())
+        .ok_or("\"edges\" must be an array")?;
+    for edge in edges {
+        let endpoint = |key: &str| {
+            edge.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| format!("edge must have a string {:?}", key))
+        };
+        let (from, to) = (endpoint("from")?, endpoint("to")?);
+        let count = match edge.get("count") {
+            None => 1,
+            Some(&JsonValue::Number(c)) if c >= 1.0 && c.fract() == 0.0 => c as usize,
+            Some(other) => return Err(format!("invalid count {:?}", other)),
+        };
+        if count > 1 && !g.is_multigraph() {
+            return Err("\"count\" is only allowed in a multigraph".to_string());
+        }
+        for _ in 0..count {
+            g.add_edge(&from, &to).map_err(|e| g.describe_error(&e))?;
+        }
+        let key = (*g.node_id(&from).unwrap(), *g.node_id(&to).unwrap()); // add_edge で確かめた
+
+        match edge.get("weight") {
+            None => {}
+            Some(&JsonValue::Number(w)) => {
+                g.weights.insert(key, w);
+            }
+            // JSON の数にできない inf や NaN は文字列で書かれる
+            Some(JsonValue::String(s)) => {
+                let w = s
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid weight {:?}", s))?;
+                g.weights.insert(key, w);
+            }
+            Some(other) => return Err(format!("invalid weight {:?}", other)),
+        }
+        if let Some(label) = edge.get("label") {
+            let label = label.as_str().ok_or("\"label\" must be a string")?;
+            g.labels.insert(key, label.to_string());
+        }
+
+        for p in edge
+            .get("provenance")
+            .map_or(Some(&[][..]), |p| p.as_array())
+            .ok_or("\"provenance\" must be an array")?
+        {
+            let field = |key: &str| {
+                p.get(key)
+                    .ok_or_else(|| format!("provenance needs {:?}", key))
+            };
+            let file = field("file")?.as_str().ok_or("\"file\" must be a string")?;
+            let rule = field("rule")?.as_str().ok_or("\"rule\" must be a string")?;
+            let line = match field("line")? {
+                &JsonValue::Number(l) if l >= 0.0 && l.fract() == 0.0 => l as usize,
+                _ => return Err("\"line\" must be a non-negative integer".to_string()),
+            };
+            g.provenance
+                .entry(key)
+                .or_default()
+                .push(Provenance::new(file, line, rule));
+        }
+    }
+
+    // 自己ループを禁止する文書に自己ループがあればエラー
+    let policy = match doc.get("self_loops").and_then(|p| p.as_str()) {
+        Some("allow") => SelfLoopPolicy::Allow,
+        Some("forbid") => SelfLoopPolicy::Forbid,
+        Some("ignore_in_cycles") => SelfLoopPolicy::IgnoreInCycles,
+        _ => {
+            return Err(
+                "\"self_loops\" must be \"allow\", \"forbid\" or \"ignore_in_cycles\"".to_string(),
+            )
+        }
+    };
+    g.set_self_loop_policy(policy)
+        .map_err(|e| g.describe_error(&e))?;
+
+    Ok(g)
+}
+
+fn parse_graph(input: &str) -> Result<Graph<String>, String> {
+    let doc = parse_json(input)?;
+    let version = json_header(&doc)?;
+    graph_from_document(&migrate(version, doc)?)
+}
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // ヘッダを除いた今のバージョンの文書．ノードは Debug 表示のラベルで，多重グラフか，
+    // 自己ループの扱いと別名を持つ．エッジは平行なエッジが 2 本以上なら count，
+    // 重み付きなら weight，ラベルがあれば label，出所があれば provenance を持つ
+    pub(super) fn to_document(&self) -> Vec<(String, JsonValue)> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let label = |id: &NodeID| JsonValue::String(node_label(nodes[id]));
+        let string = |s: &str| JsonValue::String(s.to_string());
+
+        let mut aliases: Vec<(NodeID, String)> = self
+            .aliases
+            .iter()
+            .map(|(a, &id)| (id, node_label(a)))
+            .collect();
+        aliases.sort_unstable();
+        let aliases = aliases
+            .into_iter()
+            .map(|(id, a)| {
+                JsonValue::Object(vec![
+                    ("alias".to_string(), JsonValue::String(a)),
+                    ("node".to_string(), label(&id)),
+                ])
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        for id in &ids {
+            let mut children = self.core.children_of(*id);
+            children.sort_unstable();
+
+            for child in children {
+                let key = (*id, child);
+                let mut fields = vec![
+                    ("from".to_string(), label(id)),
+                    ("to".to_string(), label(&child)),
+                ];
+                let count = self.multiplicity_of(*id, child);
+                if count > 1 {
+                    fields.push(("count".to_string(), JsonValue::Number(count as f64)));
+                }
+                if let Some(&weight) = self.weights.get(&key) {
+                    let weight = if weight.is_finite() {
+                        JsonValue::Number(weight)
+                    } else {
+                        JsonValue::String(weight.to_string())
+                    };
+                    fields.push(("weight".to_string(), weight));
+                }
+                if let Some(l) = self.labels.get(&key) {
+                    fields.push(("label".to_string(), string(l)));
+                }
+                if let Some(ps) = self.provenance.get(&key) {
+                    let ps = ps
+                        .iter()
+                        .map(|p| {
+                            JsonValue::Object(vec![
+                                ("file".to_string(), string(&p.file)),
+                                ("line".to_string(), JsonValue::Number(p.line as f64)),
+                                ("rule".to_string(), string(&p.rule)),
+                            ])
+                        })
+                        .collect();
+                    fields.push(("provenance".to_string(), JsonValue::Array(ps)));
+                }
+                edges.push(JsonValue::Object(fields));
+            }
+        }
+
+        vec![
+            ("multigraph".to_string(), JsonValue::Bool(self.multigraph)),
+            (
+                "self_loops".to_string(),
+                string(self_loop_name(self.self_loop_policy)),
+            ),
+            (
+                "nodes".to_string(),
+                JsonValue::Array(ids.iter().map(label).collect()),
+            ),
+            ("aliases".to_string(), JsonValue::Array(aliases)),
+            ("edges".to_string(), JsonValue::Array(edges)),
+        ]
+    }
+
+    // このクレートの JSON 形式で書き出す．先頭に形式の名前とバージョンを持ち，
+    // 続く内容は to_document のとおり
+    pub fn write_json<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut fields = vec![
+            (
+                "format".to_string(),
+                JsonValue::String(FORMAT_NAME.to_string()),
+            ),
+            (
+                "version".to_string(),
+                JsonValue::Number(FORMAT_VERSION as f64),
+            ),
+        ];
+        fields.extend(self.to_document());
+        JsonValue::Object(fields).write_to(w)?;
+        writeln!(w)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_json(&mut buf).unwrap(); // Vec への書き込みは失敗しない
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+impl Graph<String> {
+    // write_json で書き出した JSON を読み込む．古いバージョンの文書は読み込み時に変換し，
+    // ヘッダが無いものやこのビルドより新しいバージョンのものはエラーにする
+    pub fn from_json(input: &str) -> Result<Graph<String>, GraphError> {
+        parse_graph(input).map_err(GraphError::Parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        error::GraphError, graph::Graph, provenance::Provenance, self_loop::SelfLoopPolicy,
+    };
+    use super::{parse_json, JsonValue};
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            parse_json(r#" {"a": [1, -2.5e1, true, null], "b": "x\"é😀"} "#),
+            Ok(JsonValue::Object(vec![
+                (
+                    "a".to_string(),
+                    JsonValue::Array(vec![
+                        JsonValue::Number(1.0),
+                        JsonValue::Number(-25.0),
+                        JsonValue::Bool(true),
+                        JsonValue::Null,
+                    ])
+                ),
+                ("b".to_string(), JsonValue::String("x\"é😀".to_string())),
+            ]))
+        );
+        assert!(parse_json("[1,]").is_err());
+        assert!(parse_json("{} x").is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut g = Graph::new();
+        for u in ["A", "B \"quoted\"", "C"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let (a, b, c) = ("A".to_string(), "B \"quoted\"".to_string(), "C".to_string());
+        let _ = g.add_edge_with_label(&a, &b, "depends-on");
+        let _ = g.add_weighted_edge(&b, &c, 0.25);
+        let _ = g.add_weighted_edge(&c, &a, f64::INFINITY);
+        let _ = g.add_edge_with_provenance(&a, &c, Provenance::new("src/a.rs", 3, "use"));
+
+        let json = g.to_json();
+        assert!(json.starts_with(
+            r#"{"format":"graph_analyses","version":2,"multigraph":false,"self_loops":"allow","nodes":["A","#
+        ));
+
+        let loaded = Graph::from_json(&json).unwrap();
+        assert_eq!(loaded, g);
+        assert_eq!(loaded.edge_weight(&c, &a), Some(f64::INFINITY));
+        assert_eq!(loaded.provenance(&a, &c), g.provenance(&a, &c));
+        assert_eq!(loaded.edge_label(&a, &b), Some("depends-on"));
+        assert_eq!(loaded.to_json(), json);
+    }
+
+    #[test]
+    fn test_from_json_versions() {
+        let doc = |version: &str| {
+            format!(r#"{{"format":"graph_analyses","version":{version},"nodes":["A"],"edges":[]}}"#)
+        };
+        assert!(Graph::from_json(&doc("1")).is_ok());
+
+        let err = Graph::from_json(&doc("3")).err().unwrap();
+        assert!(
+            matches!(&err, GraphError::Parse(msg) if msg.starts_with("unsupported version 3")),
+            "{err}"
+        );
+        // v2 の文書は多重グラフかなどを持たなければならない
+        assert!(Graph::from_json(&doc("2")).is_err());
+        assert!(Graph::from_json(&doc("0")).is_err());
+        assert!(Graph::from_json(r#"{"nodes":[],"edges":[]}"#).is_err());
+        assert!(Graph::from_json(
+            &doc("1").replace(r#""edges":[]"#, r#""edges":[{"from":"A","to":"Z"}]"#)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_from_json_v1() {
+        // v1 の文書は v2 に変換して読む (多重グラフではなく，自己ループを許し，別名は無い)
+        let v1 = r#"{"format":"graph_analyses","version":1,"nodes":["A","B"],
+            "edges":[{"from":"A","to":"B","weight":2.5,"label":"calls"},{"from":"B","to":"B"}]}"#;
+        let g = Graph::from_json(v1).unwrap();
+        assert!(!g.is_multigraph());
+        assert_eq!(g.self_loop_policy(), SelfLoopPolicy::Allow);
+        assert_eq!(g.edge_weight("A", "B"), Some(2.5));
+        assert_eq!(g.edge_label("A", "B"), Some("calls"));
+        assert!(g.contains_edge("B", "B"));
+        assert_eq!(
+            g.to_json(),
+            r#"{"format":"graph_analyses","version":2,"multigraph":false,"self_loops":"allow","nodes":["A","B"],"aliases":[],"edges":[{"from":"A","to":"B","weight":2.5,"label":"calls"},{"from":"B","to":"B"}]}"#
+                .to_string()
+                + "\n"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip_model() {
+        // 平行なエッジの本数，別名と自己ループの扱いも残る
+        let mut g = Graph::new_multigraph();
+        for u in ["foo.rs", "bar.rs"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.alias("foo.rs", "crate::foo");
+        let _ = g.add_weighted_edge("foo.rs", "bar.rs", 2.0);
+        let _ = g.add_edge("crate::foo", "bar.rs");
+        let _ = g.add_edge("bar.rs", "foo.rs");
+        let _ = g.set_self_loop_policy(SelfLoopPolicy::Forbid);
+
+        let json = g.to_json();
+        assert!(json.contains(r#""aliases":[{"alias":"crate::foo","node":"foo.rs"}]"#));
+        assert!(json.contains(r#"{"from":"foo.rs","to":"bar.rs","count":2,"weight":2}"#));
+
+        let loaded = Graph::from_json(&json).unwrap();
+        assert_eq!(loaded, g);
+        assert!(loaded.is_multigraph());
+        assert_eq!(loaded.edge_multiplicity("foo.rs", "bar.rs"), 2);
+        assert_eq!(loaded.edge_count(), 3);
+        assert_eq!(
+            loaded.canonical("crate::foo").map(|u| u.as_str()),
+            Some("foo.rs")
+        );
+        assert_eq!(loaded.self_loop_policy(), SelfLoopPolicy::Forbid);
+        assert_eq!(loaded.to_json(), json);
+
+        // 自己ループを禁止する文書に自己ループがあれば読めない
+        let looped = json.replace(
+            r#"{"from":"bar.rs","to":"foo.rs"}"#,
+            r#"{"from":"bar.rs","to":"bar.rs"}"#,
+        );
+        assert!(matches!(
+            Graph::from_json(&looped),
+            Err(GraphError::Parse(msg)) if msg.contains("self-loop")
+        ));
+        // 多重グラフでなければ count は書けない
+        let simple = json.replace(r#""multigraph":true"#, r#""multigraph":false"#);
+        assert!(Graph::from_json(&simple).is_err());
+    }
+}
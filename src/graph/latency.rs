@@ -0,0 +1,213 @@
+use std::{borrow::Borrow, collections::HashMap, fmt::Debug, hash::Hash, time::Duration};
+
+use super::{core::NodeID, dijkstra::path_to, error::GraphError, graph::Graph, handle::NodeHandle};
+
+/// 遅延付きの経路．`hops` は経路上の各エッジとその遅延
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyPath<'a, T> {
+    pub nodes: Vec<&'a T>,
+    pub total: Duration,
+    pub hops: Vec<(&'a T, &'a T, Duration)>,
+}
+
+impl<T> LatencyPath<'_, T> {
+    // 遅延が最も大きいエッジ (同じならより前のもの)
+    pub fn slowest_hop(&self) -> Option<&(&T, &T, Duration)> {
+        self.hops
+            .iter()
+            .reduce(|acc, h| if h.2 > acc.2 { h } else { acc })
+    }
+}
+
+/// エッジが遅延 (`Duration`) を持つグラフ．遅延は f64 に変換せずに足し合わせる
+pub struct LatencyGraph<T: PartialEq + Eq + Hash> {
+    graph: Graph<T>,
+    latencies: HashMap<(NodeID, NodeID), Duration>,
+}
+
+impl<T: PartialEq + Eq + Hash> Default for LatencyGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> LatencyGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            latencies: HashMap::new(),
+        }
+    }
+
+    // 遅延を除いたグラフ
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    pub fn add_node(&mut self, u: T) -> Result<NodeHandle, GraphError> {
+        self.graph.add_node(u)
+    }
+
+    // 遅延 `latency` のエッジを追加する．すでにあれば遅延を上書きして true を返す
+    pub fn add_edge<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        latency: Duration,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ret = self.graph.add_edge(u_from, u_to)?;
+
+        self.latencies.insert(
+            (
+                *self.graph.node_id(u_from).unwrap(),
+                *self.graph.node_id(u_to).unwrap(),
+            ),
+            latency,
+        );
+        Ok(ret)
+    }
+
+    pub fn latency<Q>(&self, u_from: &Q, u_to: &Q) -> Option<Duration>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = self.graph.node_id(u_from)?;
+        let to_id = self.graph.node_id(u_to)?;
+        self.latencies.get(&(*from_id, *to_id)).copied()
+    }
+
+    // ノードの列 (別名でもよい) に沿った遅延の内訳．無いノードや列にないエッジがあれば None，
+    // 合計が桁あふれしたらエラー
+    pub fn path_latency<Q>(&self, nodes: &[&Q]) -> Result<Option<LatencyPath<'_, T>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(nodes) = nodes
+            .iter()
+            .map(|u| self.graph.canonical(*u))
+            .collect::<Option<Vec<&T>>>()
+        else {
+            return Ok(None);
+        };
+
+        let mut hops = Vec::new();
+        let mut total = Duration::ZERO;
+        for w in nodes.windows(2) {
+            let Some(latency) = self.latency::<T>(w[0], w[1]) else {
+                return Ok(None);
+            };
+            total = total
+                .checked_add(latency)
+                .ok_or_else(|| GraphError::WeightOverflow {
+                    from: self.graph.handle_of::<T>(w[0]).unwrap(),
+                    to: self.graph.handle_of::<T>(w[1]).unwrap(),
+                })?;
+            hops.push((w[0], w[1], latency));
+        }
+
+        Ok(Some(LatencyPath { nodes, total, hops }))
+    }
+
+    // 遅延の合計が最小の経路．到達できなければ Ok(None)
+    pub fn shortest_latency_path<Q>(
+        &self,
+        from: &Q,
+        to: &Q,
+    ) -> Result<Option<LatencyPath<'_, T>>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &from_id = self
+            .graph
+            .node_id(from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let &to_id = self
+            .graph
+            .node_id(to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+
+        let (dist, parent) = self
+            .graph
+            .core
+            .dijkstra_by(from_id, Some(to_id), |a, b| self.latencies[&(a, b)])
+            .map_err(|(a, b)| GraphError::WeightOverflow {
+                from: self.graph.handle(a),
+                to: self.graph.handle(b),
+            })?;
+        if !dist.contains_key(&to_id) {
+            return Ok(None);
+        }
+
+        let path = self.graph.nodes_from_ids(&path_to(&parent, from_id, to_id));
+        self.path_latency::<T>(&path)
+    }
+
+    // `from` から到達できる各ノードへの最小の遅延
+    pub fn latencies_from<Q>(&self, from: &Q) -> Result<HashMap<&T, Duration>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &from_id = self
+            .graph
+            .node_id(from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+
+        let nodes = self.graph.nodes_by_id();
+        let (dist, _) = self
+            .graph
+            .core
+            .dijkstra_by(from_id, None, |a, b| self.latencies[&(a, b)])
+            .map_err(|(a, b)| GraphError::WeightOverflow {
+                from: self.graph.handle(a),
+                to: self.graph.handle(b),
+            })?;
+        Ok(dist.into_iter().map(|(id, d)| (nodes[&id], d)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LatencyGraph;
+
+    #[test]
+    fn test_shortest_latency_path() {
+        let ms = Duration::from_millis;
+        let ns = Duration::from_nanos;
+
+        let mut g = LatencyGraph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        // f64 の秒にすると区別できない差 (1ns)
+        let _ = g.add_edge(&"A", &"B", ms(10_000_000));
+        let _ = g.add_edge(&"B", &"C", ns(1));
+        let _ = g.add_edge(&"A", &"C", ms(10_000_000) + ns(2));
+
+        let p = g.shortest_latency_path(&"A", &"C").unwrap().unwrap();
+        assert_eq!(p.nodes, vec![&"A", &"B", &"C"]);
+        assert_eq!(p.total, ms(10_000_000) + ns(1));
+        assert_eq!(p.hops[1], (&"B", &"C", ns(1)));
+        assert_eq!(p.slowest_hop(), Some(&(&"A", &"B", ms(10_000_000))));
+
+        assert_eq!(g.shortest_latency_path(&"C", &"A"), Ok(None));
+        assert_eq!(g.latencies_from(&"A").unwrap()[&"C"], p.total);
+        assert_eq!(
+            g.path_latency(&[&"A", &"C"]).unwrap().unwrap().hops.len(),
+            1
+        );
+        assert_eq!(g.path_latency(&[&"C", &"A"]), Ok(None));
+
+        let _ = g.add_edge(&"C", &"A", Duration::MAX);
+        assert!(g.path_latency(&[&"A", &"C", &"A"]).is_err());
+    }
+}
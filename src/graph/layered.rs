@@ -0,0 +1,211 @@
+use std::{borrow::Borrow, collections::HashMap, fmt::Debug, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, handle::NodeHandle};
+
+/// エッジが同じ層の中のものか，層をまたぐものか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    IntraLayer,
+    InterLayer,
+}
+
+/// ノードが名前付きの層 ("module", "function" など) に属するグラフ
+pub struct LayeredGraph<T: PartialEq + Eq + Hash> {
+    graph: Graph<T>,
+    layers: HashMap<NodeID, String>,
+}
+
+impl<T: PartialEq + Eq + Hash> Default for LayeredGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> LayeredGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            layers: HashMap::new(),
+        }
+    }
+
+    // 全層をまとめたグラフ
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    // ノードを層 `layer` に登録する
+    pub fn add_node(&mut self, layer: &str, u: T) -> Result<NodeHandle, GraphError> {
+        let h = self.graph.add_node(u)?;
+        self.layers.insert(h.index(), layer.to_string());
+        Ok(h)
+    }
+
+    // 層の内外を問わずエッジを追加する
+    pub fn add_edge<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.add_edge(u_from, u_to)
+    }
+
+    pub fn add_weighted_edge<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        weight: f64,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.add_weighted_edge(u_from, u_to, weight)
+    }
+
+    pub fn layer_of<Q>(&self, u: &Q) -> Option<&str>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.graph.node_id(u)?;
+        self.layers.get(id).map(|s| s.as_str())
+    }
+
+    // 層の名前の一覧 (辞書順)
+    pub fn layers(&self) -> Vec<&str> {
+        let mut ret: Vec<&str> = self.layers.values().map(|s| s.as_str()).collect();
+        ret.sort_unstable();
+        ret.dedup();
+        ret
+    }
+
+    pub fn nodes_in_layer(&self, layer: &str) -> Vec<&T> {
+        let mut ids: Vec<NodeID> = self
+            .layers
+            .iter()
+            .filter(|(_, l)| l.as_str() == layer)
+            .map(|(&id, _)| id)
+            .collect();
+        ids.sort_unstable();
+
+        self.graph.nodes_from_ids(&ids)
+    }
+
+    // エッジ u_from → u_to の種類．エッジが無ければ None
+    pub fn edge_kind<Q>(&self, u_from: &Q, u_to: &Q) -> Option<EdgeKind>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.edge_weight(u_from, u_to)?;
+
+        if self.layer_of(u_from) == self.layer_of(u_to) {
+            Some(EdgeKind::IntraLayer)
+        } else {
+            Some(EdgeKind::InterLayer)
+        }
+    }
+
+    // 層をまたぐエッジの一覧
+    pub fn inter_layer_edges(&self) -> Vec<(&T, &T)> {
+        let nodes = self.graph.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .graph
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .filter(|(a, b)| self.layers[a] != self.layers[b])
+            .collect();
+        edges.sort_unstable();
+
+        edges.iter().map(|(a, b)| (nodes[a], nodes[b])).collect()
+    }
+
+    // 1 つの層のノードと，その層の中のエッジ (重み付き) だけからなるグラフ
+    pub fn projection(&self, layer: &str) -> Graph<&T> {
+        let nodes = self.graph.nodes_by_id();
+        let mut ids: Vec<NodeID> = self
+            .layers
+            .iter()
+            .filter(|(_, l)| l.as_str() == layer)
+            .map(|(&id, _)| id)
+            .collect();
+        ids.sort_unstable();
+
+        let mut g = Graph::new();
+        for id in &ids {
+            let _ = g.add_node(nodes[id]);
+        }
+        for id in &ids {
+            for child in self.graph.core.children_of(*id) {
+                if self.layers[&child] == layer {
+                    let w = self.graph.weight_of(*id, child);
+                    let _ = g.add_weighted_edge(&nodes[id], &nodes[&child], w);
+                }
+            }
+        }
+
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EdgeKind, LayeredGraph};
+
+    fn sample() -> LayeredGraph<&'static str> {
+        let mut g = LayeredGraph::new();
+        let _ = g.add_node("module", "app");
+        let _ = g.add_node("module", "util");
+        let _ = g.add_node("function", "main");
+        let _ = g.add_node("function", "parse");
+        let _ = g.add_node("function", "helper");
+
+        let _ = g.add_edge(&"app", &"util");
+        let _ = g.add_edge(&"main", &"parse");
+        let _ = g.add_weighted_edge(&"parse", &"helper", 3.0);
+        let _ = g.add_edge(&"app", &"main"); // 定義
+        let _ = g.add_edge(&"util", &"helper");
+        g
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut g = sample();
+
+        assert_eq!(g.layers(), vec!["function", "module"]);
+        assert_eq!(g.layer_of(&"parse"), Some("function"));
+        assert_eq!(g.layer_of(&"nothing"), None);
+        assert_eq!(g.nodes_in_layer("module"), vec![&"app", &"util"]);
+        assert!(g.add_node("module", "main").is_err());
+    }
+
+    #[test]
+    fn test_edge_kinds() {
+        let g = sample();
+
+        assert_eq!(g.edge_kind(&"app", &"util"), Some(EdgeKind::IntraLayer));
+        assert_eq!(g.edge_kind(&"app", &"main"), Some(EdgeKind::InterLayer));
+        assert_eq!(g.edge_kind(&"util", &"app"), None);
+        assert_eq!(
+            g.inter_layer_edges(),
+            vec![(&"app", &"main"), (&"util", &"helper")]
+        );
+    }
+
+    #[test]
+    fn test_projection() {
+        let g = sample();
+
+        let functions = g.projection("function");
+        assert_eq!(functions.edge_weight(&&"parse", &&"helper"), Some(3.0));
+        assert_eq!(functions.edge_weight(&&"main", &&"parse"), Some(1.0));
+        assert_eq!(functions.dfs_iter(&&"main").count(), 3);
+
+        let modules = g.projection("module");
+        assert_eq!(modules.dfs_iter(&&"app").count(), 2);
+        assert!(g.projection("nothing").detect_cycle().is_none());
+    }
+}
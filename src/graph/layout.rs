@@ -0,0 +1,286 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+// ディレクトリ → 中身のエッジのラベル
+pub const CONTAINS: &str = "contains";
+// merge_imports で取り込んだエッジのラベル
+pub const IMPORTS: &str = "imports";
+// CODEOWNERS の所有者 → パスのエッジのラベル
+pub const OWNS: &str = "owns";
+
+/// ディレクトリ構成を包含関係のグラフ (ディレクトリ → 中のファイル・ディレクトリ) として読み込む
+///
+/// ノードはルートからの相対パス (区切りは `/`，ルート自身は `"."`) で，
+/// エッジには `CONTAINS` のラベルが付く．シンボリックリンクはたどらない
+pub struct DirectoryLoader {
+    root: PathBuf,
+    ignored: Vec<String>,
+    extensions: Vec<String>,
+}
+
+impl DirectoryLoader {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            ignored: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    // この名前のファイルとディレクトリ (`.git` など) は読まない
+    pub fn ignore(mut self, name: &str) -> Self {
+        self.ignored.push(name.to_string());
+        self
+    }
+
+    // 拡張子 `ext` のファイルだけを読む．何度でも指定でき，指定が無ければすべてのファイルを読む
+    pub fn extension(mut self, ext: &str) -> Self {
+        self.extensions
+            .push(ext.trim_start_matches('.').to_string());
+        self
+    }
+
+    fn wants_file(&self, path: &Path) -> bool {
+        self.extensions.is_empty()
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| self.extensions.iter().any(|x| x == e))
+    }
+
+    // ディレクトリを名前順にたどってグラフを作る
+    pub fn load(&self) -> Result<Graph<String>, GraphError> {
+        let mut g = Graph::new();
+        g.add_node(".".to_string())?;
+
+        let mut stack = vec![(self.root.clone(), ".".to_string())];
+        while let Some((dir, name)) = stack.pop() {
+            let mut entries = fs::read_dir(&dir)?.collect::<Result<Vec<_>, _>>()?;
+            entries.sort_by_key(|e| e.file_name());
+
+            let mut subdirs = Vec::new();
+            for entry in entries {
+                let file_name = entry.file_name().to_string_lossy().into_owned();
+                if self.ignored.contains(&file_name) {
+                    continue;
+                }
+                let file_type = entry.file_type()?;
+                if !(file_type.is_dir() || file_type.is_file() && self.wants_file(&entry.path())) {
+                    continue;
+                }
+
+                let child = if name == "." {
+                    file_name
+                } else {
+                    format!("{}/{}", name, file_name)
+                };
+                g.add_node(child.clone())?;
+                g.add_edge_with_label(&name, &child, CONTAINS)?;
+                if file_type.is_dir() {
+                    subdirs.push((entry.path(), child));
+                }
+            }
+            // 名前順に読むため逆順に積む
+            stack.extend(subdirs.into_iter().rev());
+        }
+        Ok(g)
+    }
+}
+
+// パターンの 1 区切り分とパスの 1 区切り分を照合する (`*` と `?` が使える)
+fn glob_match(pattern: &[u8], s: &[u8]) -> bool {
+    match (pattern.first(), s.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], s) || !s.is_empty() && glob_match(pattern, &s[1..])
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &s[1..]),
+        (Some(p), Some(c)) if p == c => glob_match(&pattern[1..], &s[1..]),
+        _ => false,
+    }
+}
+
+// CODEOWNERS のパターンが `path` (ルートからの相対パス) に当てはまるか．
+// ディレクトリに当てはまればその中身にも当てはまる
+fn codeowners_match(pattern: &str, path: &str, is_dir: bool) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    // 途中に '/' があればルートからのパスとして扱う
+    let anchored = pattern.contains('/');
+    let pattern: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+    let segments: Vec<&str> = path.split('/').collect();
+
+    (1..=segments.len()).any(|k| {
+        // k 番目までの区切り (パス自身か，その祖先のディレクトリ)
+        let prefix = &segments[..k];
+        if dir_only && k == segments.len() && !is_dir {
+            return false;
+        }
+        if anchored {
+            prefix.len() == pattern.len()
+                && pattern
+                    .iter()
+                    .zip(prefix)
+                    .all(|(p, s)| glob_match(p.as_bytes(), s.as_bytes()))
+        } else {
+            pattern[0] == "*" || glob_match(pattern[0].as_bytes(), prefix[k - 1].as_bytes())
+        }
+    })
+}
+
+impl Graph<String> {
+    // 包含関係のグラフに import のグラフを取り込み，エッジに `IMPORTS` のラベルを付ける．
+    // ノードは同じ相対パスで対応させ，包含関係のグラフに無いものは追加する．
+    // 取り込んだエッジの本数を返す
+    pub fn merge_imports(&mut self, imports: &Graph<String>) -> Result<usize, GraphError> {
+        for u in imports.nodes() {
+            if !self.contains_node(u) {
+                self.add_node(u.clone())?;
+            }
+        }
+        let mut count = 0;
+        for (a, b) in imports.edges() {
+            self.add_edge_with_label(a, b, IMPORTS)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    // CODEOWNERS (`パターン 所有者...` の行) を読み，所有者のノードから包含関係のグラフの
+    // ノードへ `OWNS` のラベル付きのエッジを張る．GitHub と同じく後の行ほど優先する．
+    // 所有者が割り当てられたノードの数を返す
+    pub fn add_codeowners<R: Read>(&mut self, reader: R) -> Result<usize, GraphError> {
+        let mut rules: Vec<(String, Vec<String>)> = Vec::new();
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| GraphError::Parse(format!("line {}: {}", i + 1, e)))?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let pattern = fields.next().unwrap().to_string();
+            rules.push((pattern, fields.map(|s| s.to_string()).collect()));
+        }
+
+        // 包含関係のグラフに現れるパス (ルートを除く) と，それがディレクトリか
+        let nodes = self.nodes_by_id();
+        let mut paths: Vec<(NodeID, bool)> = Vec::new();
+        for (&(from, to), label) in &self.labels {
+            if label == CONTAINS {
+                paths.push((to, false));
+                paths.push((from, true));
+            }
+        }
+        // 同じノードはディレクトリとしての記録を残す
+        paths.sort_unstable_by_key(|&(id, is_dir)| (id, !is_dir));
+        paths.dedup_by_key(|(id, _)| *id);
+        let paths: Vec<(String, bool)> = paths
+            .into_iter()
+            .filter(|&(id, _)| nodes[&id] != ".")
+            .map(|(id, is_dir)| (nodes[&id].clone(), is_dir))
+            .collect();
+
+        let mut owned = 0;
+        for (path, is_dir) in paths {
+            let Some((_, owners)) = rules
+                .iter()
+                .rev()
+                .find(|(p, _)| codeowners_match(p, &path, is_dir))
+            else {
+                continue;
+            };
+            for owner in owners {
+                if !self.contains_node(owner) {
+                    self.add_node(owner.clone())?;
+                }
+                self.add_edge_with_label(owner, &path, OWNS)?;
+            }
+            owned += 1;
+        }
+        Ok(owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::super::graph::Graph;
+    use super::{codeowners_match, DirectoryLoader, CONTAINS, IMPORTS, OWNS};
+
+    #[test]
+    fn test_directory_loader() {
+        let root = std::env::temp_dir().join(format!("graph_layout_{}", std::process::id()));
+        for dir in ["src/graph", "docs", ".git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "src/lib.rs",
+            "src/graph/core.rs",
+            "docs/guide.md",
+            ".git/HEAD",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let g = DirectoryLoader::new(&root)
+            .ignore(".git")
+            .extension("rs")
+            .load();
+        fs::remove_dir_all(&root).unwrap();
+        let mut g = g.unwrap();
+
+        let mut nodes: Vec<&str> = g.nodes().map(|u| u.as_str()).collect();
+        nodes.sort_unstable();
+        assert_eq!(
+            nodes,
+            vec![
+                ".",
+                "docs",
+                "src",
+                "src/graph",
+                "src/graph/core.rs",
+                "src/lib.rs"
+            ]
+        );
+        assert_eq!(g.edge_label("src", "src/lib.rs"), Some(CONTAINS));
+        assert!(!g.contains_node("docs/guide.md"));
+
+        // import のグラフを取り込む (ディレクトリをまたぐ import)
+        let mut imports = Graph::new();
+        for u in ["src/lib.rs", "src/graph/core.rs"] {
+            let _ = imports.add_node(u.to_string());
+        }
+        let _ = imports.add_edge("src/graph/core.rs", "src/lib.rs");
+        assert_eq!(g.merge_imports(&imports), Ok(1));
+        assert_eq!(
+            g.edges_with_label(IMPORTS),
+            vec![(&"src/graph/core.rs".to_string(), &"src/lib.rs".to_string())]
+        );
+
+        let owners = "# owners\n* @all\n/src/graph/ @graph-team\n*.md @docs\n";
+        assert_eq!(g.add_codeowners(owners.as_bytes()), Ok(5));
+        assert_eq!(g.edge_label("@graph-team", "src/graph/core.rs"), Some(OWNS));
+        assert!(g.contains_edge("@all", "src/lib.rs"));
+        assert!(!g.contains_edge("@all", "src/graph"));
+    }
+
+    #[test]
+    fn test_codeowners_match() {
+        assert!(codeowners_match("*.rs", "src/lib.rs", false));
+        assert!(codeowners_match("graph/", "src/graph/core.rs", false));
+        assert!(!codeowners_match("graph/", "src/graph", false));
+        assert!(codeowners_match(
+            "/src/*/core.rs",
+            "src/graph/core.rs",
+            false
+        ));
+        assert!(!codeowners_match("/graph", "src/graph/core.rs", false));
+        assert!(codeowners_match("docs", "docs/guide.md", false));
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+};
+
+/// リンク予測の指標．いずれもエッジの向きを無視した隣接ノードで計算する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkScore {
+    // |N(a) ∩ N(b)|
+    CommonNeighbors,
+    // |N(a) ∩ N(b)| / |N(a) ∪ N(b)|
+    Jaccard,
+    // Σ_{z ∈ N(a) ∩ N(b)} 1 / ln |N(z)|
+    AdamicAdar,
+    // |N(a)| * |N(b)|
+    PreferentialAttachment,
+}
+
+impl GraphCore {
+    // エッジの向きを無視した隣接ノード (自己ループは除く)
+    pub(crate) fn undirected_neighbors(&self) -> HashMap<NodeID, HashSet<NodeID>> {
+        let mut ret: HashMap<NodeID, HashSet<NodeID>> = self
+            .nodes_dict
+            .keys()
+            .map(|&id| (id, HashSet::new()))
+            .collect();
+        for (&id, n) in self.nodes_dict.iter() {
+            for &child in n.children.iter().filter(|&&child| child != id) {
+                ret.get_mut(&id).unwrap().insert(child);
+                if let Some(s) = ret.get_mut(&child) {
+                    s.insert(id);
+                }
+            }
+        }
+        ret
+    }
+
+    pub fn link_score(
+        neighbors: &HashMap<NodeID, HashSet<NodeID>>,
+        a: NodeID,
+        b: NodeID,
+        metric: LinkScore,
+    ) -> f64 {
+        let (na, nb) = (&neighbors[&a], &neighbors[&b]);
+        let common = || na.intersection(nb);
+
+        match metric {
+            LinkScore::CommonNeighbors => common().count() as f64,
+            LinkScore::Jaccard => {
+                let union = na.union(nb).count();
+                if union == 0 {
+                    0.0
+                } else {
+                    common().count() as f64 / union as f64
+                }
+            }
+            LinkScore::AdamicAdar => common()
+                .map(|z| neighbors[z].len())
+                // 共通の隣接ノードは a と b の 2 つに隣接するので次数は 2 以上
+                .map(|d| 1.0 / (d as f64).ln())
+                .sum(),
+            LinkScore::PreferentialAttachment => (na.len() * nb.len()) as f64,
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 候補のノードの組をスコアの高い順に並べて返す (同点の場合は候補の順)
+    pub fn score_pairs<Q>(
+        &self,
+        candidates: &[(&Q, &Q)],
+        metric: LinkScore,
+    ) -> Result<Vec<(&T, &T, f64)>, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let neighbors = self.core.undirected_neighbors();
+
+        let mut ret = Vec::with_capacity(candidates.len());
+        let nodes = self.nodes_by_id();
+        for &(a, b) in candidates {
+            let a_id = *self
+                .node_id(a)
+                .ok_or(GraphError::NodeNotFound { arg: "a" })?;
+            let b_id = *self
+                .node_id(b)
+                .ok_or(GraphError::NodeNotFound { arg: "b" })?;
+            ret.push((
+                nodes[&a_id],
+                nodes[&b_id],
+                GraphCore::link_score(&neighbors, a_id, b_id, metric),
+            ));
+        }
+
+        ret.sort_by(|x, y| y.2.total_cmp(&x.2));
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::LinkScore;
+
+    // A, B は C, D を共有し，E は D のみを共有する
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"A", &"D");
+        let _ = g.add_edge(&"C", &"B");
+        let _ = g.add_edge(&"D", &"B");
+        let _ = g.add_edge(&"E", &"D");
+        g
+    }
+
+    #[test]
+    fn test_score_pairs() {
+        let g = sample();
+        let candidates = [(&"A", &"E"), (&"A", &"B")];
+
+        let ranked = g
+            .score_pairs(&candidates, LinkScore::CommonNeighbors)
+            .unwrap();
+        assert_eq!(ranked, vec![(&"A", &"B", 2.0), (&"A", &"E", 1.0)]);
+
+        let ranked = g.score_pairs(&candidates, LinkScore::Jaccard).unwrap();
+        assert_eq!(ranked[0], (&"A", &"B", 1.0));
+        assert_eq!(ranked[1], (&"A", &"E", 0.5));
+
+        let ranked = g
+            .score_pairs(&candidates, LinkScore::PreferentialAttachment)
+            .unwrap();
+        assert_eq!(ranked, vec![(&"A", &"B", 4.0), (&"A", &"E", 2.0)]);
+    }
+
+    #[test]
+    fn test_score_pairs_adamic_adar() {
+        let g = sample();
+
+        let ranked = g
+            .score_pairs(&[(&"A", &"E"), (&"A", &"B")], LinkScore::AdamicAdar)
+            .unwrap();
+        let c = 1.0 / 2f64.ln();
+        let d = 1.0 / 3f64.ln();
+        assert_eq!(ranked[0].1, &"B");
+        assert!((ranked[0].2 - (c + d)).abs() < 1e-9);
+        assert!((ranked[1].2 - d).abs() < 1e-9);
+
+        assert!(g.score_pairs(&[(&"A", &"Z")], LinkScore::Jaccard).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    graph::Graph,
+    path::Path,
+    weight::Weight,
+};
+
+impl GraphCore {
+    // DAG の最長パス (トポロジカル順の動的計画法)．長さが同じならトポロジカル順で先に見つかった方．
+    // サイクルがあれば None，ノードが無ければ空の列
+    pub fn longest_path<F>(&self, weight: F) -> Option<Vec<NodeID>>
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+    {
+        // f64 の足し算は桁あふれでエラーにならない
+        self.longest_path_by(weight).map(|r| r.unwrap().0)
+    }
+
+    // 重みの型を選べる最長パスとその長さ．長さを足して桁あふれしたら，そのエッジをエラーとして返す
+    #[allow(clippy::type_complexity)]
+    pub fn longest_path_by<W, F>(
+        &self,
+        weight: F,
+    ) -> Option<Result<(Vec<NodeID>, W), (NodeID, NodeID)>>
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+    {
+        let order = self.topological_order()?;
+
+        // そのノードで終わる最長パスの長さと直前のノード
+        let mut best: HashMap<NodeID, (W, Option<NodeID>)> = HashMap::new();
+        for &id in &order {
+            let mut parents: Vec<NodeID> = self.nodes_dict[&id].parents.iter().copied().collect();
+            parents.sort_unstable();
+
+            let mut entry = (W::zero(), None);
+            for p in parents {
+                let Some(len) = best[&p].0.checked_add(weight(p, id)) else {
+                    return Some(Err((p, id)));
+                };
+                if len.cmp_weight(&entry.0) == Ordering::Greater {
+                    entry = (len, Some(p));
+                }
+            }
+            best.insert(id, entry);
+        }
+
+        let mut end = None;
+        for &id in &order {
+            if end.is_none_or(|e| best[&id].0.cmp_weight(&best[&e].0) == Ordering::Greater) {
+                end = Some(id);
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut cur = end;
+        while let Some(id) = cur {
+            path.push(id);
+            cur = best[&id].1;
+        }
+        path.reverse();
+        let len = end.map_or(W::zero(), |e| best[&e].0);
+        Some(Ok((path, len)))
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // エッジの本数が最も多いパス (重みはエッジの本数)．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn longest_path(&self) -> Result<Path<'_, T>, Vec<&T>> {
+        let Some(ids) = self.core.longest_path(|_, _| 1.0) else {
+            return Err(self.find_cycle().unwrap());
+        };
+
+        Ok(Path::new(
+            self.nodes_from_ids(&ids),
+            ids.len().saturating_sub(1) as f64,
+        ))
+    }
+
+    // 重みの和が最大のパス．負の重みのエッジは含めない方が長ければ使わない．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn longest_weighted_path(&self) -> Result<Path<'_, T>, Vec<&T>> {
+        let Some(ids) = self.core.longest_path(|a, b| self.weight_of(a, b)) else {
+            return Err(self.find_cycle().unwrap());
+        };
+
+        Ok(self.path_from_ids(&ids))
+    }
+
+    // 重みを `weight` で与える最長パス (u32 や Duration などの重みも使える)．
+    // サイクルがあれば CycleDetected，長さが桁あふれしたら WeightOverflow
+    pub fn longest_path_by<W, F>(&self, weight: F) -> Result<Path<'_, T, W>, GraphError>
+    where
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+    {
+        let nodes = self.nodes_by_id();
+        let Some(result) = self
+            .core
+            .longest_path_by(|a, b| weight(nodes[&a], nodes[&b]))
+        else {
+            return Err(GraphError::CycleDetected {
+                cycle: self.handles(&self.core.detect_cycle().unwrap()),
+            });
+        };
+
+        let (ids, len) = result.map_err(|(a, b)| GraphError::WeightOverflow {
+            from: self.handle(a),
+            to: self.handle(b),
+        })?;
+        Ok(Path::new(self.nodes_from_ids(&ids), len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_longest_path() {
+        // A → B → C → D と A → D (重み 10)
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_weighted_edge(&"A", &"D", 10.0);
+
+        let p = g.longest_path().unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"B", &"C", &"D"]);
+        assert_eq!(p.weight(), 3.0);
+
+        let p = g.longest_weighted_path().unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"D"]);
+        assert_eq!(p.weight(), 10.0);
+
+        let _ = g.add_edge(&"D", &"B");
+        let cycle = g.longest_path().unwrap_err();
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(Graph::<u32>::new().longest_path().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_longest_path_by() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+
+        let p = g.longest_path_by(|_, _| 100u8).unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"B", &"C"]);
+        assert_eq!(p.weight(), 200);
+        // 200 + 100 は u8 に収まらない
+        let _ = g.add_node("D");
+        let _ = g.add_edge(&"C", &"D");
+        assert_eq!(
+            g.longest_path_by(|_, _| 100u8),
+            Err(GraphError::WeightOverflow {
+                from: NodeHandle::new(2, 0),
+                to: NodeHandle::new(3, 0)
+            })
+        );
+
+        let _ = g.add_edge(&"D", &"A");
+        assert!(matches!(
+            g.longest_path_by(|_, _| 1u32),
+            Err(GraphError::CycleDetected { .. })
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+/// ノードと隣接リストを並べてグラフを作る．テストや例で使う
+///
+/// ```
+/// use graph_analyses::graph;
+///
+/// let g = graph! {
+///     "A" => ["B", "C"],
+///     "B" => ["C"],
+///     "D" => [],
+/// };
+/// assert!(g.contains_edge(&"A", &"C"));
+/// assert!(g.contains_node(&"D"));
+/// ```
+///
+/// 値にだけ現れるノードも追加される．ノードは現れた順に追加される (`T: Clone` が必要)
+#[macro_export]
+macro_rules! graph {
+    ($($from:expr => [$($to:expr),* $(,)?]),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut builder = $crate::graph::graph::Graph::builder();
+        $(
+            let from = $from;
+            builder = builder.node(from.clone());
+            $(
+                builder = builder.edge(from.clone(), $to);
+            )*
+        )*
+        // 重みを指定しないので build が失敗することはない
+        builder.build().unwrap()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_graph_macro() {
+        let g = crate::graph! {
+            "A" => ["B", "C"],
+            "B" => ["C",],
+            "C" => [],
+            "A" => ["D"],
+        };
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert!(g.contains_edge(&"A", &"D"));
+        assert!(g.contains_edge(&"B", &"C"));
+        assert!(!g.contains_edge(&"C", &"A"));
+
+        let empty: Graph<u32> = crate::graph! {};
+        assert_eq!(empty.node_count(), 0);
+    }
+}
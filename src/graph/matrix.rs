@@ -0,0 +1,115 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // `order` の順に並べた隣接行列．matrix[i][j] は order[i] → order[j] のエッジの有無
+    pub fn adjacency_matrix(&self, order: &[NodeID]) -> Vec<Vec<bool>> {
+        let index: HashMap<NodeID, usize> =
+            order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut matrix = vec![vec![false; order.len()]; order.len()];
+        for (i, id) in order.iter().enumerate() {
+            for child in &self.nodes_dict[id].children {
+                if let Some(&j) = index.get(child) {
+                    matrix[i][j] = true;
+                }
+            }
+        }
+        matrix
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 行列の行・列の並び (ノードを追加した順)
+    fn matrix_order(&self) -> Vec<NodeID> {
+        let mut order: Vec<NodeID> = self.id_dict.values().copied().collect();
+        order.sort_unstable();
+        order
+    }
+
+    // ノードの並びと隣接行列を返す．matrix[i][j] は nodes[i] → nodes[j] のエッジの有無
+    pub fn adjacency_matrix(&self) -> (Vec<&T>, Vec<Vec<bool>>) {
+        let order = self.matrix_order();
+        (
+            self.nodes_from_ids(&order),
+            self.core.adjacency_matrix(&order),
+        )
+    }
+
+    // 隣接行列の重み版．エッジが無い要素は 0
+    pub fn weight_matrix(&self) -> (Vec<&T>, Vec<Vec<f64>>) {
+        let order = self.matrix_order();
+        let matrix = self
+            .core
+            .adjacency_matrix(&order)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(j, e)| {
+                        if e {
+                            self.weight_of(order[i], order[j])
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        (self.nodes_from_ids(&order), matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_weighted_edge(&"B", &"C", 2.5);
+        let _ = g.add_edge(&"C", &"C");
+        g
+    }
+
+    #[test]
+    fn test_adjacency_matrix() {
+        let g = sample();
+        let (nodes, matrix) = g.adjacency_matrix();
+
+        assert_eq!(nodes, vec![&"A", &"B", &"C"]);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![false, true, false],
+                vec![false, false, true],
+                vec![false, false, true],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weight_matrix() {
+        let g = sample();
+        let (nodes, matrix) = g.weight_matrix();
+
+        assert_eq!(nodes, vec![&"A", &"B", &"C"]);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 2.5],
+                vec![0.0, 0.0, 1.0],
+            ]
+        );
+    }
+}
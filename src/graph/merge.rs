@@ -0,0 +1,377 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use super::{
+    core::NodeID, error::GraphError, graph::Graph, handle::NodeHandle, self_loop::SelfLoopPolicy,
+};
+
+/// 両方のグラフにあるエッジの重みをどう決めるか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    // 取り込む側 (self) の重みを残す
+    KeepFirst,
+    // 取り込まれる側 (other) の重みで上書きする
+    KeepLast,
+    // 重みを足し合わせる
+    CombineWeights,
+    // 重みが食い違うエッジがあればエラーにする (グラフは変更しない)
+    Fail,
+}
+
+/// 両方のグラフにあったエッジ
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeCollision<T> {
+    pub from: T,
+    pub to: T,
+    pub existing: f64,
+    pub incoming: f64,
+    pub resolved: f64,
+}
+
+/// `merge_with` の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeReport<T> {
+    pub added_nodes: usize,
+    pub added_edges: usize,
+    pub node_collisions: Vec<T>,
+    // 取り込めなかった別名 (self で別のノードかその別名になっているもの)
+    pub alias_collisions: Vec<T>,
+    pub edge_collisions: Vec<EdgeCollision<T>>,
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> Graph<T> {
+    // `other` のノードとエッジを取り込む．両方にあるエッジの重みは `policy` に従って決め，
+    // 衝突したノードとエッジを報告する．エッジの出所は両方のものが残り，多重グラフでは
+    // 平行なエッジの本数を足し合わせる．別名は取り込んだノードを指すように付け直し，
+    // self で既に別のノードを指している名前は self のものを残して報告する．
+    // 取り込むと self_loop_policy で禁止された自己ループになるエッジがあれば SelfLoopForbidden
+    // (まだ無いノードなら取り込んだときのハンドル)．エラーならグラフは変更しない
+    pub fn merge_with(
+        &mut self,
+        other: Graph<T>,
+        policy: MergePolicy,
+    ) -> Result<MergeReport<T>, GraphError> {
+        // other のノードを self のどの NodeID にするかを先に決める．
+        // 新しいノードの NodeID は add_node と同じ順 (削除で空いたものから) に割り当てる
+        let mut keys: Vec<(&T, NodeID)> = other.id_dict.iter().map(|(u, &id)| (u, id)).collect();
+        keys.sort_unstable_by_key(|(_, id)| *id);
+        let mut free_ids = self.free_ids.clone();
+        let mut next_id = self.id_counter;
+        let mut remap: HashMap<NodeID, NodeID> = HashMap::new();
+        for &(u, other_id) in &keys {
+            let id = match self.node_id(u) {
+                Some(&id) => id,
+                None => free_ids.pop().unwrap_or_else(|| {
+                    next_id += 1;
+                    next_id - 1
+                }),
+            };
+            remap.insert(other_id, id);
+        }
+
+        // 先に衝突と自己ループを調べ，エラーならグラフを変更する前に返す．
+        // 別名で同じノードに対応する 2 ノード間のエッジも自己ループになる
+        let other_nodes = other.nodes_by_id();
+        let mut edge_collisions = Vec::new();
+        for (&id, n) in other.core.nodes_dict.iter() {
+            let from = remap[&id];
+            for &child in &n.children {
+                let to = remap[&child];
+                if from == to && self.self_loop_policy == SelfLoopPolicy::Forbid {
+                    let generation = self.generations.get(from).copied().unwrap_or(0);
+                    return Err(GraphError::SelfLoopForbidden {
+                        node: NodeHandle::new(from, generation),
+                    });
+                }
+                let exists = self
+                    .core
+                    .nodes_dict
+                    .get(&from)
+                    .is_some_and(|n| n.children.contains(&to));
+                if !exists {
+                    continue;
+                }
+
+                let existing = self.weight_of(from, to);
+                let incoming = other.weight_of(id, child);
+                let resolved = match policy {
+                    MergePolicy::KeepFirst => existing,
+                    MergePolicy::KeepLast => incoming,
+                    MergePolicy::CombineWeights => existing + incoming,
+                    MergePolicy::Fail if existing != incoming => {
+                        return Err(GraphError::ConflictingWeights {
+                            from: self.handle(from),
+                            to: self.handle(to),
+                            existing,
+                            incoming,
+                        })
+                    }
+                    MergePolicy::Fail => existing,
+                };
+                edge_collisions.push(EdgeCollision {
+                    from: other_nodes[&id].clone(),
+                    to: other_nodes[&child].clone(),
+                    existing,
+                    incoming,
+                    resolved,
+                });
+            }
+        }
+        drop(other_nodes);
+        drop(keys);
+
+        let Graph {
+            id_dict,
+            aliases,
+            core,
+            weights,
+            provenance,
+            labels,
+            multiplicity,
+            ..
+        } = other;
+
+        let mut node_collisions = Vec::new();
+        let mut added_nodes = 0;
+        let mut keys: Vec<(T, NodeID)> = id_dict.into_iter().collect();
+        keys.sort_unstable_by_key(|(_, id)| *id);
+        for (u, other_id) in keys {
+            if self.node_id(&u).is_some() {
+                node_collisions.push(u);
+            } else {
+                let h = self.add_node(u)?;
+                debug_assert_eq!(h.index(), remap[&other_id]);
+                added_nodes += 1;
+            }
+        }
+
+        let mut added_edges = 0;
+        for (&other_id, n) in core.nodes_dict.iter() {
+            let from = remap[&other_id];
+            for child in &n.children {
+                let to = remap[child];
+                if !self.add_edge_by_id(from, to)? {
+                    added_edges += 1;
+                    if let Some(&w) = weights.get(&(other_id, *child)) {
+                        self.weights.insert((from, to), w);
+                    }
+                }
+                // 多重グラフなら残りの平行なエッジも足す
+                for _ in 1..multiplicity.get(&(other_id, *child)).copied().unwrap_or(1) {
+                    self.add_edge_by_id(from, to)?;
+                }
+            }
+        }
+        for c in &edge_collisions {
+            let key = (
+                *self.node_id(&c.from).unwrap(),
+                *self.node_id(&c.to).unwrap(),
+            );
+            if c.resolved != self.weight_of(key.0, key.1) {
+                self.weights.insert(key, c.resolved);
+            }
+        }
+        // ラベルは重みと同じく KeepLast のときだけ上書きする
+        for ((a, b), label) in labels {
+            let key = (remap[&a], remap[&b]);
+            if policy == MergePolicy::KeepLast || !self.labels.contains_key(&key) {
+                self.labels.insert(key, label);
+            }
+        }
+        for ((a, b), ps) in provenance {
+            self.provenance
+                .entry((remap[&a], remap[&b]))
+                .or_default()
+                .extend(ps);
+        }
+
+        let mut alias_collisions = Vec::new();
+        let mut aliases: Vec<(T, NodeID)> = aliases.into_iter().collect();
+        aliases.sort_unstable_by_key(|(_, id)| *id);
+        for (alias, other_id) in aliases {
+            let id = remap[&other_id];
+            match self.node_id(&alias) {
+                Some(&node) if node == id => {}
+                Some(_) => alias_collisions.push(alias),
+                None => {
+                    self.aliases.insert(alias, id);
+                }
+            }
+        }
+
+        Ok(MergeReport {
+            added_nodes,
+            added_edges,
+            node_collisions,
+            alias_collisions,
+            edge_collisions,
+        })
+    }
+
+    // `other` のノードとエッジを和集合として取り込む．両方にあるエッジの重みとラベルは
+    // `other` のもので上書きする (merge_with を KeepLast で呼ぶのと同じ)
+    pub fn merge(&mut self, other: Graph<T>) -> Result<MergeReport<T>, GraphError> {
+        self.merge_with(other, MergePolicy::KeepLast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        error::GraphError, graph::Graph, handle::NodeHandle, provenance::Provenance,
+        self_loop::SelfLoopPolicy,
+    };
+    use super::MergePolicy;
+
+    fn sources() -> (Graph<&'static str>, Graph<&'static str>) {
+        let mut a = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = a.add_node(u);
+        }
+        let _ = a.add_weighted_edge(&"A", &"B", 1.0);
+        let _ = a.add_weighted_edge(&"B", &"C", 2.0);
+
+        let mut b = Graph::new();
+        for u in ["D", "B", "C"] {
+            let _ = b.add_node(u);
+        }
+        let _ = b.add_weighted_edge(&"B", &"C", 5.0);
+        let _ = b.add_edge_with_provenance(&"C", &"D", Provenance::new("b.txt", 1, "import"));
+        (a, b)
+    }
+
+    #[test]
+    fn test_merge_with_policies() {
+        for (policy, expected) in [
+            (MergePolicy::KeepFirst, 2.0),
+            (MergePolicy::KeepLast, 5.0),
+            (MergePolicy::CombineWeights, 7.0),
+        ] {
+            let (mut a, b) = sources();
+            let report = a.merge_with(b, policy).unwrap();
+
+            assert_eq!(report.added_nodes, 1);
+            assert_eq!(report.added_edges, 1);
+            assert_eq!(report.node_collisions, vec!["B", "C"]);
+            assert_eq!(report.edge_collisions.len(), 1);
+            assert_eq!(report.edge_collisions[0].existing, 2.0);
+            assert_eq!(report.edge_collisions[0].incoming, 5.0);
+            assert_eq!(report.edge_collisions[0].resolved, expected);
+
+            assert_eq!(a.edge_weight(&"B", &"C"), Some(expected));
+            assert_eq!(a.edge_weight(&"C", &"D"), Some(1.0));
+            assert_eq!(a.provenance(&"C", &"D").len(), 1);
+            assert_eq!(a.shortest_path(&"A", &"D").unwrap().len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let (mut a, b) = sources();
+        let report = a.merge(b).unwrap();
+
+        assert_eq!(report.added_nodes, 1);
+        assert_eq!(a.node_count(), 4);
+        assert!(a.contains_edge(&"A", &"B"));
+        assert_eq!(a.edge_weight(&"B", &"C"), Some(5.0));
+        assert_eq!(a.edges().count(), report.added_edges + 2);
+    }
+
+    #[test]
+    fn test_merge_with_fail() {
+        let (mut a, b) = sources();
+        assert!(matches!(
+            a.merge_with(b, MergePolicy::Fail),
+            Err(GraphError::ConflictingWeights { existing, incoming, .. })
+                if existing == 2.0 && incoming == 5.0
+        ));
+        // 失敗した場合は変更されない
+        assert!(a.add_node("D").is_ok());
+
+        // 重みが一致していれば衝突しても失敗しない
+        let (mut a, _) = sources();
+        let mut c = Graph::new();
+        let _ = c.add_node("A");
+        let _ = c.add_node("B");
+        let _ = c.add_edge(&"A", &"B");
+        let report = a.merge_with(c, MergePolicy::Fail).unwrap();
+        assert_eq!(report.edge_collisions.len(), 1);
+        assert_eq!(report.added_nodes, 0);
+    }
+
+    #[test]
+    fn test_merge_self_loop_forbidden() {
+        let (mut a, _) = sources();
+        let _ = a.set_self_loop_policy(SelfLoopPolicy::Forbid);
+        let mut b = Graph::new();
+        for u in ["C", "X"] {
+            let _ = b.add_node(u);
+        }
+        let _ = b.add_edge(&"C", &"X");
+        let _ = b.add_edge(&"X", &"X");
+        // X は取り込んだときのハンドルで報告するが，ノードは追加しない
+        assert_eq!(
+            a.merge(b),
+            Err(GraphError::SelfLoopForbidden {
+                node: NodeHandle::new(3, 0)
+            })
+        );
+        assert_eq!(a.node_count(), 3);
+        assert!(!a.contains_node(&"X"));
+        assert!(a.self_loops().is_empty());
+        assert!(!a.contains_edge(&"C", &"X"));
+
+        // 別名で同じノードになる 2 ノード間のエッジも自己ループ
+        let mut a = Graph::new();
+        let _ = a.add_node("x".to_string());
+        let _ = a.alias("x", "X");
+        let _ = a.set_self_loop_policy(SelfLoopPolicy::Forbid);
+        let mut b = Graph::new();
+        for u in ["x", "X"] {
+            let _ = b.add_node(u.to_string());
+        }
+        let _ = b.add_edge("X", "x");
+        assert_eq!(
+            a.merge(b),
+            Err(GraphError::SelfLoopForbidden {
+                node: NodeHandle::new(0, 0)
+            })
+        );
+        assert_eq!(a.node_count(), 1);
+        assert_eq!(a.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_merge_aliases() {
+        let (mut a, mut b) = sources();
+        let _ = a.alias(&"A", &"a");
+        let _ = b.alias(&"D", &"d");
+        let _ = b.alias(&"C", &"c");
+        // a は self で A の別名なので取り込まない
+        let _ = b.alias(&"B", &"a");
+        let report = a.merge(b).unwrap();
+
+        assert_eq!(report.alias_collisions, vec!["a"]);
+        assert_eq!(a.canonical(&"a"), Some(&"A"));
+        assert_eq!(a.canonical(&"c"), Some(&"C"));
+        // 新しいノードの別名は取り込んだノードを指す
+        assert_eq!(a.canonical(&"d"), Some(&"D"));
+        assert!(a.contains_edge(&"c", &"d"));
+    }
+
+    #[test]
+    fn test_merge_multigraph() {
+        let mut a = Graph::new_multigraph();
+        let mut b = Graph::new_multigraph();
+        for g in [&mut a, &mut b] {
+            let _ = g.add_node("A");
+            let _ = g.add_node("B");
+            let _ = g.add_edge(&"A", &"B");
+        }
+        let _ = b.add_edge(&"A", &"B");
+
+        let report = a.merge(b).unwrap();
+        assert_eq!(report.added_edges, 0);
+        assert_eq!(a.edge_multiplicity(&"A", &"B"), 3);
+        assert_eq!(a.out_degree(&"A"), Some(3));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{borrow::Borrow, hash::Hash};
+
+use super::{core::NodeID, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 平行なエッジを数える多重グラフ．同じエッジを add_edge するたびに本数が増え，
+    // remove_edge では 1 本ずつ減る．重み・ラベル・出所はエッジ (from, to) ごとに 1 つのまま
+    pub fn new_multigraph() -> Self {
+        Self {
+            multigraph: true,
+            ..Self::new()
+        }
+    }
+
+    pub fn is_multigraph(&self) -> bool {
+        self.multigraph
+    }
+
+    // エッジ from → to の本数 (多重グラフでなければ 0 か 1)
+    pub fn edge_multiplicity<Q>(&self, u_from: &Q, u_to: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.node_id(u_from), self.node_id(u_to)) {
+            (Some(&a), Some(&b)) if self.core.nodes_dict[&a].children.contains(&b) => {
+                self.multiplicity_of(a, b)
+            }
+            _ => 0,
+        }
+    }
+
+    // 存在するエッジの本数
+    pub(super) fn multiplicity_of(&self, from_id: NodeID, to_id: NodeID) -> usize {
+        self.multiplicity
+            .get(&(from_id, to_id))
+            .copied()
+            .unwrap_or(1)
+    }
+
+    // 平行なエッジをまとめた (from, to, 本数) をノードの追加順に
+    pub fn edges_with_multiplicity(&self) -> Vec<(&T, &T, usize)> {
+        let nodes = self.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .collect();
+        edges.sort_unstable();
+        edges
+            .into_iter()
+            .map(|(a, b)| (nodes[&a], nodes[&b], self.multiplicity_of(a, b)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_multigraph() {
+        let mut g = Graph::new_multigraph();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        assert_eq!(g.add_edge(&"A", &"B"), Ok(false));
+        assert_eq!(g.add_edge(&"A", &"B"), Ok(true));
+        let _ = g.add_weighted_edge(&"A", &"B", 2.0);
+        let _ = g.add_edge(&"B", &"C");
+
+        assert!(g.is_multigraph());
+        assert_eq!(g.edge_multiplicity(&"A", &"B"), 3);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.edges().filter(|&(a, _)| *a == "A").count(), 3);
+        assert_eq!(
+            g.edges_with_multiplicity(),
+            vec![(&"A", &"B", 3), (&"B", &"C", 1)]
+        );
+
+        // 1 本ずつ削除され，重みは最後の 1 本まで残る
+        assert_eq!(g.remove_edge(&"A", &"B"), Ok(true));
+        assert_eq!(g.edge_multiplicity(&"A", &"B"), 2);
+        assert_eq!(g.edge_weight(&"A", &"B"), Some(2.0));
+        let _ = g.remove_edge(&"A", &"B");
+        let _ = g.remove_edge(&"A", &"B");
+        assert_eq!(g.edge_multiplicity(&"A", &"B"), 0);
+        assert_eq!(g.edge_count(), 1);
+
+        // 通常のグラフでは平行なエッジは 1 本にまとめられる
+        let mut simple = Graph::new();
+        let _ = simple.add_node("A");
+        let _ = simple.add_edge(&"A", &"A");
+        let _ = simple.add_edge(&"A", &"A");
+        assert_eq!(simple.edge_multiplicity(&"A", &"A"), 1);
+        assert_eq!(simple.edge_count(), 1);
+    }
+}
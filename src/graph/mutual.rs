@@ -0,0 +1,143 @@
+use std::hash::Hash;
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // 両方向のエッジがあるノードの組 (ID の小さい方が先)．自己ループは含まない
+    pub fn mutual_edges(&self) -> Vec<(NodeID, NodeID)> {
+        let mut ret: Vec<(NodeID, NodeID)> = self
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| {
+                n.children
+                    .iter()
+                    .filter(move |&&child| id < child)
+                    .filter(move |&&child| {
+                        self.nodes_dict
+                            .get(&child)
+                            .is_some_and(|c| c.children.contains(&id))
+                    })
+                    .map(move |&child| (id, child))
+            })
+            .collect();
+
+        ret.sort_unstable();
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // A ⇄ B となっている組をすべて返す (各組は一度だけ)
+    pub fn mutual_edges(&self) -> Vec<(&T, &T)> {
+        let nodes = self.nodes_by_id();
+
+        self.core
+            .mutual_edges()
+            .into_iter()
+            .map(|(a, b)| (nodes[&a], nodes[&b]))
+            .collect()
+    }
+
+    // すべてのエッジに逆向きのエッジを (同じ重みで) 追加する．多重グラフでは平行なエッジの
+    // 本数も写す．追加したエッジの数を返す
+    pub fn symmetrize(&mut self) -> usize {
+        let edges: Vec<(NodeID, NodeID)> = self
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .collect();
+
+        let mut added = 0;
+        for (a, b) in edges {
+            if let Ok(false) = self.add_edge_by_id(b, a) {
+                if let Some(&w) = self.weights.get(&(a, b)) {
+                    self.weights.insert((b, a), w);
+                }
+                added += 1;
+                for _ in 1..self.multiplicity_of(a, b) {
+                    let _ = self.add_edge_by_id(b, a);
+                    added += 1;
+                }
+            }
+        }
+
+        added
+    }
+
+    // A ⇄ B となっている組の両方向のエッジを削除する．削除した組の数を返す
+    pub fn remove_mutual(&mut self) -> usize {
+        let pairs = self.core.mutual_edges();
+
+        for &(a, b) in &pairs {
+            self.remove_edge_by_id(a, b);
+            self.remove_edge_by_id(b, a);
+        }
+
+        pairs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"C", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_weighted_edge(&"C", &"D", 2.0);
+        let _ = g.add_edge(&"D", &"D");
+        g
+    }
+
+    #[test]
+    fn test_mutual_edges() {
+        let g = sample();
+        assert_eq!(g.mutual_edges(), vec![(&"A", &"B"), (&"B", &"C")]);
+    }
+
+    #[test]
+    fn test_symmetrize() {
+        let mut g = sample();
+
+        assert_eq!(g.symmetrize(), 1);
+        assert_eq!(g.edge_weight(&"D", &"C"), Some(2.0));
+        assert_eq!(g.mutual_edges().len(), 3);
+        assert_eq!(g.symmetrize(), 0);
+    }
+
+    #[test]
+    fn test_remove_mutual() {
+        let mut g = sample();
+
+        assert_eq!(g.remove_mutual(), 2);
+        assert!(g.mutual_edges().is_empty());
+        assert_eq!(g.edge_weight(&"A", &"B"), None);
+        assert_eq!(g.edge_weight(&"C", &"B"), None);
+        assert_eq!(g.edge_weight(&"C", &"D"), Some(2.0));
+        assert_eq!(g.edge_weight(&"D", &"D"), Some(1.0));
+    }
+
+    #[test]
+    fn test_symmetrize_multigraph() {
+        let mut g = Graph::new_multigraph();
+        for u in ["A", "B"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"A", &"B");
+
+        assert_eq!(g.symmetrize(), 2);
+        assert_eq!(g.edge_multiplicity(&"B", &"A"), 2);
+        assert_eq!(g.edge_count(), 4);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, handle::NodeHandle};
+
+/// ノードのキーとは別にデータを持たせたグラフ
+///
+/// キーはハッシュできる必要があるが，データ `D` は任意の型でよい
+/// (比較もハッシュもされず，書き換えてもグラフの構造に影響しない)
+pub struct DataGraph<T: PartialEq + Eq + Hash, D> {
+    graph: Graph<T>,
+    data: HashMap<NodeID, D>,
+}
+
+impl<T: PartialEq + Eq + Hash, D> Default for DataGraph<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash, D> DataGraph<T, D> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            data: HashMap::new(),
+        }
+    }
+
+    // データを除いたグラフ
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    // データ `data` を持つノードを追加する
+    pub fn add_node_with_data(&mut self, key: T, data: D) -> Result<NodeHandle, GraphError> {
+        let h = self.graph.add_node(key)?;
+        self.data.insert(h.index(), data);
+        Ok(h)
+    }
+
+    pub fn add_edge<Q>(&mut self, u_from: &Q, u_to: &Q) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.add_edge(u_from, u_to)
+    }
+
+    pub fn add_weighted_edge<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        weight: f64,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.add_weighted_edge(u_from, u_to, weight)
+    }
+
+    pub fn node_data<Q>(&self, key: &Q) -> Option<&D>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.graph.node_id(key)?;
+        self.data.get(id)
+    }
+
+    pub fn node_data_mut<Q>(&mut self, key: &Q) -> Option<&mut D>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.graph.node_id(key)?;
+        self.data.get_mut(id)
+    }
+
+    // (キー, データ) をノードの追加順に
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &D)> {
+        let nodes = self.graph.nodes_by_id();
+        let mut ids: Vec<NodeID> = self.data.keys().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter().map(move |id| (nodes[&id], &self.data[&id]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataGraph;
+
+    // ハッシュできないデータ
+    #[derive(Debug)]
+    struct Package {
+        version: (u32, u32),
+        size: f64,
+    }
+
+    #[test]
+    fn test_node_data() {
+        let mut g = DataGraph::new();
+        let _ = g.add_node_with_data(
+            "app",
+            Package {
+                version: (1, 0),
+                size: 2.5,
+            },
+        );
+        let _ = g.add_node_with_data(
+            "lib",
+            Package {
+                version: (0, 3),
+                size: 0.5,
+            },
+        );
+        let _ = g.add_edge(&"app", &"lib");
+        assert!(g
+            .add_node_with_data(
+                "lib",
+                Package {
+                    version: (0, 4),
+                    size: 0.0
+                }
+            )
+            .is_err());
+
+        assert_eq!(g.node_data(&"lib").unwrap().version, (0, 3));
+        g.node_data_mut(&"lib").unwrap().version.1 += 1;
+        assert_eq!(g.node_data(&"lib").unwrap().version, (0, 4));
+        assert!(g.node_data(&"x").is_none());
+
+        let total: f64 = g.iter().map(|(_, p)| p.size).sum();
+        assert_eq!(total, 3.0);
+        assert_eq!(g.iter().next().unwrap().0, &"app");
+        assert!(g.graph().contains_edge(&"app", &"lib"));
+    }
+}
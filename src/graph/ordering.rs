@@ -0,0 +1,193 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // 次数が最小のノードを順に取り除いていく順序 (向きは無視) と縮退度．
+    // 次数が同じなら ID の小さい方を先に取り除く
+    pub fn degeneracy_ordering(&self) -> (Vec<NodeID>, usize) {
+        let neighbors = self.undirected_neighbors();
+        let mut degree: HashMap<NodeID, usize> =
+            neighbors.iter().map(|(&id, ns)| (id, ns.len())).collect();
+
+        // 次数ごとのバケツ (ID の小さいものを先に取り出せるよう BTreeSet)
+        let max_degree = degree.values().copied().max().unwrap_or(0);
+        let mut buckets = vec![BTreeSet::new(); max_degree + 1];
+        for (&id, &d) in &degree {
+            buckets[d].insert(id);
+        }
+
+        let mut order = Vec::with_capacity(neighbors.len());
+        let mut removed = HashSet::new();
+        let mut degeneracy = 0;
+        let mut d: usize = 0;
+        while order.len() < neighbors.len() {
+            // 取り除くと隣の次数が 1 減るので，1 つ下のバケツから探し直せば足りる
+            d = d.saturating_sub(1);
+            while buckets[d].is_empty() {
+                d += 1;
+            }
+            let id = buckets[d].pop_first().unwrap();
+            degeneracy = degeneracy.max(d);
+            removed.insert(id);
+            order.push(id);
+
+            for &x in &neighbors[&id] {
+                if removed.contains(&x) {
+                    continue;
+                }
+                let dx = degree.get_mut(&x).unwrap();
+                buckets[*dx].remove(&x);
+                *dx -= 1;
+                buckets[*dx].insert(x);
+            }
+        }
+
+        (order, degeneracy)
+    }
+
+    // 逆 Cuthill–McKee 順序 (向きは無視)．連結成分ごとに次数最小のノードから幅優先でたどり，
+    // 隣接ノードは次数の小さい順に加え，最後に全体を逆順にする
+    pub fn reverse_cuthill_mckee(&self) -> Vec<NodeID> {
+        let neighbors = self.undirected_neighbors();
+        let key = |id: &NodeID| (neighbors[id].len(), *id);
+
+        let mut starts: Vec<NodeID> = neighbors.keys().copied().collect();
+        starts.sort_unstable_by_key(key);
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::with_capacity(starts.len());
+        for start in starts {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut queue = VecDeque::from([start]);
+            while let Some(id) = queue.pop_front() {
+                order.push(id);
+
+                let mut next: Vec<NodeID> = neighbors[&id]
+                    .iter()
+                    .copied()
+                    .filter(|x| !visited.contains(x))
+                    .collect();
+                next.sort_unstable_by_key(key);
+                for x in next {
+                    visited.insert(x);
+                    queue.push_back(x);
+                }
+            }
+        }
+
+        order.reverse();
+        order
+    }
+
+    // `order` で並べた隣接行列のバンド幅 (エッジの両端の位置の差の最大値，向きは無視)
+    pub fn bandwidth(&self, order: &[NodeID]) -> usize {
+        let index: HashMap<NodeID, usize> =
+            order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut ret = 0;
+        for (&id, n) in &self.nodes_dict {
+            for child in &n.children {
+                if let (Some(&i), Some(&j)) = (index.get(&id), index.get(child)) {
+                    ret = ret.max(i.abs_diff(j));
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 縮退順序とグラフの縮退度．この順序で後ろにある隣接ノードは縮退度以下なので，
+    // クリークの列挙 (Bron–Kerbosch など) の外側のループに使うと探索が小さくなる
+    pub fn degeneracy_ordering(&self) -> (Vec<&T>, usize) {
+        let (order, degeneracy) = self.core.degeneracy_ordering();
+        (self.nodes_from_ids(&order), degeneracy)
+    }
+
+    // 隣接行列のバンド幅を小さくするノードの並び (逆 Cuthill–McKee 法)
+    pub fn reverse_cuthill_mckee(&self) -> Vec<&T> {
+        self.nodes_from_ids(&self.core.reverse_cuthill_mckee())
+    }
+
+    // 逆 Cuthill–McKee 順で並べた隣接行列 (非零要素が対角線の近くに集まる)
+    pub fn adjacency_matrix_rcm(&self) -> (Vec<&T>, Vec<Vec<bool>>) {
+        let order = self.core.reverse_cuthill_mckee();
+        (
+            self.nodes_from_ids(&order),
+            self.core.adjacency_matrix(&order),
+        )
+    }
+
+    // `nodes` の順で並べた隣接行列のバンド幅．`nodes` に無いノードのエッジは数えない
+    pub fn bandwidth<Q>(&self, nodes: &[&Q]) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let order: Vec<NodeID> = nodes
+            .iter()
+            .filter_map(|u| self.node_id(*u).copied())
+            .collect();
+        self.core.bandwidth(&order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_degeneracy_ordering() {
+        // 三角形 A, B, C に D と E がぶら下がる
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "B"), ("B", "C"), ("C", "A"), ("C", "D"), ("D", "E")] {
+            let _ = g.add_edge(&a, &b);
+        }
+
+        let (order, degeneracy) = g.degeneracy_ordering();
+        assert_eq!(degeneracy, 2);
+        assert_eq!(order, vec![&"E", &"D", &"A", &"B", &"C"]);
+        assert_eq!(Graph::<u32>::new().degeneracy_ordering(), (vec![], 0));
+    }
+
+    #[test]
+    fn test_reverse_cuthill_mckee() {
+        // 0 - 1 - 2 - ... - 9 のパスを，隣どうしが離れるような ID で作る
+        let mut g = Graph::new();
+        let labels = [0, 5, 1, 6, 2, 7, 3, 8, 4, 9];
+        for u in 0..10 {
+            let _ = g.add_node(u);
+        }
+        for w in labels.windows(2) {
+            let _ = g.add_edge(&w[0], &w[1]);
+        }
+
+        let added: Vec<i32> = (0..10).collect();
+        assert_eq!(g.bandwidth(&added.iter().collect::<Vec<_>>()), 5);
+
+        let order = g.reverse_cuthill_mckee();
+        assert_eq!(order.len(), 10);
+        assert_eq!(g.bandwidth(&order), 1);
+
+        let (nodes, matrix) = g.adjacency_matrix_rcm();
+        assert_eq!(nodes, order);
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, &e) in row.iter().enumerate() {
+                assert!(!e || i.abs_diff(j) <= 1);
+            }
+        }
+    }
+}
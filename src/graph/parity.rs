@@ -0,0 +1,217 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // エッジの向きを無視した 2 彩色 (`removed` のノードは無いものとする)．
+    // 各連結成分の ID 最小のノードを false にする．彩色できなければ奇サイクル (閉じた列) を返す
+    pub fn two_coloring(
+        &self,
+        removed: &HashSet<NodeID>,
+    ) -> Result<HashMap<NodeID, bool>, Vec<NodeID>> {
+        let mut ids: Vec<NodeID> = self
+            .nodes_dict
+            .keys()
+            .copied()
+            .filter(|id| !removed.contains(id))
+            .collect();
+        ids.sort_unstable();
+
+        // 自己ループは長さ 1 の奇サイクル
+        if let Some(&id) = ids
+            .iter()
+            .find(|id| self.nodes_dict[id].children.contains(id))
+        {
+            return Err(vec![id, id]);
+        }
+
+        let neighbors = self.undirected_neighbors();
+        let mut color: HashMap<NodeID, bool> = HashMap::new();
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        for &root in &ids {
+            if color.contains_key(&root) {
+                continue;
+            }
+            color.insert(root, false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(u) = queue.pop_front() {
+                let mut next: Vec<NodeID> = neighbors[&u]
+                    .iter()
+                    .copied()
+                    .filter(|v| !removed.contains(v))
+                    .collect();
+                next.sort_unstable();
+                for v in next {
+                    match color.get(&v) {
+                        None => {
+                            color.insert(v, !color[&u]);
+                            parent.insert(v, u);
+                            queue.push_back(v);
+                        }
+                        Some(&c) if c == color[&u] => return Err(odd_cycle(&parent, u, v)),
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(color)
+    }
+}
+
+// BFS 木の上で同じ色の u, v を結ぶエッジから奇サイクル u → ... → lca → ... → v → u を作る
+fn odd_cycle(parent: &HashMap<NodeID, NodeID>, u: NodeID, v: NodeID) -> Vec<NodeID> {
+    let to_root = |mut x: NodeID| {
+        let mut path = vec![x];
+        while let Some(&p) = parent.get(&x) {
+            path.push(p);
+            x = p;
+        }
+        path
+    };
+    let up = to_root(u);
+    let down = to_root(v);
+    let on_up: HashSet<NodeID> = up.iter().copied().collect();
+    let lca_pos = down.iter().position(|x| on_up.contains(x)).unwrap();
+    let lca = down[lca_pos];
+
+    let mut cycle: Vec<NodeID> = up.iter().copied().take_while(|&x| x != lca).collect();
+    cycle.push(lca);
+    cycle.extend(down[..lca_pos].iter().rev());
+    cycle.push(u);
+    cycle
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // エッジの向きを無視してノードを 2 つに分ける (同じ側のノード同士にはエッジが無い)．
+    // 各側はノードの追加順．二部グラフでなければ奇サイクルをエラーとして返す
+    pub fn bipartition(&self) -> Result<(Vec<&T>, Vec<&T>), Vec<&T>> {
+        let color = self
+            .core
+            .two_coloring(&HashSet::new())
+            .map_err(|cycle| self.nodes_from_ids(&cycle))?;
+
+        let mut ids: Vec<NodeID> = color.keys().copied().collect();
+        ids.sort_unstable();
+        let (left, right): (Vec<NodeID>, Vec<NodeID>) = ids.into_iter().partition(|id| !color[id]);
+        Ok((self.nodes_from_ids(&left), self.nodes_from_ids(&right)))
+    }
+
+    pub fn is_bipartite(&self) -> bool {
+        self.core.two_coloring(&HashSet::new()).is_ok()
+    }
+
+    // 二重被覆 (テンソル積 G × K2)．ノード u を (u, false) と (u, true) に分け，
+    // エッジ u → v を (u, false) → (v, true) と (u, true) → (v, false) にする (重みは元のまま)．
+    // 結果は常に二部グラフで，元のグラフが二部グラフなら 2 つの複製に分かれる
+    pub fn bipartite_double_cover(&self) -> Graph<(&T, bool)> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut g = Graph::new();
+        for side in [false, true] {
+            for id in &ids {
+                let _ = g.add_node((nodes[id], side));
+            }
+        }
+        for id in &ids {
+            for child in &self.core.nodes_dict[id].children {
+                let w = self.weight_of(*id, *child);
+                for side in [false, true] {
+                    let _ = g.add_weighted_edge(&(nodes[id], side), &(nodes[child], !side), w);
+                }
+            }
+        }
+        g
+    }
+
+    // 取り除くと二部グラフになるノードの集合 (奇サイクル横断) を貪欲法で求める．
+    // 奇サイクルが見つかるたびに，その上で残っている次数が最大のノードを取り除く．
+    // 最小とは限らない．取り除いた順に返す
+    pub fn odd_cycle_transversal(&self) -> Vec<&T> {
+        let neighbors = self.core.undirected_neighbors();
+        let mut removed: HashSet<NodeID> = HashSet::new();
+        let mut ret = Vec::new();
+
+        while let Err(cycle) = self.core.two_coloring(&removed) {
+            let degree = |id: &NodeID| {
+                neighbors[id]
+                    .iter()
+                    .filter(|v| !removed.contains(v))
+                    .count()
+            };
+            let &victim = cycle[..cycle.len() - 1]
+                .iter()
+                .max_by(|a, b| degree(a).cmp(&degree(b)).then(b.cmp(a)))
+                .unwrap();
+            removed.insert(victim);
+            ret.push(victim);
+        }
+
+        self.nodes_from_ids(&ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_bipartition() {
+        // 4 サイクル A - B - C - D - A
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "B"), ("C", "B"), ("C", "D"), ("D", "A")] {
+            let _ = g.add_edge(&a, &b);
+        }
+        assert_eq!(g.bipartition(), Ok((vec![&"A", &"C"], vec![&"B", &"D"])));
+        assert!(g.odd_cycle_transversal().is_empty());
+
+        // 三角形 A - B - E を作る
+        let _ = g.add_node("E");
+        let _ = g.add_edge(&"B", &"E");
+        let _ = g.add_edge(&"E", &"A");
+        let cycle = g.bipartition().unwrap_err();
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(!g.is_bipartite());
+
+        // A と B はどちらも三角形と 4 サイクルにまたがり，次数が最大
+        let removed = g.odd_cycle_transversal();
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0] == &"A" || removed[0] == &"B");
+    }
+
+    #[test]
+    fn test_bipartite_double_cover() {
+        // 三角形の二重被覆は 6 サイクル
+        let mut g = Graph::new();
+        for u in [1, 2, 3] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&1, &2);
+        let _ = g.add_edge(&2, &3);
+        let _ = g.add_weighted_edge(&3, &1, 2.0);
+
+        let cover = g.bipartite_double_cover();
+        assert_eq!(cover.node_count(), 6);
+        assert_eq!(cover.edge_count(), 6);
+        assert!(cover.is_bipartite());
+        assert_eq!(cover.edge_weight(&(&3, true), &(&1, false)), Some(2.0));
+        assert_eq!(cover.weakly_connected_components().len(), 1);
+
+        let mut looped = Graph::new();
+        let _ = looped.add_node(0);
+        let _ = looped.add_edge(&0, &0);
+        assert_eq!(looped.odd_cycle_transversal(), vec![&0]);
+    }
+}
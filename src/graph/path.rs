@@ -0,0 +1,164 @@
+use std::{borrow::Borrow, fmt::Debug, hash::Hash};
+
+use super::format::{dot_quote, node_label};
+
+/// 経路探索の結果．通過するノード列と経路全体の重みを持つ
+///
+/// 重みの型は普通は f64 で，`shortest_path_by` などで重みの型を選んだときはその型になる
+#[derive(Debug, PartialEq)]
+pub struct Path<'a, T, W = f64> {
+    nodes: Vec<&'a T>,
+    weight: W,
+}
+
+impl<'a, T, W: Clone> Clone for Path<'a, T, W> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            weight: self.weight.clone(),
+        }
+    }
+}
+
+impl<'a, T, W: Copy> Path<'a, T, W> {
+    pub fn new(nodes: Vec<&'a T>, weight: W) -> Self {
+        Self { nodes, weight }
+    }
+
+    pub fn nodes(&self) -> &[&'a T] {
+        &self.nodes
+    }
+
+    // 経路上のエッジを (from, to) の順に返す
+    pub fn edges(&self) -> impl Iterator<Item = (&'a T, &'a T)> + '_ {
+        self.nodes.windows(2).map(|w| (w[0], w[1]))
+    }
+
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+
+    pub fn source(&self) -> Option<&'a T> {
+        self.nodes.first().copied()
+    }
+
+    pub fn target(&self) -> Option<&'a T> {
+        self.nodes.last().copied()
+    }
+
+    // ノード数
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    pub fn into_nodes(self) -> Vec<&'a T> {
+        self.nodes
+    }
+}
+
+impl<'a, T: PartialEq, W> Path<'a, T, W> {
+    pub fn contains<Q>(&self, u: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.nodes.iter().any(|v| (*v).borrow() == u)
+    }
+}
+
+impl<'a, T: Debug, W: Copy> Path<'a, T, W> {
+    /// 経路上のノードとエッジを `color` で強調する DOT 文を返す (digraph の本体に貼り付けて使う)
+    pub fn to_dot_highlight(&self, color: &str) -> String {
+        let mut ret = String::new();
+
+        for u in self.nodes.iter() {
+            ret.push_str(&format!(
+                "    {} [color={}];\n",
+                dot_quote(&node_label(*u)),
+                dot_quote(color)
+            ));
+        }
+        for (from, to) in self.edges() {
+            ret.push_str(&format!(
+                "    {} -> {} [color={}, penwidth=2];\n",
+                dot_quote(&node_label(from)),
+                dot_quote(&node_label(to)),
+                dot_quote(color)
+            ));
+        }
+
+        ret
+    }
+}
+
+impl<'a, T, W> IntoIterator for Path<'a, T, W> {
+    type Item = &'a T;
+    type IntoIter = std::vec::IntoIter<&'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a, 'b, T, W> IntoIterator for &'b Path<'a, T, W> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'b, &'a T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Path;
+
+    #[test]
+    fn test_path_accessors() {
+        let (a, b, c) = ("A", "B", "C");
+        let p = Path::new(vec![&a, &b, &c], 2.0);
+
+        assert_eq!(p.len(), 3);
+        assert_eq!(p.edge_count(), 2);
+        assert_eq!(p.weight(), 2.0);
+        assert_eq!(p.source(), Some(&"A"));
+        assert_eq!(p.target(), Some(&"C"));
+        assert!(p.contains(&"B"));
+        assert!(!p.contains(&"D"));
+        assert_eq!(p.edges().collect::<Vec<_>>(), vec![(&a, &b), (&b, &c)]);
+        assert_eq!(p.iter().collect::<Vec<_>>(), vec![&a, &b, &c]);
+        assert_eq!((&p).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_path_single_node() {
+        let a = "A";
+        let p = Path::new(vec![&a], 0.0);
+
+        assert_eq!(p.edge_count(), 0);
+        assert_eq!(p.edges().count(), 0);
+        assert!(!p.is_empty());
+    }
+
+    #[test]
+    fn test_path_to_dot_highlight() {
+        let (a, b) = ("A", "B");
+        let p = Path::new(vec![&a, &b], 1.0);
+
+        let dot = p.to_dot_highlight("red");
+        assert!(dot.contains("\"A\" [color=\"red\"];"));
+        assert!(dot.contains("\"A\" -> \"B\" [color=\"red\", penwidth=2];"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    path::Path,
+};
+
+// 左右に同じノードを並べた二部グラフ (左の u → 右の v は adj(u)) の最大マッチング．
+// BFS で増加路を探す．(左 → 右, 右 → 左) の対応を返す
+pub(crate) fn bipartite_matching<F>(
+    ids: &[NodeID],
+    adj: F,
+) -> (HashMap<NodeID, NodeID>, HashMap<NodeID, NodeID>)
+where
+    F: Fn(NodeID) -> Vec<NodeID>,
+{
+    let mut next: HashMap<NodeID, NodeID> = HashMap::new(); // 左 → 右
+    let mut prev: HashMap<NodeID, NodeID> = HashMap::new(); // 右 → 左
+
+    for &u in ids {
+        let mut from: HashMap<NodeID, NodeID> = HashMap::new(); // 右のノード → それに来た左のノード
+        let mut queue = VecDeque::from([u]);
+        let mut end = None;
+        'search: while let Some(x) = queue.pop_front() {
+            for v in adj(x) {
+                if from.contains_key(&v) {
+                    continue;
+                }
+                from.insert(v, x);
+                match prev.get(&v) {
+                    Some(&y) => queue.push_back(y),
+                    None => {
+                        end = Some(v);
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        // 増加路に沿ってマッチングを入れ替える
+        let mut v = end;
+        while let Some(right) = v {
+            let left = from[&right];
+            v = next.insert(left, right);
+            prev.insert(right, left);
+        }
+    }
+
+    (next, prev)
+}
+
+// マッチングをたどってパスに分ける．パスは始点の ID 順に並ぶ
+pub(crate) fn chains_from_matching(
+    ids: &[NodeID],
+    next: &HashMap<NodeID, NodeID>,
+    prev: &HashMap<NodeID, NodeID>,
+) -> Vec<Vec<NodeID>> {
+    ids.iter()
+        .filter(|id| !prev.contains_key(id))
+        .map(|&start| {
+            let mut path = vec![start];
+            while let Some(&n) = next.get(path.last().unwrap()) {
+                path.push(n);
+            }
+            path
+        })
+        .collect()
+}
+
+impl GraphCore {
+    // DAG の最小パス被覆 (頂点素)．各ノードを左右に分けた二部グラフの最大マッチングで求め，
+    // マッチした u → v を同じパスでつなぐ．パスは始点の ID 順に並ぶ
+    pub fn minimum_path_cover(&self) -> Vec<Vec<NodeID>> {
+        let mut ids: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+
+        let (next, prev) = bipartite_matching(&ids, |x| {
+            let mut children = self.children_of(x);
+            children.sort_unstable();
+            children
+        });
+        chains_from_matching(&ids, &next, &prev)
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // すべてのノードをちょうど 1 回ずつ通る，本数が最小のパスの集合 (DAG のみ)．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn minimum_path_cover(&self) -> Result<Vec<Path<'_, T>>, Vec<&T>> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(cycle);
+        }
+
+        Ok(self
+            .core
+            .minimum_path_cover()
+            .iter()
+            .map(|p| self.path_from_ids(p))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_minimum_path_cover() {
+        // A → B → C,  A → D,  E → B (B を奪い合う)
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"A", &"D");
+        let _ = g.add_edge(&"E", &"B");
+
+        let cover = g.minimum_path_cover().unwrap();
+        assert_eq!(cover.len(), 2);
+
+        // すべてのノードがちょうど 1 回ずつ現れ，隣り合うノードはエッジでつながっている
+        let mut seen = HashSet::new();
+        for p in &cover {
+            for pair in p.nodes().windows(2) {
+                assert!(g.contains_edge(pair[0], pair[1]));
+            }
+            for u in p.nodes() {
+                assert!(seen.insert(*u));
+            }
+        }
+        assert_eq!(seen.len(), 5);
+    }
+
+    #[test]
+    fn test_minimum_path_cover_cycle() {
+        let mut g = Graph::new();
+        let _ = g.add_node(1);
+        let _ = g.add_edge(&1, &1);
+        assert!(g.minimum_path_cover().is_err());
+        assert!(Graph::<u32>::new().minimum_path_cover().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use std::{borrow::Borrow, fmt, fmt::Debug, hash::Hash};
+
+use super::{error::GraphError, format::json_quote, graph::Graph};
+
+/// エッジがどこから得られたか (ソースファイル・行・抽出ルール)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Provenance {
+    pub file: String,
+    pub line: usize,
+    pub rule: String,
+}
+
+impl Provenance {
+    pub fn new(file: &str, line: usize, rule: &str) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            rule: rule.to_string(),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        format!(
+            "{{\"file\":{},\"line\":{},\"rule\":{}}}",
+            json_quote(&self.file),
+            self.line,
+            json_quote(&self.rule)
+        )
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} ({})", self.file, self.line, self.rule)
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 出所付きでエッジを追加する．同じエッジに対して呼ぶたびに出所が追記される
+    pub fn add_edge_with_provenance<Q>(
+        &mut self,
+        u_from: &Q,
+        u_to: &Q,
+        provenance: Provenance,
+    ) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ret = self.add_edge(u_from, u_to)?;
+
+        let key = (*self.node_id(u_from).unwrap(), *self.node_id(u_to).unwrap());
+        self.provenance.entry(key).or_default().push(provenance);
+
+        Ok(ret)
+    }
+
+    // エッジの出所の一覧 (記録が無ければ空)
+    pub fn provenance<Q>(&self, u_from: &Q, u_to: &Q) -> &[Provenance]
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.node_id(u_from), self.node_id(u_to)) {
+            (Some(&a), Some(&b)) => self.provenance.get(&(a, b)).map_or(&[], |v| v.as_slice()),
+            _ => &[],
+        }
+    }
+
+    // サイクル (閉じた形のノード列) の各エッジの出所
+    pub fn cycle_provenance<Q>(&self, cycle: &[&Q]) -> Vec<&[Provenance]>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        cycle
+            .windows(2)
+            .map(|w| self.provenance(w[0], w[1]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::Provenance;
+
+    #[test]
+    fn test_provenance() {
+        let mut g = Graph::new();
+        let _ = g.add_node("a.rs");
+        let _ = g.add_node("b.rs");
+
+        let p1 = Provenance::new("a.rs", 3, "use");
+        let p2 = Provenance::new("a.rs", 10, "call");
+        assert_eq!(
+            g.add_edge_with_provenance(&"a.rs", &"b.rs", p1.clone()),
+            Ok(false)
+        );
+        assert_eq!(
+            g.add_edge_with_provenance(&"a.rs", &"b.rs", p2.clone()),
+            Ok(true)
+        );
+        assert!(g
+            .add_edge_with_provenance(&"a.rs", &"c.rs", p1.clone())
+            .is_err());
+
+        assert_eq!(g.provenance(&"a.rs", &"b.rs"), &[p1.clone(), p2]);
+        assert!(g.provenance(&"b.rs", &"a.rs").is_empty());
+        assert_eq!(p1.to_string(), "a.rs:3 (use)");
+
+        let _ = g.add_edge(&"b.rs", &"a.rs");
+        let cycle = g.detect_cycle().unwrap();
+        let prov = g.cycle_provenance(&cycle);
+        assert_eq!(prov.len(), 2);
+        assert_eq!(prov.iter().map(|p| p.len()).sum::<usize>(), 2);
+
+        let _ = g.remove_edge(&"a.rs", &"b.rs");
+        let _ = g.add_edge(&"a.rs", &"b.rs");
+        assert!(g.provenance(&"a.rs", &"b.rs").is_empty());
+    }
+}
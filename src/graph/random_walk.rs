@@ -0,0 +1,179 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    format::node_label,
+    graph::Graph,
+    rng::Rng,
+};
+
+impl GraphCore {
+    // node2vec のバイアス付きランダムウォーク．直前のノード t から v に来たとき，
+    // 次のノード x の重みは x == t なら w/p，t → x があれば w，それ以外は w/q．
+    // 子ノードが無くなった時点で打ち切る
+    pub fn node2vec_walk<W, R>(
+        &self,
+        start: NodeID,
+        walk_len: usize,
+        p: f64,
+        q: f64,
+        weight: W,
+        rng: &mut R,
+    ) -> Vec<NodeID>
+    where
+        W: Fn(NodeID, NodeID) -> f64,
+        R: Rng,
+    {
+        let mut walk = vec![start];
+        while walk.len() < walk_len {
+            let v = *walk.last().unwrap();
+            let mut children = self.children_of(v);
+            if children.is_empty() {
+                break;
+            }
+            children.sort_unstable();
+
+            let prev = walk.len().checked_sub(2).map(|i| walk[i]);
+            let probs: Vec<f64> = children
+                .iter()
+                .map(|&x| {
+                    let w = weight(v, x);
+                    match prev {
+                        None => w,
+                        Some(t) if t == x => w / p,
+                        Some(t) if self.nodes_dict[&t].children.contains(&x) => w,
+                        Some(_) => w / q,
+                    }
+                })
+                .collect();
+
+            let total: f64 = probs.iter().sum();
+            let mut r = rng.next_f64() * total;
+            let mut next = *children.last().unwrap();
+            for (&x, &prob) in children.iter().zip(&probs) {
+                if r < prob {
+                    next = x;
+                    break;
+                }
+                r -= prob;
+            }
+            walk.push(next);
+        }
+        walk
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // すべてのノードから walks_per_node 回ずつ node2vec 形式のウォークを生成する．
+    // エッジの重みは遷移確率 (の比) として使う
+    pub fn random_walk_corpus<R: Rng>(
+        &self,
+        walks_per_node: usize,
+        walk_len: usize,
+        p: f64,
+        q: f64,
+        rng: &mut R,
+    ) -> Vec<Vec<&T>> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut walks = Vec::with_capacity(walks_per_node * ids.len());
+        for _ in 0..walks_per_node {
+            for &id in &ids {
+                let walk =
+                    self.core
+                        .node2vec_walk(id, walk_len, p, q, |a, b| self.weight_of(a, b), rng);
+                walks.push(walk.iter().map(|id| nodes[id]).collect());
+            }
+        }
+        walks
+    }
+}
+
+impl<T: PartialEq + Eq + Hash + Debug> Graph<T> {
+    // random_walk_corpus の結果を 1 行 1 ウォークで書き出す (word2vec などの入力用)．
+    // トークンはノードのラベルで，空白は '_' に置き換える
+    pub fn write_random_walk_corpus<W: Write, R: Rng>(
+        &self,
+        w: &mut W,
+        walks_per_node: usize,
+        walk_len: usize,
+        p: f64,
+        q: f64,
+        rng: &mut R,
+    ) -> io::Result<()> {
+        let tokens: HashMap<&T, String> = self
+            .id_dict
+            .keys()
+            .map(|u| (u, node_label(u).replace(char::is_whitespace, "_")))
+            .collect();
+
+        for walk in self.random_walk_corpus(walks_per_node, walk_len, p, q, rng) {
+            let line: Vec<&str> = walk.iter().map(|u| tokens[u].as_str()).collect();
+            writeln!(w, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{graph::Graph, rng::XorShift64};
+
+    #[test]
+    fn test_random_walk_corpus() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+
+        let walks = g.random_walk_corpus(3, 5, 1.0, 1.0, &mut XorShift64::new(7));
+        assert_eq!(walks.len(), 12);
+        for walk in &walks {
+            assert!(walk.len() <= 5);
+            for pair in walk.windows(2) {
+                assert!(g.edge_weight(pair[0], pair[1]).is_some());
+            }
+        }
+        // D は行き止まり
+        assert!(walks.iter().any(|w| w == &vec![&"D"]));
+
+        // 同じシードなら同じコーパス
+        let again = g.random_walk_corpus(3, 5, 1.0, 1.0, &mut XorShift64::new(7));
+        assert_eq!(walks, again);
+    }
+
+    #[test]
+    fn test_random_walk_return_bias() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"B", &"C");
+
+        // p が非常に小さいと B からはほぼ必ず A に戻る
+        let walks = g.random_walk_corpus(20, 3, 1e-9, 1.0, &mut XorShift64::new(1));
+        for walk in walks.iter().filter(|w| w[0] == &"A") {
+            assert_eq!(walk, &vec![&"A", &"B", &"A"]);
+        }
+
+        let mut h = Graph::new();
+        let _ = h.add_node("x y");
+        let mut buf = Vec::new();
+        let _ = h.write_random_walk_corpus(&mut buf, 2, 3, 1.0, 1.0, &mut XorShift64::new(1));
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(text, "x_y\nx_y\n");
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // `start` に到達できるノード (親をたどる．`start` 自身はサイクル上にあっても含まない)
+    pub fn ancestors(&self, start: NodeID) -> HashSet<NodeID> {
+        let mut ret = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if let Some(n) = self.nodes_dict.get(&id) {
+                for &p in &n.parents {
+                    if p != start && ret.insert(p) {
+                        stack.push(p);
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+/// 到達可能性の問い合わせを高速に答えるための索引
+///
+/// 強連結成分を縮約した DAG 上で全域森の帰りがけ順に番号を振り，各成分から到達できる
+/// 成分の番号を区間の列で持つ．問い合わせは区間列の二分探索で済む
+pub struct ReachabilityIndex<'a, T: PartialEq + Eq + Hash> {
+    graph: &'a Graph<T>,
+    component: HashMap<NodeID, usize>,
+    post: Vec<usize>,
+    intervals: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> ReachabilityIndex<'a, T> {
+    fn new(graph: &'a Graph<T>) -> Self {
+        // 成分番号は逆トポロジカル順 (エッジ u → v (成分が異なる) について v の方が小さい)
+        let (component, dag) = graph.core.condensation();
+        let k = dag.len();
+
+        // 全域森の帰りがけ順 post と，部分木内の最小番号 low
+        let mut post = vec![usize::MAX; k];
+        let mut low = vec![0; k];
+        let mut counter = 0;
+        for root in (0..k).rev() {
+            if post[root] != usize::MAX {
+                continue;
+            }
+
+            let mut work: Vec<(usize, usize, usize)> = vec![(root, 0, counter)];
+            post[root] = usize::MAX - 1; // 訪問中
+            while let Some((c, i, first)) = work.last_mut() {
+                if *i < dag[*c].len() {
+                    let next = dag[*c][*i];
+                    *i += 1;
+                    if post[next] == usize::MAX {
+                        post[next] = usize::MAX - 1;
+                        work.push((next, 0, counter));
+                    }
+                    continue;
+                }
+
+                let (c, first) = (*c, *first);
+                work.pop();
+                post[c] = counter;
+                low[c] = first;
+                counter += 1;
+            }
+        }
+
+        // 成分番号の小さい順 (= 逆トポロジカル順) に区間をまとめる
+        let mut intervals: Vec<Vec<(usize, usize)>> = vec![Vec::new(); k];
+        for c in 0..k {
+            let mut all = vec![(low[c], post[c])];
+            for &next in &dag[c] {
+                all.extend(intervals[next].iter().copied());
+            }
+            intervals[c] = merge_intervals(all);
+        }
+
+        Self {
+            graph,
+            component,
+            post,
+            intervals,
+        }
+    }
+
+    // `from` から `to` へ到達できるか．同じノード同士は常に true
+    pub fn can_reach<Q>(&self, from: &Q, to: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (Some(from_id), Some(to_id)) = (self.graph.node_id(from), self.graph.node_id(to))
+        else {
+            return false;
+        };
+
+        let (cf, ct) = (self.component[from_id], self.component[to_id]);
+        if cf == ct {
+            return true;
+        }
+
+        let p = self.post[ct];
+        let intervals = &self.intervals[cf];
+        let i = intervals.partition_point(|&(_, end)| end < p);
+        i < intervals.len() && intervals[i].0 <= p
+    }
+}
+
+fn merge_intervals(mut all: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    all.sort_unstable();
+
+    let mut ret: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in all {
+        match ret.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => ret.push((start, end)),
+        }
+    }
+    ret
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // `from` から `to` へ到達できるか．同じノード同士は常に true，無いノードは false．
+    // 問い合わせが多い場合は reachability_index を使う
+    pub fn is_reachable<Q>(&self, from: &Q, to: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (Some(&from_id), Some(&to_id)) = (self.node_id(from), self.node_id(to)) else {
+            return false;
+        };
+
+        let mut found = false;
+        self.core.traverse(from_id, |id| found |= id == to_id);
+        found
+    }
+
+    // `from` から到達できるノード (`from` 自身は含まない)
+    pub fn descendants<Q>(&self, from: &Q) -> HashSet<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&from_id) = self.node_id(from) else {
+            return HashSet::new();
+        };
+
+        let nodes = self.nodes_by_id();
+        let mut ret = HashSet::new();
+        self.core.traverse(from_id, |id| {
+            if id != from_id {
+                ret.insert(nodes[&id]);
+            }
+        });
+        ret
+    }
+
+    // 直接の親ノード (ノードを追加した順)．無いノードは空
+    pub fn predecessors<Q>(&self, u: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(id) = self.node_id(u) else {
+            return Vec::new();
+        };
+
+        let mut parents: Vec<NodeID> = self.core.nodes_dict[id].parents.iter().copied().collect();
+        parents.sort_unstable();
+        self.nodes_from_ids(&parents)
+    }
+
+    // `to` に到達できるノード (`to` 自身は含まない)
+    pub fn ancestors<Q>(&self, to: &Q) -> HashSet<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&to_id) = self.node_id(to) else {
+            return HashSet::new();
+        };
+
+        let nodes = self.nodes_by_id();
+        self.core
+            .ancestors(to_id)
+            .iter()
+            .map(|id| nodes[id])
+            .collect()
+    }
+
+    // 各ノードから到達できるノードの数 (`descendants(u).len()` と同じ)．
+    // 縮約した DAG 上で到達できる成分をビット集合で伝播させるので，成分数 k に対して O(k^2 / 64) のメモリを使う
+    pub fn descendant_counts(&self) -> HashMap<&T, usize> {
+        let (component, dag) = self.core.condensation();
+        let k = dag.len();
+        let mut size = vec![0; k];
+        for &c in component.values() {
+            size[c] += 1;
+        }
+
+        // 成分番号は逆トポロジカル順なので，番号の小さい成分から確定する
+        let words = k.div_ceil(64);
+        let mut reach: Vec<Vec<u64>> = vec![vec![0; words]; k];
+        let mut counts = vec![0; k];
+        for c in 0..k {
+            let mut bits = vec![0; words];
+            bits[c / 64] |= 1 << (c % 64);
+            for &next in &dag[c] {
+                for (b, r) in bits.iter_mut().zip(&reach[next]) {
+                    *b |= r;
+                }
+            }
+            counts[c] = (0..k)
+                .filter(|&d| bits[d / 64] & (1 << (d % 64)) != 0)
+                .map(|d| size[d])
+                .sum::<usize>()
+                - 1; // 自分自身
+            reach[c] = bits;
+        }
+
+        let nodes = self.nodes_by_id();
+        component
+            .iter()
+            .map(|(id, &c)| (nodes[id], counts[c]))
+            .collect()
+    }
+
+    // 到達可能性の索引を作る．索引はグラフを借用するので，変更するときは作り直す
+    pub fn reachability_index(&self) -> ReachabilityIndex<'_, T> {
+        ReachabilityIndex::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::super::graph::Graph;
+    use super::merge_intervals;
+
+    #[test]
+    fn test_merge_intervals() {
+        assert_eq!(
+            merge_intervals(vec![(5, 6), (0, 1), (2, 3), (8, 9), (9, 10)]),
+            vec![(0, 3), (5, 6), (8, 10)]
+        );
+    }
+
+    #[test]
+    fn test_can_reach() {
+        // A → B ⇄ C → D,  A → E,  F は孤立
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"B");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"A", &"E");
+
+        let index = g.reachability_index();
+        assert!(index.can_reach(&"A", &"D"));
+        assert!(index.can_reach(&"C", &"B"));
+        assert!(index.can_reach(&"B", &"D"));
+        assert!(index.can_reach(&"A", &"E"));
+        assert!(index.can_reach(&"F", &"F"));
+        assert!(!index.can_reach(&"D", &"A"));
+        assert!(!index.can_reach(&"E", &"D"));
+        assert!(!index.can_reach(&"A", &"F"));
+        assert!(!index.can_reach(&"A", &"Z"));
+    }
+
+    #[test]
+    fn test_is_reachable_and_descendants() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"B");
+
+        assert!(g.is_reachable(&"A", &"C"));
+        assert!(g.is_reachable(&"D", &"D"));
+        assert!(!g.is_reachable(&"C", &"A"));
+        assert!(!g.is_reachable(&"A", &"Z"));
+
+        assert_eq!(g.descendants(&"A"), HashSet::from([&"B", &"C"]));
+        assert_eq!(g.descendants(&"B"), HashSet::from([&"C"]));
+        assert!(g.descendants(&"D").is_empty());
+        assert!(g.descendants(&"Z").is_empty());
+    }
+
+    #[test]
+    fn test_predecessors_and_ancestors() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"D", &"C");
+
+        assert_eq!(g.predecessors(&"C"), vec![&"A", &"B", &"D"]);
+        assert_eq!(g.ancestors(&"D"), HashSet::from([&"A", &"B", &"C"]));
+        assert_eq!(g.ancestors(&"C"), HashSet::from([&"A", &"B", &"D"]));
+        assert!(g.ancestors(&"A").is_empty());
+
+        // エッジを削除すると逆向きの隣接も更新される
+        let _ = g.remove_edge(&"B", &"C");
+        assert_eq!(g.predecessors(&"C"), vec![&"A", &"D"]);
+        assert!(g.predecessors(&"Z").is_empty());
+    }
+
+    #[test]
+    fn test_descendant_counts() {
+        // A → B ⇄ C → D,  A → E
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"B");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"A", &"E");
+
+        let counts = g.descendant_counts();
+        for u in ["A", "B", "C", "D", "E"] {
+            assert_eq!(counts[&u], g.descendants(&u).len(), "{u}");
+        }
+        assert_eq!(counts[&"A"], 4);
+        assert_eq!(counts[&"B"], 2);
+    }
+
+    #[test]
+    fn test_can_reach_matches_traversal() {
+        // 合流の多い DAG で全ペアを dfs_iter と突き合わせる
+        let mut g = Graph::new();
+        for i in 0..30 {
+            let _ = g.add_node(i);
+        }
+        for i in 0..30 {
+            for j in [i * 2 + 1, i * 3 + 2, i + 7] {
+                if j < 30 {
+                    let _ = g.add_edge(&i, &j);
+                }
+            }
+        }
+        let _ = g.add_edge(&29, &20);
+
+        let index = g.reachability_index();
+        for a in 0..30 {
+            let reachable: Vec<_> = g.dfs_iter(&a).collect();
+            for b in 0..30 {
+                assert_eq!(
+                    index.can_reach(&a, &b),
+                    reachable.contains(&&b),
+                    "{a} -> {b}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_reach_alias() {
+        let mut g = Graph::new();
+        for u in ["a", "b", "c"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.add_edge("a", "b");
+        let _ = g.alias("a", "A");
+
+        // String のノードを &str で問い合わせられる
+        let index = g.reachability_index();
+        assert!(g.is_reachable("A", "b"));
+        assert_eq!(index.can_reach("A", "b"), g.is_reachable("A", "b"));
+        assert!(!index.can_reach("A", "c"));
+        assert!(!index.can_reach("b", "A"));
+        assert_eq!(g.predecessors("b"), vec!["a"]);
+        assert_eq!(g.descendants("A").len(), 1);
+        assert_eq!(g.in_degree("b"), Some(1));
+    }
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashMap, hash::Hash, mem};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+// (from, to) をキーにした表の向きを入れ替える
+fn flip_keys<V>(m: &mut HashMap<(NodeID, NodeID), V>) {
+    *m = mem::take(m)
+        .into_iter()
+        .map(|((a, b), v)| ((b, a), v))
+        .collect();
+}
+
+impl GraphCore {
+    // すべてのエッジの向きを反転したグラフ (転置グラフ)．NodeID はそのまま
+    pub fn reversed(&self) -> GraphCore {
+        let mut core = self.clone();
+        for n in core.nodes_dict.values_mut() {
+            mem::swap(&mut n.children, &mut n.parents);
+        }
+        // 反転するとトポロジカル順は使えなくなる
+        core.topo = None;
+        core
+    }
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> Graph<T> {
+    // すべてのエッジの向きを反転したグラフ．重み・ラベル・出所・平行なエッジの本数と
+    // 別名は反転したエッジとノードに引き継ぐ
+    pub fn reversed(&self) -> Graph<T> {
+        let mut g = self.clone();
+        g.core = self.core.reversed();
+        flip_keys(&mut g.weights);
+        flip_keys(&mut g.labels);
+        flip_keys(&mut g.provenance);
+        flip_keys(&mut g.multiplicity);
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_reversed() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 2.0);
+        let _ = g.add_edge_with_label(&"B", &"C", "calls");
+        let _ = g.add_edge(&"A", &"C");
+
+        let r = g.reversed();
+        assert_eq!(r.edge_count(), 3);
+        assert_eq!(r.edge_weight(&"B", &"A"), Some(2.0));
+        assert_eq!(r.edge_label(&"C", &"B"), Some("calls"));
+        assert!(!r.contains_edge(&"A", &"B"));
+        assert_eq!(r.predecessors(&"A"), vec![&"B", &"C"]);
+        assert_eq!(r.topological_sort().unwrap(), vec![&"C", &"B", &"A"]);
+        assert_eq!(r.reversed(), g);
+    }
+
+    #[test]
+    fn test_reversed_keeps_aliases() {
+        let mut g = Graph::new();
+        for u in ["foo.rs", "bar.rs"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.alias("foo.rs", "crate::foo");
+        let _ = g.add_edge("crate::foo", "bar.rs");
+
+        let mut r = g.reversed();
+        assert!(r.contains_edge("bar.rs", "crate::foo"));
+        assert_eq!(
+            r.canonical("crate::foo").map(|u| u.as_str()),
+            Some("foo.rs")
+        );
+        assert!(r.alias("bar.rs", "crate::foo").is_err());
+    }
+}
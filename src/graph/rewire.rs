@@ -0,0 +1,141 @@
+use std::hash::Hash;
+
+use super::{core::NodeID, graph::Graph, rng::Rng};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 各ノードの入次数・出次数を保ったままエッジをランダムに張り替える (帰無モデル用)．
+    // 2 本のエッジ a → b, c → d を選んで a → d, c → b に付け替える操作を iterations 回試し，
+    // 自己ループや重複エッジができる場合はその回を見送る．成功した回数を返す．
+    // 重みとラベルは出発点側に付いたまま移り，付け替えたエッジの出所は消える．
+    // 多重グラフでは平行なエッジを 1 本ずつ付け替える
+    pub fn rewire_preserving_degrees<R: Rng>(&mut self, iterations: usize, rng: &mut R) -> usize {
+        let mut edges: Vec<(NodeID, NodeID)> = Vec::new();
+        for (&id, n) in self.core.nodes_dict.iter() {
+            for &child in &n.children {
+                for _ in 0..self.multiplicity_of(id, child) {
+                    edges.push((id, child));
+                }
+            }
+        }
+        edges.sort_unstable();
+        if edges.len() < 2 {
+            return 0;
+        }
+
+        let mut swaps = 0;
+        for _ in 0..iterations {
+            let i = rng.gen_index(edges.len());
+            let j = rng.gen_index(edges.len());
+            let ((a, b), (c, d)) = (edges[i], edges[j]);
+            if a == c || b == d || a == d || c == b {
+                continue;
+            }
+            if self.core.nodes_dict[&a].children.contains(&d)
+                || self.core.nodes_dict[&c].children.contains(&b)
+            {
+                continue;
+            }
+
+            self.move_edge((a, b), (a, d));
+            self.move_edge((c, d), (c, b));
+            edges[i] = (a, d);
+            edges[j] = (c, b);
+            swaps += 1;
+        }
+        swaps
+    }
+
+    // 平行なエッジのうち 1 本を `from` から `to` に付け替え，重みとラベルも移す
+    fn move_edge(&mut self, from: (NodeID, NodeID), to: (NodeID, NodeID)) {
+        let weight = self.weights.get(&from).copied();
+        let label = self.labels.get(&from).cloned();
+        self.remove_one_edge_by_id(from.0, from.1);
+        let _ = self.add_edge_by_id(to.0, to.1); // 自己ループにならないことを確かめた
+        if let Some(w) = weight {
+            self.weights.insert(to, w);
+        }
+        if let Some(label) = label {
+            self.labels.insert(to, label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::super::{graph::Graph, rng::XorShift64};
+
+    fn degrees(g: &Graph<u32>) -> HashMap<u32, (usize, usize)> {
+        let mut ret: HashMap<u32, (usize, usize)> = HashMap::new();
+        for a in 0..10 {
+            for b in 0..10 {
+                if g.edge_weight(&a, &b).is_some() {
+                    ret.entry(a).or_default().1 += 1;
+                    ret.entry(b).or_default().0 += 1;
+                }
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn test_rewire_preserving_degrees() {
+        let mut g = Graph::new();
+        for i in 0..10 {
+            let _ = g.add_node(i);
+        }
+        for i in 0..10 {
+            let _ = g.add_edge(&i, &((i + 1) % 10));
+            let _ = g.add_edge(&i, &((i + 3) % 10));
+        }
+        let before = degrees(&g);
+
+        let swaps = g.rewire_preserving_degrees(100, &mut XorShift64::new(3));
+        assert!(swaps > 0);
+        assert_eq!(degrees(&g), before);
+        for i in 0..10 {
+            assert_eq!(g.edge_weight(&i, &i), None);
+        }
+    }
+
+    #[test]
+    fn test_rewire_too_few_edges() {
+        let mut g = Graph::new();
+        let _ = g.add_node(0);
+        let _ = g.add_node(1);
+        let _ = g.add_edge(&0, &1);
+        assert_eq!(g.rewire_preserving_degrees(10, &mut XorShift64::new(1)), 0);
+        assert_eq!(g.edge_weight(&0, &1), Some(1.0));
+    }
+
+    #[test]
+    fn test_rewire_multigraph() {
+        let mut g = Graph::new_multigraph();
+        for i in 0..10 {
+            let _ = g.add_node(i);
+        }
+        for i in 0..10 {
+            let _ = g.add_edge_with_label(&i, &((i + 1) % 10), "next");
+            let _ = g.add_edge(&i, &((i + 1) % 10));
+            let _ = g.add_weighted_edge(&i, &((i + 3) % 10), 2.0);
+        }
+        let degrees = |g: &Graph<u32>| -> Vec<(usize, usize)> {
+            (0..10)
+                .map(|i| (g.in_degree(&i).unwrap(), g.out_degree(&i).unwrap()))
+                .collect()
+        };
+        let before = degrees(&g);
+
+        let swaps = g.rewire_preserving_degrees(100, &mut XorShift64::new(3));
+        assert!(swaps > 0);
+        assert_eq!(degrees(&g), before);
+        assert_eq!(g.edge_count(), 30);
+        // 付け替えたエッジには元の重みかラベルが付いている
+        for (a, b) in g.edges() {
+            let labeled = g.edge_label(a, b) == Some("next");
+            let weighted = g.edge_weight(a, b) == Some(2.0);
+            assert!(labeled != weighted, "{a} -> {b}");
+        }
+    }
+}
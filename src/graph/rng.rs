@@ -0,0 +1,64 @@
+/// 乱数を使うアルゴリズムに渡す乱数生成器
+///
+/// 外部クレートに依存しないよう最小限のインターフェースにしている．
+/// 再現性が必要ならシードを固定した `XorShift64` を使う
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    // [0, 1) の一様乱数
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // [0, n) の一様乱数 (n > 0)
+    fn gen_index(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize
+    }
+}
+
+/// xorshift64 による軽量な乱数生成器
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // 状態が 0 だと 0 しか出なくなる
+        Self {
+            state: if seed == 0 {
+                0x9e37_79b9_7f4a_7c15
+            } else {
+                seed
+            },
+        }
+    }
+}
+
+impl Rng for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rng, XorShift64};
+
+    #[test]
+    fn test_xorshift_reproducible() {
+        let mut a = XorShift64::new(42);
+        let mut b = XorShift64::new(42);
+        for _ in 0..100 {
+            let x = a.next_f64();
+            assert_eq!(x, b.next_f64());
+            assert!((0.0..1.0).contains(&x));
+        }
+        assert!(XorShift64::new(0).gen_index(10) < 10);
+    }
+}
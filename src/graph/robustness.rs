@@ -0,0 +1,233 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{core::NodeID, graph::Graph, union_find::UnionFind};
+
+/// `simulate_removals` で取り除くもの
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Removal<'q, T> {
+    Node(&'q T),
+    Edge(&'q T, &'q T),
+}
+
+/// 各段階での (エッジの向きを無視した) 連結性の指標
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovalStep {
+    pub node_count: usize,
+    pub component_count: usize,
+    pub largest_component: usize,
+    // 互いに到達できないノードの組の数 (順序なし)
+    pub disconnected_pairs: usize,
+}
+
+// 実際に効いた削除 (NodeID 版)
+enum Removed {
+    Node(NodeID),
+    Edge(NodeID, NodeID),
+}
+
+// 素集合に対して指標を差分で更新する
+struct Tracker {
+    uf: UnionFind,
+    size: Vec<usize>,
+    node_count: usize,
+    component_count: usize,
+    largest_component: usize,
+    connected_pairs: usize,
+}
+
+impl Tracker {
+    fn add_node(&mut self) {
+        self.node_count += 1;
+        self.component_count += 1;
+        self.largest_component = self.largest_component.max(1);
+    }
+
+    fn add_edge(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.uf.find(a), self.uf.find(b));
+        if ra == rb {
+            return;
+        }
+        let (sa, sb) = (self.size[ra], self.size[rb]);
+        self.uf.union(ra, rb);
+        let root = self.uf.find(ra);
+        self.size[root] = sa + sb;
+
+        self.component_count -= 1;
+        self.connected_pairs += sa * sb;
+        self.largest_component = self.largest_component.max(sa + sb);
+    }
+
+    fn step(&self) -> RemovalStep {
+        let all_pairs = self.node_count * self.node_count.saturating_sub(1) / 2;
+        RemovalStep {
+            node_count: self.node_count,
+            component_count: self.component_count,
+            largest_component: self.largest_component,
+            disconnected_pairs: all_pairs - self.connected_pairs,
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // ノードやエッジを順に取り除いたときの連結性の推移 (頑健性曲線) を求める
+    // 戻り値の k 番目は先頭から k 個を取り除いた後の状態 (0 番目は元のグラフ)
+    // 存在しないものや既に取り除かれたものの削除は何もしない
+    //
+    // 最後の状態から逆順に付け戻しながら素集合で数えるので，グラフ自体は変更しない
+    pub fn simulate_removals(&self, items: &[Removal<T>]) -> Vec<RemovalStep> {
+        let core = &self.core;
+        let mut index: HashMap<NodeID, usize> = HashMap::new();
+        let mut ids: Vec<NodeID> = core.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        for (i, &id) in ids.iter().enumerate() {
+            index.insert(id, i);
+        }
+
+        let mut edges: HashSet<(NodeID, NodeID)> = HashSet::new();
+        let mut incident: HashMap<NodeID, Vec<(NodeID, NodeID)>> = HashMap::new();
+        for (&id, n) in core.nodes_dict.iter() {
+            for &child in &n.children {
+                edges.insert((id, child));
+                incident.entry(id).or_default().push((id, child));
+                incident.entry(child).or_default().push((id, child));
+            }
+        }
+
+        // 前向きにたどって実際に効く削除を決める
+        let mut alive: HashSet<NodeID> = ids.iter().copied().collect();
+        let mut removed_edges: HashSet<(NodeID, NodeID)> = HashSet::new();
+        let mut effective: Vec<Option<Removed>> = Vec::with_capacity(items.len());
+        for item in items {
+            let op = match *item {
+                Removal::Node(u) => self
+                    .node_id(u)
+                    .filter(|id| alive.remove(id))
+                    .map(|&id| Removed::Node(id)),
+                Removal::Edge(a, b) => match (self.node_id(a), self.node_id(b)) {
+                    (Some(&a), Some(&b))
+                        if edges.contains(&(a, b))
+                            && alive.contains(&a)
+                            && alive.contains(&b)
+                            && removed_edges.insert((a, b)) =>
+                    {
+                        Some(Removed::Edge(a, b))
+                    }
+                    _ => None,
+                },
+            };
+            effective.push(op);
+        }
+
+        let mut tracker = Tracker {
+            uf: UnionFind::new(ids.len()),
+            size: vec![1; ids.len()],
+            node_count: 0,
+            component_count: 0,
+            largest_component: 0,
+            connected_pairs: 0,
+        };
+        for _ in alive.iter() {
+            tracker.add_node();
+        }
+        for &(a, b) in edges.iter() {
+            if alive.contains(&a) && alive.contains(&b) && !removed_edges.contains(&(a, b)) {
+                tracker.add_edge(index[&a], index[&b]);
+            }
+        }
+
+        let mut steps = vec![tracker.step()];
+        for op in effective.iter().rev() {
+            match *op {
+                Some(Removed::Node(id)) => {
+                    alive.insert(id);
+                    tracker.add_node();
+                    for &(a, b) in incident.get(&id).into_iter().flatten() {
+                        if alive.contains(&a)
+                            && alive.contains(&b)
+                            && !removed_edges.contains(&(a, b))
+                        {
+                            tracker.add_edge(index[&a], index[&b]);
+                        }
+                    }
+                }
+                Some(Removed::Edge(a, b)) => {
+                    removed_edges.remove(&(a, b));
+                    tracker.add_edge(index[&a], index[&b]);
+                }
+                None => {}
+            }
+            steps.push(tracker.step());
+        }
+
+        steps.reverse();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::{Removal, RemovalStep};
+
+    fn step(n: usize, c: usize, l: usize, d: usize) -> RemovalStep {
+        RemovalStep {
+            node_count: n,
+            component_count: c,
+            largest_component: l,
+            disconnected_pairs: d,
+        }
+    }
+
+    #[test]
+    fn test_simulate_removals() {
+        // A → B → C → D と E
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+
+        let steps = g.simulate_removals(&[
+            Removal::Edge(&"B", &"C"),
+            Removal::Node(&"Z"), // 存在しない
+            Removal::Node(&"A"),
+            Removal::Edge(&"A", &"B"), // A と一緒に消えている
+            Removal::Node(&"D"),
+        ]);
+
+        assert_eq!(
+            steps,
+            vec![
+                step(5, 2, 4, 4),
+                step(5, 3, 2, 8),
+                step(5, 3, 2, 8),
+                step(4, 3, 2, 5),
+                step(4, 3, 2, 5),
+                step(3, 3, 1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simulate_removals_hub() {
+        // ハブ H に集まる星形のグラフからハブを消す
+        let mut g = Graph::new();
+        let _ = g.add_node("H");
+        for u in ["a", "b", "c", "d"] {
+            let _ = g.add_node(u);
+            let _ = g.add_edge(&u, &"H");
+        }
+        let _ = g.add_edge(&"H", &"a");
+
+        let steps = g.simulate_removals(&[Removal::Edge(&"a", &"H"), Removal::Node(&"H")]);
+        assert_eq!(steps[0], step(5, 1, 5, 0));
+        assert_eq!(steps[1], step(5, 1, 5, 0)); // H → a が残る
+        assert_eq!(steps[2], step(4, 4, 1, 6));
+    }
+}
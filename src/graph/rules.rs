@@ -0,0 +1,187 @@
+use std::{fmt::Debug, hash::Hash};
+
+use super::{core::NodeID, graph::Graph};
+
+/// ルールでノードの分類を指定するパターン
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassPattern<C> {
+    Any,
+    Class(C),
+}
+
+impl<C> From<C> for ClassPattern<C> {
+    fn from(c: C) -> Self {
+        ClassPattern::Class(c)
+    }
+}
+
+impl<C: PartialEq> ClassPattern<C> {
+    fn matches(&self, c: &C) -> bool {
+        match self {
+            ClassPattern::Any => true,
+            ClassPattern::Class(x) => x == c,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule<C> {
+    from: ClassPattern<C>,
+    to: ClassPattern<C>,
+    allow: bool,
+}
+
+/// ルールに違反したエッジ
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleViolation<'a, T, C> {
+    pub from: &'a T,
+    pub to: &'a T,
+    pub from_class: C,
+    pub to_class: C,
+    // 違反となったルールの番号 (追加順)．どのルールにも当てはまらず既定で禁止された場合は None
+    pub rule: Option<usize>,
+}
+
+/// ノードの分類の間で許可・禁止するエッジを宣言し，グラフがそれに従っているかを検査する
+///
+/// ルールは追加した順に調べ，最初に当てはまったものを使う．どれにも当てはまらない
+/// エッジは既定で許可する (`deny_by_default` で禁止にできる)
+pub struct RuleSet<T, C> {
+    classify: Box<dyn Fn(&T) -> C>,
+    rules: Vec<Rule<C>>,
+    default_allow: bool,
+}
+
+impl<T: PartialEq + Eq + Hash, C: PartialEq + Clone + Debug> RuleSet<T, C> {
+    pub fn new<F>(classify: F) -> Self
+    where
+        F: Fn(&T) -> C + 'static,
+    {
+        Self {
+            classify: Box::new(classify),
+            rules: Vec::new(),
+            default_allow: true,
+        }
+    }
+
+    pub fn deny_by_default(mut self) -> Self {
+        self.default_allow = false;
+        self
+    }
+
+    pub fn allow(
+        mut self,
+        from: impl Into<ClassPattern<C>>,
+        to: impl Into<ClassPattern<C>>,
+    ) -> Self {
+        self.rules.push(Rule {
+            from: from.into(),
+            to: to.into(),
+            allow: true,
+        });
+        self
+    }
+
+    pub fn forbid(
+        mut self,
+        from: impl Into<ClassPattern<C>>,
+        to: impl Into<ClassPattern<C>>,
+    ) -> Self {
+        self.rules.push(Rule {
+            from: from.into(),
+            to: to.into(),
+            allow: false,
+        });
+        self
+    }
+
+    // 違反しているエッジをすべて返す (エッジの出発点の追加順)
+    pub fn check<'a>(&self, graph: &'a Graph<T>) -> Vec<RuleViolation<'a, T, C>> {
+        let nodes = graph.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = graph
+            .core
+            .nodes_dict
+            .iter()
+            .flat_map(|(&id, n)| n.children.iter().map(move |&child| (id, child)))
+            .collect();
+        edges.sort_unstable();
+
+        let mut violations = Vec::new();
+        for (a, b) in edges {
+            let (from, to) = (nodes[&a], nodes[&b]);
+            let (from_class, to_class) = ((self.classify)(from), (self.classify)(to));
+
+            let matched = self
+                .rules
+                .iter()
+                .position(|r| r.from.matches(&from_class) && r.to.matches(&to_class));
+            let allowed = match matched {
+                Some(i) => self.rules[i].allow,
+                None => self.default_allow,
+            };
+            if !allowed {
+                violations.push(RuleViolation {
+                    from,
+                    to,
+                    from_class,
+                    to_class,
+                    rule: matched,
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::{ClassPattern, RuleSet};
+
+    fn modules() -> Graph<&'static str> {
+        let mut g = Graph::new();
+        for u in ["ui::view", "domain::user", "infra::db", "ui::form"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"ui::view", &"domain::user");
+        let _ = g.add_edge(&"ui::view", &"ui::form");
+        let _ = g.add_edge(&"domain::user", &"ui::form"); // 違反
+        let _ = g.add_edge(&"infra::db", &"domain::user");
+        g
+    }
+
+    fn layer(u: &&str) -> String {
+        u.split("::").next().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_rule_set_forbid() {
+        let g = modules();
+        let rules = RuleSet::new(layer)
+            .allow("domain".to_string(), "domain".to_string())
+            .forbid("domain".to_string(), ClassPattern::Any);
+
+        let violations = rules.check(&g);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            (violations[0].from, violations[0].to),
+            (&"domain::user", &"ui::form")
+        );
+        assert_eq!(violations[0].to_class, "ui");
+        assert_eq!(violations[0].rule, Some(1));
+    }
+
+    #[test]
+    fn test_rule_set_deny_by_default() {
+        let g = modules();
+        let rules = RuleSet::new(layer)
+            .deny_by_default()
+            .allow("ui".to_string(), ClassPattern::Any)
+            .allow("infra".to_string(), "domain".to_string());
+
+        let violations = rules.check(&g);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].from_class, "domain");
+        assert_eq!(violations[0].rule, None);
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    format::{json_quote, node_label},
+    graph::Graph,
+    provenance::Provenance,
+};
+
+impl GraphCore {
+    // Tarjan のアルゴリズムで強連結成分を求める (再帰は使わない)
+    // 成分は逆トポロジカル順 (シンクになる成分が先) に並ぶ
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeID>> {
+        let mut index: HashMap<NodeID, usize> = HashMap::new();
+        let mut low: HashMap<NodeID, usize> = HashMap::new();
+        let mut on_stack: HashSet<NodeID> = HashSet::new();
+        let mut stack: Vec<NodeID> = Vec::new();
+        let mut sccs: Vec<Vec<NodeID>> = Vec::new();
+        let mut counter = 0;
+
+        let mut roots: Vec<NodeID> = self.nodes_dict.keys().copied().collect();
+        roots.sort_unstable();
+
+        for root in roots {
+            if index.contains_key(&root) {
+                continue;
+            }
+
+            // (ノード, その子のリスト, 次に見る子の位置)
+            let mut work: Vec<(NodeID, Vec<NodeID>, usize)> = Vec::new();
+            index.insert(root, counter);
+            low.insert(root, counter);
+            counter += 1;
+            stack.push(root);
+            on_stack.insert(root);
+            work.push((root, self.children_of(root), 0));
+
+            while let Some((v, children, i)) = work.last_mut() {
+                let v = *v;
+
+                if *i < children.len() {
+                    let w = children[*i];
+                    *i += 1;
+
+                    if let Entry::Vacant(e) = index.entry(w) {
+                        e.insert(counter);
+                        low.insert(w, counter);
+                        counter += 1;
+                        stack.push(w);
+                        on_stack.insert(w);
+                        work.push((w, self.children_of(w), 0));
+                    } else if on_stack.contains(&w) {
+                        let l = low[&v].min(index[&w]);
+                        low.insert(v, l);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some((parent, _, _)) = work.last() {
+                    let l = low[parent].min(low[&v]);
+                    low.insert(*parent, l);
+                }
+
+                if low[&v] == index[&v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack.remove(&w);
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    sccs.push(component);
+                }
+            }
+        }
+
+        sccs
+    }
+
+    pub(crate) fn children_of(&self, id: NodeID) -> Vec<NodeID> {
+        match self.nodes_dict.get(&id) {
+            Some(n) => n.children.iter().copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // 成分が自己ループを含むか 2 ノード以上か (= サイクルを含むか)．
+    // `skip_self_loops` なら自己ループだけの成分はサイクルとみなさない
+    pub(crate) fn is_nontrivial_component(
+        &self,
+        members: &[NodeID],
+        skip_self_loops: bool,
+    ) -> bool {
+        match members {
+            [id] => {
+                !skip_self_loops
+                    && self
+                        .nodes_dict
+                        .get(id)
+                        .is_some_and(|n| n.children.contains(id))
+            }
+            _ => members.len() >= 2,
+        }
+    }
+
+    // 成分内の最小の ID のノードを通る最短のサイクルを返す (閉じた形: 先頭 == 末尾)．
+    // `skip_self_loops` なら自己ループを通らない
+    pub(crate) fn cycle_in_component(
+        &self,
+        members: &[NodeID],
+        skip_self_loops: bool,
+    ) -> Option<Vec<NodeID>> {
+        let member_set: HashSet<NodeID> = members.iter().copied().collect();
+        let start = *members.iter().min()?;
+
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        let mut visited = HashSet::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            for child in self.children_of(node) {
+                if skip_self_loops && child == node {
+                    continue;
+                }
+                if child == start {
+                    let mut cycle = vec![start];
+                    let mut cur = node;
+                    while cur != start {
+                        cycle.push(cur);
+                        cur = parent[&cur];
+                    }
+                    cycle.push(start);
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                if member_set.contains(&child) && visited.insert(child) {
+                    parent.insert(child, node);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        None
+    }
+
+    // サイクルを含む強連結成分ごとに代表のサイクルを 1 つずつ返す (閉じた形)．
+    // 成分は最小の ID の順に並ぶ
+    pub fn detect_cycles(&self) -> Vec<Vec<NodeID>> {
+        self.detect_cycles_inner(false)
+    }
+
+    // 自己ループをサイクルとみなさない detect_cycles
+    pub fn detect_cycles_ignoring_self_loops(&self) -> Vec<Vec<NodeID>> {
+        self.detect_cycles_inner(true)
+    }
+
+    fn detect_cycles_inner(&self, skip_self_loops: bool) -> Vec<Vec<NodeID>> {
+        let mut sccs: Vec<Vec<NodeID>> = self
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|c| self.is_nontrivial_component(c, skip_self_loops))
+            .collect();
+        sccs.sort_by_key(|c| c.iter().min().copied());
+
+        sccs.iter()
+            .filter_map(|c| self.cycle_in_component(c, skip_self_loops))
+            .collect()
+    }
+
+    // 両端が `members` に含まれるエッジの数
+    pub(crate) fn internal_edge_count(&self, members: &[NodeID]) -> usize {
+        let member_set: HashSet<NodeID> = members.iter().copied().collect();
+
+        members
+            .iter()
+            .filter_map(|id| self.nodes_dict.get(id))
+            .map(|n| n.children.iter().filter(|c| member_set.contains(c)).count())
+            .sum()
+    }
+}
+
+/// `scc_report` の 1 成分分の結果
+#[derive(Debug, PartialEq)]
+pub struct SccComponent<'a, T> {
+    pub size: usize,
+    pub members: Vec<&'a T>,
+    pub internal_edges: usize,
+    /// 成分内の代表的なサイクル (先頭 == 末尾)
+    pub cycle: Vec<&'a T>,
+    /// 代表サイクルの各エッジの出所
+    pub cycle_provenance: Vec<&'a [Provenance]>,
+}
+
+/// サイクルを含む強連結成分ごとの分析結果
+#[derive(Debug, PartialEq)]
+pub struct SccReport<'a, T> {
+    pub components: Vec<SccComponent<'a, T>>,
+}
+
+impl<'a, T: Debug> SccReport<'a, T> {
+    pub fn to_json(&self) -> String {
+        let labels = |nodes: &[&T]| {
+            nodes
+                .iter()
+                .map(|u| json_quote(&node_label(*u)))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let components = self
+            .components
+            .iter()
+            .map(|c| {
+                let provenance = c
+                    .cycle_provenance
+                    .iter()
+                    .map(|ps| {
+                        let ps: Vec<String> = ps.iter().map(|p| p.to_json()).collect();
+                        format!("[{}]", ps.join(","))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                format!(
+                    "{{\"size\":{},\"members\":[{}],\"internal_edges\":{},\"cycle\":[{}],\"cycle_provenance\":[{}]}}",
+                    c.size,
+                    labels(&c.members),
+                    c.internal_edges,
+                    labels(&c.cycle),
+                    provenance
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{{\"components\":[{}]}}", components)
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&T>> {
+        let nodes = self.nodes_by_id();
+
+        self.core
+            .strongly_connected_components()
+            .into_iter()
+            .map(|c| c.iter().map(|id| nodes[id]).collect())
+            .collect()
+    }
+
+    // detect_cycle と違い最初のサイクルで止まらず，サイクルを含む強連結成分 (自己ループを含む)
+    // ごとに最短の代表サイクルを 1 つずつ返す．成分はノードの追加順に並ぶ．
+    // 自己ループの扱いは self_loop_policy に従う
+    pub fn detect_cycles(&self) -> Vec<Vec<&T>> {
+        let cycles = if self.ignores_self_loops() {
+            self.core.detect_cycles_ignoring_self_loops()
+        } else {
+            self.core.detect_cycles()
+        };
+        cycles.iter().map(|c| self.nodes_from_ids(c)).collect()
+    }
+
+    // 自明でない (サイクルを含む) 強連結成分ごとにサイズ・メンバー・内部エッジ数・代表サイクルをまとめる
+    // 成分はサイズの大きい順に並ぶ．自己ループの扱いは self_loop_policy に従う
+    pub fn scc_report(&self) -> SccReport<'_, T> {
+        let nodes = self.nodes_by_id();
+        let skip_self_loops = self.ignores_self_loops();
+
+        let mut sccs: Vec<Vec<NodeID>> = self
+            .core
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|c| self.core.is_nontrivial_component(c, skip_self_loops))
+            .collect();
+        sccs.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+
+        let components = sccs
+            .iter()
+            .map(|c| {
+                let cycle: Vec<&T> = self
+                    .core
+                    .cycle_in_component(c, skip_self_loops)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|id| nodes[id])
+                    .collect();
+
+                SccComponent {
+                    size: c.len(),
+                    members: c.iter().map(|id| nodes[id]).collect(),
+                    internal_edges: self.core.internal_edge_count(c),
+                    cycle_provenance: self.cycle_provenance(&cycle),
+                    cycle,
+                }
+            })
+            .collect();
+
+        SccReport { components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{graph::Graph, provenance::Provenance};
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+
+        let mut sccs = g.strongly_connected_components();
+        sccs.sort_by_key(|c| c.len());
+        assert_eq!(sccs.len(), 3);
+        assert_eq!(sccs[2], vec![&"A", &"B"]);
+    }
+
+    #[test]
+    fn test_scc_report() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "X", "Y", "Z"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"A");
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"X", &"Y");
+        let _ = g.add_edge(&"Y", &"X");
+        let _ = g.add_edge(&"Z", &"Z"); // 自己ループ
+        let _ = g.add_edge(&"C", &"X");
+
+        let report = g.scc_report();
+        assert_eq!(report.components.len(), 3);
+
+        let first = &report.components[0];
+        assert_eq!(first.size, 3);
+        assert_eq!(first.members, vec![&"A", &"B", &"C"]);
+        assert_eq!(first.internal_edges, 4);
+        assert_eq!(first.cycle, vec![&"A", &"C", &"A"]);
+
+        assert_eq!(report.components[1].members, vec![&"X", &"Y"]);
+        assert_eq!(report.components[1].internal_edges, 2);
+        assert_eq!(report.components[2].cycle, vec![&"Z", &"Z"]);
+    }
+
+    #[test]
+    fn test_scc_report_to_json() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_edge_with_provenance(&"A", &"B", Provenance::new("a.rs", 1, "use"));
+        let _ = g.add_edge(&"B", &"A");
+
+        assert_eq!(
+            g.scc_report().to_json(),
+            r#"{"components":[{"size":2,"members":["A","B"],"internal_edges":2,"cycle":["A","B","A"],"cycle_provenance":[[{"file":"a.rs","line":1,"rule":"use"}],[]]}]}"#
+        );
+        assert_eq!(
+            Graph::<&str>::new().scc_report().to_json(),
+            r#"{"components":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_detect_cycles() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E", "F"] {
+            let _ = g.add_node(u);
+        }
+        // {A, B, C} の成分，D の自己ループ，{E, F} の成分
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"A");
+        let _ = g.add_edge(&"B", &"A");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"D", &"D");
+        let _ = g.add_edge(&"E", &"F");
+        let _ = g.add_edge(&"F", &"E");
+
+        assert_eq!(
+            g.detect_cycles(),
+            vec![
+                vec![&"A", &"B", &"A"],
+                vec![&"D", &"D"],
+                vec![&"E", &"F", &"E"],
+            ]
+        );
+        assert!(Graph::<u32>::new().detect_cycles().is_empty());
+    }
+}
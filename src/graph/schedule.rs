@@ -0,0 +1,238 @@
+use std::{
+    collections::{BinaryHeap, HashMap},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    dijkstra::MinScored,
+    error::GraphError,
+    graph::Graph,
+};
+
+/// ワーカーに割り当てられた 1 つのタスクの実行区間 [start, end)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledTask<'a, T> {
+    pub node: &'a T,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// `list_schedule` の結果．`workers[i]` はワーカー i が実行したタスクの開始時刻順の列
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule<'a, T> {
+    pub makespan: f64,
+    pub workers: Vec<Vec<ScheduledTask<'a, T>>>,
+}
+
+impl<T> Schedule<'_, T> {
+    // ワーカーが動いていた時間の合計 / (ワーカー数 × makespan)
+    pub fn utilization(&self) -> f64 {
+        if self.makespan <= 0.0 {
+            return 0.0;
+        }
+        let busy: f64 = self.workers.iter().flatten().map(|t| t.end - t.start).sum();
+        busy / (self.workers.len() as f64 * self.makespan)
+    }
+}
+
+impl GraphCore {
+    // リストスケジューリングのシミュレーション．ワーカーが空くたびに，実行可能なタスクのうち
+    // 残りの最長パス (自身を含む) が最も長いものを割り当てる．DAG であること，
+    // duration は非負であること．(タスク, ワーカー, 開始時刻, 終了時刻) を開始時刻順に返す
+    pub fn list_schedule<F>(
+        &self,
+        order: &[NodeID],
+        workers: usize,
+        duration: F,
+    ) -> Vec<(NodeID, usize, f64, f64)>
+    where
+        F: Fn(NodeID) -> f64,
+    {
+        // 優先度 = 自身から始まる最長パスの長さ
+        let mut level: HashMap<NodeID, f64> = HashMap::new();
+        for &id in order.iter().rev() {
+            let tail = self.nodes_dict[&id]
+                .children
+                .iter()
+                .map(|c| level[c])
+                .fold(0.0, f64::max);
+            level.insert(id, duration(id) + tail);
+        }
+
+        let mut in_degree: HashMap<NodeID, usize> = order
+            .iter()
+            .map(|&id| (id, self.nodes_dict[&id].parents.len()))
+            .collect();
+        let mut ready: BinaryHeap<MinScored> = order
+            .iter()
+            .filter(|id| in_degree[id] == 0)
+            .map(|&id| MinScored(-level[&id], id))
+            .collect();
+        let mut running: BinaryHeap<MinScored> = BinaryHeap::new();
+        let mut worker_of: HashMap<NodeID, usize> = HashMap::new();
+        let mut idle: Vec<usize> = (0..workers).rev().collect(); // 末尾から取るので番号の小さい順
+        let mut now = 0.0;
+        let mut ret = Vec::with_capacity(order.len());
+
+        loop {
+            while !idle.is_empty() {
+                let Some(MinScored(_, id)) = ready.pop() else {
+                    break;
+                };
+                let w = idle.pop().unwrap();
+                let end = now + duration(id);
+                worker_of.insert(id, w);
+                running.push(MinScored(end, id));
+                ret.push((id, w, now, end));
+            }
+
+            // 同じ時刻に終わるタスクをまとめて終わらせる
+            let Some(MinScored(end, id)) = running.pop() else {
+                break;
+            };
+            now = end;
+            let mut finished = vec![id];
+            while let Some(&MinScored(e, other)) = running.peek() {
+                if e > now {
+                    break;
+                }
+                running.pop();
+                finished.push(other);
+            }
+
+            for id in finished {
+                idle.push(worker_of[&id]);
+                for c in &self.nodes_dict[&id].children {
+                    let d = in_degree.get_mut(c).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        ready.push(MinScored(-level[c], *c));
+                    }
+                }
+            }
+            idle.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // ノードをタスク，エッジを依存関係 (from が終わってから to を始める) として，
+    // `workers` 人のワーカーでの実行をシミュレーションする．
+    // サイクル，ワーカー数 0，負または有限でない所要時間はエラー
+    pub fn list_schedule<F>(
+        &self,
+        workers: usize,
+        duration: F,
+    ) -> Result<Schedule<'_, T>, GraphError>
+    where
+        F: Fn(&T) -> f64,
+    {
+        if workers == 0 {
+            return Err(GraphError::InvalidArgument(
+                "worker count must be positive".to_string(),
+            ));
+        }
+        let Some(order) = self.core.topological_order() else {
+            return Err(GraphError::CycleDetected {
+                cycle: self.handles(&self.core.detect_cycle().unwrap()),
+            });
+        };
+
+        let nodes = self.nodes_by_id();
+        let mut durations: HashMap<NodeID, f64> = HashMap::new();
+        for &id in &order {
+            let d = duration(nodes[&id]);
+            if !(d.is_finite() && d >= 0.0) {
+                return Err(GraphError::InvalidArgument(format!(
+                    "duration of node #{} must be finite and non-negative, got {}",
+                    id, d
+                )));
+            }
+            durations.insert(id, d);
+        }
+
+        let mut timelines: Vec<Vec<ScheduledTask<'_, T>>> =
+            (0..workers).map(|_| Vec::new()).collect();
+        let mut makespan: f64 = 0.0;
+        for (id, w, start, end) in self
+            .core
+            .list_schedule(&order, workers, |id| durations[&id])
+        {
+            makespan = makespan.max(end);
+            timelines[w].push(ScheduledTask {
+                node: nodes[&id],
+                start,
+                end,
+            });
+        }
+
+        Ok(Schedule {
+            makespan,
+            workers: timelines,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph};
+
+    #[test]
+    fn test_list_schedule() {
+        // A(2) → C(3),  B(1) → C,  B → D(4)
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "C"), ("B", "C"), ("B", "D")] {
+            let _ = g.add_edge(&a, &b);
+        }
+        let duration = |u: &&str| match *u {
+            "A" => 2.0,
+            "B" => 1.0,
+            "C" => 3.0,
+            _ => 4.0,
+        };
+
+        // 1 人なら合計時間
+        let serial = g.list_schedule(1, duration).unwrap();
+        assert_eq!(serial.makespan, 10.0);
+        assert_eq!(serial.utilization(), 1.0);
+
+        // A と B は残りがどちらも 5 なので A が先にワーカー 0 に入る
+        let s = g.list_schedule(2, duration).unwrap();
+        assert_eq!(s.makespan, 5.0);
+        let starts: Vec<Vec<(&str, f64)>> = s
+            .workers
+            .iter()
+            .map(|ts| ts.iter().map(|t| (*t.node, t.start)).collect())
+            .collect();
+        assert_eq!(
+            starts,
+            vec![vec![("A", 0.0), ("C", 2.0)], vec![("B", 0.0), ("D", 1.0)]]
+        );
+    }
+
+    #[test]
+    fn test_list_schedule_errors() {
+        let mut g = Graph::new();
+        let _ = g.add_node(1);
+        let _ = g.add_node(2);
+        let _ = g.add_edge(&1, &2);
+        assert!(matches!(
+            g.list_schedule(0, |_| 1.0),
+            Err(GraphError::InvalidArgument(_))
+        ));
+        assert!(g.list_schedule(1, |_| -1.0).is_err());
+
+        let _ = g.add_edge(&2, &1);
+        assert!(matches!(
+            g.list_schedule(2, |_| 1.0),
+            Err(GraphError::CycleDetected { cycle }) if cycle.len() == 3
+        ));
+    }
+}
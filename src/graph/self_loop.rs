@@ -0,0 +1,152 @@
+use std::{fmt::Debug, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+/// 自己ループ (u → u) の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfLoopPolicy {
+    // 追加でき，サイクルとして報告する (既定)
+    #[default]
+    Allow,
+    // 追加しようとするとエラーにする
+    Forbid,
+    // 追加できるが detect_cycle では報告しない
+    // (トポロジカル順など DAG であることが必要な処理では引き続きサイクルとして扱う)
+    IgnoreInCycles,
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    pub fn self_loop_policy(&self) -> SelfLoopPolicy {
+        self.self_loop_policy
+    }
+
+    // 自己ループの扱いを変える．Forbid にするとき既に自己ループがあればエラー (変更しない)
+    pub fn set_self_loop_policy(&mut self, policy: SelfLoopPolicy) -> Result<(), GraphError> {
+        if policy == SelfLoopPolicy::Forbid {
+            if let Some(&node) = self.self_loop_ids().first() {
+                return Err(GraphError::SelfLoopForbidden {
+                    node: self.handle(node),
+                });
+            }
+        }
+        self.self_loop_policy = policy;
+        Ok(())
+    }
+
+    // 自己ループを持つノード (ノードの追加順)
+    pub fn self_loops(&self) -> Vec<&T> {
+        self.nodes_from_ids(&self.self_loop_ids())
+    }
+
+    fn self_loop_ids(&self) -> Vec<NodeID> {
+        let mut ids: Vec<NodeID> = self
+            .core
+            .nodes_dict
+            .iter()
+            .filter(|(id, n)| n.children.contains(id))
+            .map(|(&id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    // サイクルを報告するときに自己ループを除くか
+    pub(super) fn ignores_self_loops(&self) -> bool {
+        self.self_loop_policy == SelfLoopPolicy::IgnoreInCycles
+    }
+
+    pub(super) fn check_self_loop(&self, from_id: NodeID, to_id: NodeID) -> Result<(), GraphError> {
+        if from_id == to_id && self.self_loop_policy == SelfLoopPolicy::Forbid {
+            return Err(GraphError::SelfLoopForbidden {
+                node: self.handle(from_id),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+    use super::SelfLoopPolicy;
+
+    #[test]
+    fn test_self_loop_policy() {
+        let mut g = Graph::new();
+        for u in ["A", "B"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"A");
+        let _ = g.add_edge(&"A", &"B");
+        assert_eq!(g.detect_cycle(), Some(vec![&"A", &"A"]));
+
+        assert!(matches!(
+            g.set_self_loop_policy(SelfLoopPolicy::Forbid),
+            Err(GraphError::SelfLoopForbidden { .. })
+        ));
+        assert_eq!(g.self_loop_policy(), SelfLoopPolicy::Allow);
+
+        // 自己ループはサイクルとして報告しないが，DAG として扱う処理はエラーのまま
+        let _ = g.set_self_loop_policy(SelfLoopPolicy::IgnoreInCycles);
+        assert_eq!(g.detect_cycle(), None);
+        assert!(g.topological_sort().is_err());
+        let _ = g.add_edge(&"B", &"A");
+        assert_eq!(g.detect_cycle().unwrap().len(), 3);
+
+        let _ = g.remove_edge(&"A", &"A");
+        assert_eq!(g.set_self_loop_policy(SelfLoopPolicy::Forbid), Ok(()));
+        let err = g.add_edge(&"B", &"B").unwrap_err();
+        assert_eq!(
+            err,
+            GraphError::SelfLoopForbidden {
+                node: NodeHandle::new(1, 0)
+            }
+        );
+        assert_eq!(
+            g.describe_error(&err),
+            "self-loop on node \"B\" is not allowed"
+        );
+        assert!(g.add_edge_acyclic(&"B", &"B").is_err());
+        assert!(g.self_loops().is_empty());
+    }
+
+    #[test]
+    fn test_ignore_self_loops_in_cycle_reports() {
+        // A → A, B → B と A → B → C → A
+        let mut g = Graph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "A"), ("B", "B"), ("A", "B"), ("B", "C"), ("C", "A")] {
+            let _ = g.add_edge(&a, &b);
+        }
+        assert_eq!(g.detect_cycles(), vec![vec![&"A", &"A"]]);
+
+        let _ = g.set_self_loop_policy(SelfLoopPolicy::IgnoreInCycles);
+        assert_eq!(g.detect_cycles(), vec![vec![&"A", &"B", &"C", &"A"]]);
+        assert_eq!(
+            g.scc_report().components[0].cycle,
+            vec![&"A", &"B", &"C", &"A"]
+        );
+        assert_eq!(g.cycles_up_to(3), vec![vec![&"A", &"B", &"C", &"A"]]);
+        assert_eq!(g.suggest_edges_to_break_cycles(), vec![(&"C", &"A")]);
+
+        // 自己ループだけの成分はサイクルを含まない
+        let _ = g.remove_edge(&"C", &"A");
+        assert!(g.detect_cycles().is_empty());
+        assert!(g.scc_report().components.is_empty());
+        assert!(g.cycles_up_to(3).is_empty());
+        assert!(g.suggest_edges_to_break_cycles().is_empty());
+        assert!(g.assert_no_new_cycles(&Graph::new()).is_ok());
+    }
+
+    #[test]
+    fn test_forbid_self_loops_in_extend() {
+        let mut g: Graph<&str> = Graph::new();
+        let _ = g.set_self_loop_policy(SelfLoopPolicy::Forbid);
+        g.extend([("A", "B"), ("B", "B")]);
+        assert!(g.contains_node(&"B"));
+        assert!(g.self_loops().is_empty());
+        assert_eq!(g.edge_count(), 1);
+    }
+}
@@ -0,0 +1,219 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Path as UrlPath, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+use super::{core::NodeID, format::json_quote, graph::Graph};
+
+/// 読み込んだグラフを HTTP で問い合わせられるようにするサーバ
+///
+/// どのエンドポイントも JSON を返す (エラーは `{"error": ...}`)．
+///
+/// - `GET /neighbors/{node}`: 子ノードと親ノード
+/// - `GET /path?from=A&to=B`: 重み付きの最短経路
+/// - `GET /cycles`: サイクルを含む強連結成分ごとの代表サイクル
+/// - `GET /stats`: グラフの基本的な統計量
+#[derive(Clone)]
+pub struct GraphServer {
+    graph: Arc<Graph<String>>,
+}
+
+// 失敗したときの応答
+fn error(status: StatusCode, message: &str) -> Response {
+    let body = format!("{{\"error\":{}}}", json_quote(message));
+    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+fn json(body: String) -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+fn json_list(nodes: &[&String]) -> String {
+    let items: Vec<String> = nodes.iter().map(|u| json_quote(u)).collect();
+    format!("[{}]", items.join(","))
+}
+
+// JSON には inf や NaN を書けないので null にする
+fn json_number(x: f64) -> String {
+    if x.is_finite() {
+        x.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+impl GraphServer {
+    pub fn new(graph: Graph<String>) -> Self {
+        Self {
+            graph: Arc::new(graph),
+        }
+    }
+
+    pub fn graph(&self) -> &Graph<String> {
+        &self.graph
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/neighbors/:node", get(neighbors))
+            .route("/path", get(shortest_path))
+            .route("/cycles", get(cycles))
+            .route("/stats", get(stats))
+            .with_state(self.clone())
+    }
+
+    // `addr` で待ち受けて応答し続ける．tokio のランタイムの中で呼ぶ
+    pub async fn serve<A: ToSocketAddrs>(self, addr: A) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        axum::serve(listener, self.router()).await
+    }
+}
+
+async fn neighbors(State(s): State<GraphServer>, UrlPath(node): UrlPath<String>) -> Response {
+    let g = s.graph();
+    let Some(&id) = g.node_id(node.as_str()) else {
+        return error(StatusCode::NOT_FOUND, &format!("node {:?} not found", node));
+    };
+
+    let mut children: Vec<NodeID> = g.core.children_of(id);
+    children.sort_unstable();
+    json(format!(
+        "{{\"node\":{},\"children\":{},\"parents\":{}}}",
+        json_quote(&node),
+        json_list(&g.nodes_from_ids(&children)),
+        json_list(&g.predecessors(&node))
+    ))
+}
+
+async fn shortest_path(
+    State(s): State<GraphServer>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let g = s.graph();
+    let (Some(from), Some(to)) = (params.get("from"), params.get("to")) else {
+        return error(StatusCode::BAD_REQUEST, "`from` and `to` are required");
+    };
+    for u in [from, to] {
+        if !g.contains_node(u) {
+            return error(StatusCode::NOT_FOUND, &format!("node {:?} not found", u));
+        }
+    }
+
+    match g.shortest_path_dijkstra(from, to) {
+        Some(p) => json(format!(
+            "{{\"nodes\":{},\"weight\":{}}}",
+            json_list(p.nodes()),
+            json_number(p.weight())
+        )),
+        None => error(
+            StatusCode::NOT_FOUND,
+            &format!("no path from {:?} to {:?}", from, to),
+        ),
+    }
+}
+
+async fn cycles(State(s): State<GraphServer>) -> Response {
+    let cycles: Vec<String> = s
+        .graph()
+        .detect_cycles()
+        .iter()
+        .map(|c| json_list(c))
+        .collect();
+    json(format!("{{\"cycles\":[{}]}}", cycles.join(",")))
+}
+
+async fn stats(State(s): State<GraphServer>) -> Response {
+    let st = s.graph().stats();
+    json(format!(
+        "{{\"node_count\":{},\"edge_count\":{},\"density\":{},\"max_degree\":{},\"min_degree\":{},\"is_dag\":{}}}",
+        st.node_count,
+        st.edge_count,
+        json_number(st.density),
+        st.max_degree,
+        st.min_degree,
+        st.is_dag
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    use tokio::net::TcpListener;
+
+    use super::super::graph::Graph;
+    use super::GraphServer;
+
+    // HTTP/1.1 で GET し，ステータス行と本文を返す
+    fn get(addr: std::net::SocketAddr, path: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    #[test]
+    fn test_graph_server() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.add_weighted_edge("A", "B", 1.0);
+        let _ = g.add_weighted_edge("B", "C", 1.0);
+        let _ = g.add_weighted_edge("A", "C", 5.0);
+        let _ = g.add_edge("C", "A");
+        let _ = g.add_weighted_edge("C", "D", f64::INFINITY);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let listener = rt.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = GraphServer::new(g).router();
+        rt.spawn(async move { axum::serve(listener, router).await });
+
+        let (status, body) = get(addr, "/neighbors/A");
+        assert!(status.ends_with("200 OK"), "{status}");
+        assert_eq!(body, r#"{"node":"A","children":["B","C"],"parents":["C"]}"#);
+
+        let (_, body) = get(addr, "/path?from=A&to=C");
+        assert_eq!(body, r#"{"nodes":["A","B","C"],"weight":2}"#);
+
+        // 重みが有限でなければ null
+        let (_, body) = get(addr, "/path?from=B&to=D");
+        assert_eq!(body, r#"{"nodes":["B","C","D"],"weight":null}"#);
+
+        let (status, _) = get(addr, "/path?from=D&to=A");
+        assert!(status.ends_with("404 Not Found"), "{status}");
+        let (status, _) = get(addr, "/path?from=A");
+        assert!(status.ends_with("400 Bad Request"), "{status}");
+        let (status, body) = get(addr, "/neighbors/X");
+        assert!(status.ends_with("404 Not Found"), "{status}");
+        assert_eq!(body, r#"{"error":"node \"X\" not found"}"#);
+
+        let (_, body) = get(addr, "/cycles");
+        assert_eq!(body, r#"{"cycles":[["A","C","A"]]}"#);
+
+        let (_, body) = get(addr, "/stats");
+        assert!(
+            body.starts_with(r#"{"node_count":4,"edge_count":5,"#),
+            "{body}"
+        );
+        assert!(body.ends_with(r#""is_dag":false}"#), "{body}");
+    }
+}
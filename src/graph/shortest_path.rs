@@ -0,0 +1,116 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+    path::Path,
+};
+
+impl GraphCore {
+    // BFS で辺数が最小の経路を求める
+    pub fn bfs_path(&self, from: NodeID, to: NodeID) -> Option<Vec<NodeID>> {
+        if !self.nodes_dict.contains_key(&from) || !self.nodes_dict.contains_key(&to) {
+            return None;
+        }
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        let mut parent: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(node) = queue.pop_front() {
+            for child in self.children_of(node) {
+                if child == from || parent.contains_key(&child) {
+                    continue;
+                }
+                parent.insert(child, node);
+
+                if child == to {
+                    let mut path = vec![to];
+                    let mut cur = to;
+                    while cur != from {
+                        cur = parent[&cur];
+                        path.push(cur);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(child);
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 重みを考えない最短経路 (辺数が最小の経路)
+    pub fn shortest_path<Q>(&self, from: &Q, to: &Q) -> Option<Path<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = *self.node_id(from)?;
+        let to_id = *self.node_id(to)?;
+
+        let ids = self.core.bfs_path(from_id, to_id)?;
+        Some(self.path_from_ids(&ids))
+    }
+
+    // NodeID の列から Path を作る．重みは経路上のエッジの重みの合計
+    pub(super) fn path_from_ids(&self, ids: &[NodeID]) -> Path<'_, T> {
+        let nodes = self.nodes_by_id();
+
+        Path::new(
+            ids.iter().map(|id| nodes[id]).collect(),
+            ids.windows(2).map(|w| self.weight_of(w[0], w[1])).sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_shortest_path() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"C");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"A", &"C");
+
+        let p = g.shortest_path(&"A", &"D").unwrap();
+        assert_eq!(p.nodes(), &[&"A", &"C", &"D"]);
+        assert_eq!(p.weight(), 2.0);
+
+        assert_eq!(g.shortest_path(&"A", &"A").unwrap().nodes(), &[&"A"]);
+        assert!(g.shortest_path(&"D", &"A").is_none());
+        assert!(g.shortest_path(&"A", &"E").is_none());
+        assert!(g.shortest_path(&"A", &"Z").is_none());
+    }
+
+    #[test]
+    fn test_shortest_path_with_cycle() {
+        let mut g = Graph::new();
+        for i in 0..4 {
+            let _ = g.add_node(i);
+        }
+        let _ = g.add_edge(&0, &1);
+        let _ = g.add_edge(&1, &0);
+        let _ = g.add_edge(&1, &2);
+        let _ = g.add_edge(&2, &3);
+
+        let p = g.shortest_path(&0, &3).unwrap();
+        assert_eq!(p.nodes(), &[&0, &1, &2, &3]);
+        assert_eq!(p.edge_count(), 3);
+    }
+}
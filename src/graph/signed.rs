@@ -0,0 +1,250 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, handle::NodeHandle};
+
+/// エッジの符号 (信頼 / 敵対など)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Positive,
+    Negative,
+}
+
+/// `frustrated_edges` の結果
+///
+/// ノードを 2 つのグループに分けたとき，同じグループ内の負のエッジと
+/// グループをまたぐ正のエッジが「不満」なエッジになる
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrustrationReport<'a, T> {
+    pub groups: (Vec<&'a T>, Vec<&'a T>),
+    pub frustrated: Vec<(&'a T, &'a T, Sign)>,
+}
+
+/// エッジが正負の符号を持つグラフ．バランスの判定ではエッジの向きを無視する
+pub struct SignedGraph<T: PartialEq + Eq + Hash> {
+    graph: Graph<T>,
+    signs: HashMap<(NodeID, NodeID), Sign>,
+}
+
+impl<T: PartialEq + Eq + Hash> Default for SignedGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> SignedGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            signs: HashMap::new(),
+        }
+    }
+
+    // 符号を除いたグラフ
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    pub fn add_node(&mut self, u: T) -> Result<NodeHandle, GraphError> {
+        self.graph.add_node(u)
+    }
+
+    // 符号付きのエッジを追加する．すでにあれば符号を上書きして false を返す
+    pub fn add_edge<Q>(&mut self, u_from: &Q, u_to: &Q, sign: Sign) -> Result<bool, GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ret = self.graph.add_edge(u_from, u_to)?;
+
+        self.signs.insert(
+            (
+                *self.graph.node_id(u_from).unwrap(),
+                *self.graph.node_id(u_to).unwrap(),
+            ),
+            sign,
+        );
+        Ok(ret)
+    }
+
+    pub fn sign<Q>(&self, u_from: &Q, u_to: &Q) -> Option<Sign>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let from_id = self.graph.node_id(u_from)?;
+        let to_id = self.graph.node_id(u_to)?;
+        self.signs.get(&(*from_id, *to_id)).copied()
+    }
+
+    // (from, to, 符号) を ID 順に
+    fn signed_edges(&self) -> Vec<(NodeID, NodeID, Sign)> {
+        let mut edges: Vec<(NodeID, NodeID, Sign)> =
+            self.signs.iter().map(|(&(a, b), &s)| (a, b, s)).collect();
+        edges.sort_unstable_by_key(|&(a, b, _)| (a, b));
+        edges
+    }
+
+    // 全域木に沿って，正のエッジは同じグループ，負のエッジは別のグループになるよう塗る．
+    // バランスしていればこの塗り分けで不満なエッジは無くなる
+    fn spanning_coloring(&self, edges: &[(NodeID, NodeID, Sign)]) -> HashMap<NodeID, bool> {
+        let mut adj: HashMap<NodeID, Vec<(NodeID, Sign)>> = HashMap::new();
+        for &(a, b, s) in edges {
+            adj.entry(a).or_default().push((b, s));
+            adj.entry(b).or_default().push((a, s));
+        }
+
+        let mut ids: Vec<NodeID> = self.graph.core.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        let mut color: HashMap<NodeID, bool> = HashMap::new();
+        for root in ids {
+            if color.contains_key(&root) {
+                continue;
+            }
+            color.insert(root, false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(u) = queue.pop_front() {
+                for &(v, s) in adj.get(&u).into_iter().flatten() {
+                    if !color.contains_key(&v) {
+                        color.insert(v, color[&u] ^ (s == Sign::Negative));
+                        queue.push_back(v);
+                    }
+                }
+            }
+        }
+        color
+    }
+
+    // 負のエッジを奇数本含むサイクル (向きは無視) が無いか
+    pub fn is_balanced(&self) -> bool {
+        let edges = self.signed_edges();
+        let color = self.spanning_coloring(&edges);
+        edges
+            .iter()
+            .all(|&(a, b, s)| !is_frustrated(&color, a, b, s))
+    }
+
+    // 不満なエッジが少なくなるようにノードを 2 つに分け，残った不満なエッジを報告する．
+    // 全域木による塗り分けから始め，1 ノードずつ反転して減る間は続ける局所探索なので
+    // 最小 (frustration index) とは限らない．バランスしていれば不満なエッジは無い
+    pub fn frustrated_edges(&self) -> FrustrationReport<'_, T> {
+        let edges = self.signed_edges();
+        let mut color = self.spanning_coloring(&edges);
+
+        let mut incident: HashMap<NodeID, Vec<usize>> = HashMap::new();
+        for (i, &(a, b, _)) in edges.iter().enumerate() {
+            incident.entry(a).or_default().push(i);
+            if a != b {
+                incident.entry(b).or_default().push(i);
+            }
+        }
+        let mut ids: Vec<NodeID> = color.keys().copied().collect();
+        ids.sort_unstable();
+
+        // 反転するたびに不満なエッジの総数が減るので必ず止まる
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for &id in &ids {
+                let Some(es) = incident.get(&id) else {
+                    continue;
+                };
+                let count = |color: &HashMap<NodeID, bool>| {
+                    es.iter()
+                        .filter(|&&i| {
+                            let (a, b, s) = edges[i];
+                            is_frustrated(color, a, b, s)
+                        })
+                        .count()
+                };
+                let before = count(&color);
+                *color.get_mut(&id).unwrap() ^= true;
+                if count(&color) < before {
+                    improved = true;
+                } else {
+                    *color.get_mut(&id).unwrap() ^= true;
+                }
+            }
+        }
+
+        let (left, right): (Vec<NodeID>, Vec<NodeID>) = ids.into_iter().partition(|id| !color[id]);
+        let nodes = self.graph.nodes_by_id();
+        FrustrationReport {
+            groups: (
+                self.graph.nodes_from_ids(&left),
+                self.graph.nodes_from_ids(&right),
+            ),
+            frustrated: edges
+                .iter()
+                .filter(|&&(a, b, s)| is_frustrated(&color, a, b, s))
+                .map(|&(a, b, s)| (nodes[&a], nodes[&b], s))
+                .collect(),
+        }
+    }
+}
+
+fn is_frustrated(color: &HashMap<NodeID, bool>, a: NodeID, b: NodeID, sign: Sign) -> bool {
+    let same = color[&a] == color[&b];
+    match sign {
+        Sign::Positive => !same,
+        Sign::Negative => same,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sign, SignedGraph};
+
+    fn sample() -> SignedGraph<&'static str> {
+        // {A, B} と {C, D} が対立している
+        let mut g = SignedGraph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B", Sign::Positive);
+        let _ = g.add_edge(&"C", &"D", Sign::Positive);
+        let _ = g.add_edge(&"A", &"C", Sign::Negative);
+        let _ = g.add_edge(&"D", &"B", Sign::Negative);
+        g
+    }
+
+    #[test]
+    fn test_is_balanced() {
+        let mut g = sample();
+        assert!(g.is_balanced());
+        assert_eq!(g.sign(&"A", &"C"), Some(Sign::Negative));
+        assert_eq!(g.sign(&"C", &"A"), None);
+
+        let report = g.frustrated_edges();
+        assert!(report.frustrated.is_empty());
+        assert_eq!(report.groups, (vec![&"A", &"B"], vec![&"C", &"D"]));
+
+        // グループをまたぐ負のエッジは増やしてもよいが，正のエッジがまたぐと崩れる
+        let _ = g.add_edge(&"B", &"C", Sign::Negative);
+        assert!(g.is_balanced());
+        let _ = g.add_edge(&"A", &"D", Sign::Positive);
+        assert!(!g.is_balanced());
+    }
+
+    #[test]
+    fn test_frustrated_edges() {
+        let mut g = sample();
+        // 味方のはずの A と B の間にもう 1 本の負のエッジ
+        let _ = g.add_edge(&"B", &"A", Sign::Negative);
+        assert!(!g.is_balanced());
+
+        let report = g.frustrated_edges();
+        assert_eq!(report.frustrated, vec![(&"B", &"A", Sign::Negative)]);
+        assert_eq!(report.groups.0.len() + report.groups.1.len(), 4);
+
+        let mut looped = SignedGraph::new();
+        let _ = looped.add_node(0);
+        let _ = looped.add_edge(&0, &0, Sign::Negative);
+        assert!(!looped.is_balanced());
+        assert_eq!(looped.frustrated_edges().frustrated.len(), 1);
+    }
+}
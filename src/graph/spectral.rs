@@ -0,0 +1,217 @@
+use std::hash::Hash;
+
+use super::{error::GraphError, graph::Graph};
+
+// 対称行列の固有値分解 (巡回 Jacobi 法)．固有値の昇順に (固有値, 固有ベクトル) を返す
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> Vec<(f64, Vec<f64>)> {
+    let n = a.len();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-20 {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                // theta = 0 のときも signum は 1 になる
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut ret: Vec<(f64, Vec<f64>)> = (0..n)
+        .map(|j| (a[j][j], v.iter().map(|row| row[j]).collect()))
+        .collect();
+    ret.sort_by(|x, y| x.0.total_cmp(&y.0));
+    ret
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+// k-means．初期の中心は最遠点法で決めるので結果は決定的
+fn k_means(points: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let mut centers = vec![points[0].clone()];
+    while centers.len() < k {
+        let far = (0..points.len())
+            .max_by(|&i, &j| {
+                let di = centers
+                    .iter()
+                    .map(|c| squared_distance(&points[i], c))
+                    .fold(f64::MAX, f64::min);
+                let dj = centers
+                    .iter()
+                    .map(|c| squared_distance(&points[j], c))
+                    .fold(f64::MAX, f64::min);
+                di.total_cmp(&dj).then(j.cmp(&i))
+            })
+            .unwrap();
+        centers.push(points[far].clone());
+    }
+
+    let mut assign = vec![0; points.len()];
+    for _ in 0..100 {
+        let next: Vec<usize> = points
+            .iter()
+            .map(|p| {
+                (0..k)
+                    .min_by(|&a, &b| {
+                        squared_distance(p, &centers[a])
+                            .total_cmp(&squared_distance(p, &centers[b]))
+                    })
+                    .unwrap()
+            })
+            .collect();
+        if next == assign {
+            break;
+        }
+        assign = next;
+
+        for (c, center) in centers.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = points
+                .iter()
+                .zip(&assign)
+                .filter(|(_, &a)| a == c)
+                .map(|(p, _)| p)
+                .collect();
+            if members.is_empty() {
+                continue; // 空のクラスタは中心を動かさない
+            }
+            for (d, x) in center.iter_mut().enumerate() {
+                *x = members.iter().map(|p| p[d]).sum::<f64>() / members.len() as f64;
+            }
+        }
+    }
+    assign
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 正規化ラプラシアンの固有ベクトルを使ったスペクトラルクラスタリング (Ng–Jordan–Weiss)．
+    // エッジの向きは無視し，重みは両方向の平均を使う．クラスタは最初のノードの追加順に並ぶ．
+    // 固有値分解は密行列で行うので数百ノード程度までを想定している
+    pub fn spectral_clusters(&self, k: usize) -> Result<Vec<Vec<&T>>, GraphError> {
+        let (nodes, weights) = self.weight_matrix();
+        let n = nodes.len();
+        if k == 0 || k > n {
+            return Err(GraphError::InvalidArgument(format!(
+                "k must be between 1 and {} (node count), got {}",
+                n, k
+            )));
+        }
+
+        // 対称化した重みと次数
+        let w: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (weights[i][j] + weights[j][i]) / 2.0)
+                    .collect()
+            })
+            .collect();
+        let inv_sqrt_degree: Vec<f64> = w
+            .iter()
+            .map(|row| row.iter().sum::<f64>())
+            .map(|d| if d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+            .collect();
+
+        // L = I - D^{-1/2} W D^{-1/2}
+        let laplacian: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let identity = if i == j { 1.0 } else { 0.0 };
+                        identity - inv_sqrt_degree[i] * w[i][j] * inv_sqrt_degree[j]
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // 小さい方から k 個の固有ベクトルを並べ，各行を正規化する
+        let eigen = symmetric_eigen(laplacian);
+        let points: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                let row: Vec<f64> = eigen[..k].iter().map(|(_, v)| v[i]).collect();
+                let norm = row.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    row.iter().map(|x| x / norm).collect()
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let assign = k_means(&points, k);
+        let mut clusters: Vec<Vec<&T>> = Vec::new();
+        let mut index: Vec<Option<usize>> = vec![None; k];
+        for (i, &c) in assign.iter().enumerate() {
+            let idx = *index[c].get_or_insert_with(|| {
+                clusters.push(Vec::new());
+                clusters.len() - 1
+            });
+            clusters[idx].push(nodes[i]);
+        }
+        Ok(clusters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::symmetric_eigen;
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let eigen = symmetric_eigen(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+        assert!((eigen[0].0 - 1.0).abs() < 1e-9);
+        assert!((eigen[1].0 - 3.0).abs() < 1e-9);
+        assert!((eigen[0].1[0] + eigen[0].1[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spectral_clusters() {
+        // 2 つの三角形を弱いエッジでつなぐ
+        let mut g = Graph::new();
+        for u in 0..6 {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            let _ = g.add_weighted_edge(&a, &b, 5.0);
+        }
+        let _ = g.add_weighted_edge(&2, &3, 0.1);
+
+        let clusters = g.spectral_clusters(2).unwrap();
+        assert_eq!(clusters, vec![vec![&0, &1, &2], vec![&3, &4, &5]]);
+        assert!(g.spectral_clusters(0).is_err());
+        assert!(g.spectral_clusters(7).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fmt::Debug, hash::Hash};
+
+use super::graph::Graph;
+
+/// グラフの基本的な統計量
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    // エッジ数 / (n (n - 1))．自己ループがあると 1 を超えることがある
+    pub density: f64,
+    // 次数は入次数と出次数の和 (自己ループは 2)．ノードが無ければ 0
+    pub max_degree: usize,
+    pub min_degree: usize,
+    pub is_dag: bool,
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    pub fn node_count(&self) -> usize {
+        self.core.nodes_dict.len()
+    }
+
+    // 多重グラフでは平行なエッジも 1 本ずつ数える
+    pub fn edge_count(&self) -> usize {
+        let distinct: usize = self
+            .core
+            .nodes_dict
+            .values()
+            .map(|n| n.children.len())
+            .sum();
+        distinct + self.multiplicity.values().map(|m| m - 1).sum::<usize>()
+    }
+
+    // 有向グラフとしての密度．ノードが 2 つ未満なら 0
+    pub fn density(&self) -> f64 {
+        let n = self.node_count();
+        if n < 2 {
+            0.0
+        } else {
+            self.edge_count() as f64 / (n * (n - 1)) as f64
+        }
+    }
+
+    pub fn stats(&self) -> GraphStats {
+        let degrees = self.degrees().map(|(_, i, o)| i + o);
+        let (min_degree, max_degree) = degrees
+            .fold(None, |acc, d| match acc {
+                None => Some((d, d)),
+                Some((lo, hi)) => Some((usize::min(lo, d), usize::max(hi, d))),
+            })
+            .unwrap_or((0, 0));
+
+        GraphStats {
+            node_count: self.node_count(),
+            edge_count: self.edge_count(),
+            density: self.density(),
+            max_degree,
+            min_degree,
+            is_dag: self.find_cycle().is_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::GraphStats;
+
+    #[test]
+    fn test_stats() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"B", &"C");
+
+        assert_eq!(
+            g.stats(),
+            GraphStats {
+                node_count: 4,
+                edge_count: 3,
+                density: 0.25,
+                max_degree: 2,
+                min_degree: 0,
+                is_dag: true,
+            }
+        );
+
+        let _ = g.add_edge(&"C", &"A");
+        assert!(!g.stats().is_dag);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let g = Graph::<u32>::new();
+        let stats = g.stats();
+        assert_eq!((stats.node_count, stats.edge_count), (0, 0));
+        assert_eq!(stats.density, 0.0);
+        assert_eq!((stats.min_degree, stats.max_degree), (0, 0));
+        assert!(stats.is_dag);
+    }
+}
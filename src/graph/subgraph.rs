@@ -0,0 +1,213 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // ノード集合 `ids` (ID の昇順) が誘導する部分グラフ．ノードと別名は `key` で作り，重み・ラベル・
+    // 出所・平行なエッジの本数と自己ループの扱いも引き継ぐ．`key` が同じ値を返すノードは
+    // 1 つにまとめ，まとめたノードの間のエッジは自己ループ (禁止されていれば落とす)，
+    // 重なったエッジは平行なエッジとして数え，重みとラベルは先に追加したノードのものを残す
+    pub(super) fn induced_subgraph<'a, U, F>(&'a self, ids: &[NodeID], key: F) -> Graph<U>
+    where
+        U: PartialEq + Eq + Hash,
+        F: Fn(&'a T) -> U,
+    {
+        let mut g = if self.multigraph {
+            Graph::new_multigraph()
+        } else {
+            Graph::new()
+        };
+        g.self_loop_policy = self.self_loop_policy;
+
+        let nodes = self.nodes_by_id();
+        let local: HashMap<NodeID, NodeID> = ids
+            .iter()
+            .map(|&id| (id, g.id_or_insert(key(nodes[&id]))))
+            .collect();
+        for (alias, id) in &self.aliases {
+            if let Some(&to) = local.get(id) {
+                let alias = key(alias);
+                if g.node_id(&alias).is_none() {
+                    g.aliases.insert(alias, to);
+                }
+            }
+        }
+
+        for &id in ids {
+            let mut children = self.core.children_of(id);
+            children.sort_unstable();
+            for child in children {
+                let Some(&to) = local.get(&child) else {
+                    continue;
+                };
+                let from_key = (id, child);
+                let to_key = (local[&id], to);
+                if g.check_self_loop(to_key.0, to_key.1).is_err() {
+                    continue;
+                }
+
+                let m = self.multiplicity.get(&from_key).copied().unwrap_or(1);
+                if let Some(p) = self.provenance.get(&from_key) {
+                    g.provenance
+                        .entry(to_key)
+                        .or_default()
+                        .extend(p.iter().cloned());
+                }
+                if let Ok(true) = g.core.add_edge(to_key.0, to_key.1) {
+                    if g.multigraph {
+                        *g.multiplicity.entry(to_key).or_insert(1) += m;
+                    }
+                    continue;
+                }
+                if m > 1 {
+                    g.multiplicity.insert(to_key, m);
+                }
+                if let Some(&w) = self.weights.get(&from_key) {
+                    g.weights.insert(to_key, w);
+                }
+                if let Some(l) = self.labels.get(&from_key) {
+                    g.labels.insert(to_key, l.clone());
+                }
+            }
+        }
+        g
+    }
+
+    // `nodes` が誘導する部分グラフ (両端が `nodes` にあるエッジだけを残す)．
+    // 登録されていないノードは無視し，ノードは元のグラフでの追加順に追加する．
+    // 残したノードの別名も引き継ぐ
+    pub fn subgraph<'a, I>(&'a self, nodes: I) -> Graph<T>
+    where
+        T: Clone + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let mut ids: Vec<NodeID> = nodes
+            .into_iter()
+            .filter_map(|u| self.node_id(u).copied())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        self.induced_subgraph(&ids, T::clone)
+    }
+
+    // ノードの値を `f` で変換したグラフ．エッジと重み・ラベル・出所・平行なエッジの本数と
+    // 別名 (`f` で変換したもの) を引き継ぐ．`f` が同じ値に写すノードは 1 つにまとめ，
+    // その間のエッジは自己ループになる (重みとラベルは先に追加したノードのものを残す)
+    pub fn map<U, F>(&self, f: F) -> Graph<U>
+    where
+        U: PartialEq + Eq + Hash,
+        F: Fn(&T) -> U,
+    {
+        let mut ids: Vec<NodeID> = self.core.nodes_dict.keys().copied().collect();
+        ids.sort_unstable();
+        self.induced_subgraph(&ids, f)
+    }
+
+    // map と同じだが，`f` が 2 つのノードを同じ値に写すと NodeAlreadyExists
+    // (ID は新しいグラフでのもの)
+    pub fn try_map<U, F>(&self, f: F) -> Result<Graph<U>, GraphError>
+    where
+        U: PartialEq + Eq + Hash,
+        F: Fn(&T) -> U,
+    {
+        let g = self.map(&f);
+        if g.node_count() == self.node_count() {
+            return Ok(g);
+        }
+
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let mut seen = HashSet::new();
+        for id in ids {
+            let &node = g.node_id(&f(nodes[&id])).unwrap(); // map で追加したノード
+            if !seen.insert(node) {
+                return Err(GraphError::NodeAlreadyExists {
+                    node: g.handle(node),
+                });
+            }
+        }
+        unreachable!("ノードが減ったならどこかで衝突している")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::super::{error::GraphError, graph::Graph, handle::NodeHandle};
+
+    #[test]
+    fn test_subgraph() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u.to_string());
+        }
+        let _ = g.add_weighted_edge("A", "B", 2.0);
+        let _ = g.add_edge_with_label("B", "C", "calls");
+        let _ = g.add_edge("C", "A");
+        let _ = g.add_edge("C", "D");
+        let _ = g.alias("A", "a");
+
+        let (a, b, c, x) = (
+            "A".to_string(),
+            "B".to_string(),
+            "C".to_string(),
+            "X".to_string(),
+        );
+        let sub = g.subgraph([&c, &a, &b, &a, &x]);
+        assert_eq!(sub.node_count(), 3);
+        assert_eq!(sub.edge_count(), 3);
+        assert_eq!(sub.edge_weight("A", "B"), Some(2.0));
+        assert_eq!(sub.edge_label("B", "C"), Some("calls"));
+        assert!(!sub.contains_node("D"));
+        assert!(sub.contains_edge("a", "B"));
+        assert!(sub.detect_cycle().is_some());
+
+        let sub = g.subgraph([&a, &c]);
+        assert!(sub.contains_edge("C", "A"));
+        assert!(sub.detect_cycle().is_none());
+        assert!(!g.subgraph([&b]).contains_node("a"));
+        assert_eq!(g.subgraph([]).node_count(), 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut g = Graph::new();
+        for u in [PathBuf::from("src/lib.rs"), PathBuf::from("src/graph.rs")] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge_with_label(Path::new("src/lib.rs"), Path::new("src/graph.rs"), "mod");
+
+        let _ = g.alias(Path::new("src/lib.rs"), Path::new("lib.rs"));
+        let names = g.map(|p| p.display().to_string());
+        assert_eq!(names.node_count(), 2);
+        assert_eq!(names.edge_label("src/lib.rs", "src/graph.rs"), Some("mod"));
+        assert_eq!(names.edge_weight("src/lib.rs", "src/graph.rs"), Some(1.0));
+
+        assert_eq!(
+            names.canonical("lib.rs").map(|u| u.as_str()),
+            Some("src/lib.rs")
+        );
+        assert_eq!(g.try_map(|p| p.display().to_string()), Ok(names));
+
+        // 同じ値に写したノードは 1 つにまとめ，その間のエッジは自己ループになる
+        let exts = g.map(|p| p.extension().map(|e| e.to_owned()));
+        assert_eq!(exts.node_count(), 1);
+        assert_eq!(exts.self_loops().len(), 1);
+        assert_eq!(
+            exts.edge_label(&Some("rs".into()), &Some("rs".into())),
+            Some("mod")
+        );
+        assert_eq!(
+            g.try_map(|p| p.extension().map(|e| e.to_owned())),
+            Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(0, 0)
+            })
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use super::{core::NodeID, error::GraphError, graph::Graph};
+
+// スーパーノードを MinHash でバケットに分けてまとめる回数 (回ごとにハッシュを変える)
+const ROUNDS: u64 = 8;
+// バケットの中で 1 つのスーパーノードと比べる相手の数の上限
+const MAX_CANDIDATES: usize = 8;
+
+/// スーパーノードと補正エッジによるグラフの要約
+///
+/// スーパーエッジ A → B は「A の各ノードから B の各ノードへエッジがある」ことを表し，
+/// 実際との差は `additions` (スーパーエッジで表されないエッジ) と
+/// `deletions` (スーパーエッジに含まれるが存在しないエッジ) で補正する．
+/// 誤差の上限が 0 なら補正を含めて元のエッジ集合を復元でき，正なら補正の一部を省く
+pub struct Summary<'a, T: PartialEq + Eq + Hash> {
+    pub supernodes: Vec<Vec<&'a T>>,
+    pub superedges: Vec<(usize, usize)>,
+    pub additions: Vec<(&'a T, &'a T)>,
+    pub deletions: Vec<(&'a T, &'a T)>,
+    edge_counts: HashMap<(usize, usize), usize>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> Summary<'a, T> {
+    // 要約の大きさ (スーパーエッジと補正エッジの数)．元のエッジ数と比べて圧縮率を見る
+    pub fn cost(&self) -> usize {
+        self.superedges.len() + self.additions.len() + self.deletions.len()
+    }
+
+    // 可視化用のスーパーノードのグラフ．ノードはスーパーノードの番号で，
+    // エッジはノードの組の間に 1 本でもエッジがあれば張り，重みはその本数
+    pub fn to_graph(&self) -> Graph<usize> {
+        let mut g = Graph::new();
+        for i in 0..self.supernodes.len() {
+            let _ = g.add_node(i);
+        }
+        for (&(a, b), &count) in &self.edge_counts {
+            let _ = g.add_weighted_edge(&a, &b, count as f64);
+        }
+        g
+    }
+
+    // 要約から元のエッジ集合を復元する (誤差の上限が正なら近似)
+    pub fn reconstruct(&self) -> HashSet<(&'a T, &'a T)> {
+        let mut edges: HashSet<(&T, &T)> = self
+            .superedges
+            .iter()
+            .flat_map(|&(a, b)| {
+                self.supernodes[a]
+                    .iter()
+                    .flat_map(move |&u| self.supernodes[b].iter().map(move |&v| (u, v)))
+            })
+            .collect();
+        for e in &self.deletions {
+            edges.remove(e);
+        }
+        edges.extend(self.additions.iter().copied());
+        edges
+    }
+}
+
+// 隣接ノード (出る側は true，入る側は false) のシード付きハッシュ
+fn seeded_hash(seed: u64, neighbor: (bool, NodeID)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (seed, neighbor).hash(&mut hasher);
+    hasher.finish()
+}
+
+// ノード → スーパーノードの対応とスーパーノードのメンバー．まとめられたスーパーノードは空になる
+struct Grouping {
+    group_of: HashMap<NodeID, usize>,
+    members: Vec<Vec<NodeID>>,
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // スーパーノード `a` (と，あれば `b` をまとめたもの) を表すのに要るスーパーエッジと補正の数．
+    // 隣のスーパーノード X との間に e 本あれば，スーパーエッジと欠けた分の削除 (|A||X| - e + 1) と
+    // 追加 (e) の少ない方で表す
+    fn encoding_cost(&self, grouping: &Grouping, a: usize, b: Option<usize>) -> usize {
+        let group = |id: &NodeID| match grouping.group_of[id] {
+            g if Some(g) == b => a,
+            g => g,
+        };
+        let size = |g: usize| match g {
+            g if g == a => grouping.members[a].len() + b.map_or(0, |b| grouping.members[b].len()),
+            g => grouping.members[g].len(),
+        };
+
+        let mut out_counts: HashMap<usize, usize> = HashMap::new();
+        let mut in_counts: HashMap<usize, usize> = HashMap::new();
+        let members = grouping.members[a]
+            .iter()
+            .chain(b.iter().flat_map(|&b| &grouping.members[b]));
+        for id in members {
+            let n = &self.core.nodes_dict[id];
+            for child in &n.children {
+                *out_counts.entry(group(child)).or_default() += 1;
+            }
+            // 自分の中のエッジは出る側で数えた
+            for parent in n.parents.iter().filter(|p| group(p) != a) {
+                *in_counts.entry(group(parent)).or_default() += 1;
+            }
+        }
+
+        out_counts
+            .into_iter()
+            .chain(in_counts)
+            .map(|(x, e)| e.min(size(a) * size(x) - e + 1))
+            .sum()
+    }
+
+    // 隣接ノードが似ているノードをまとめて要約する．
+    //
+    // 各回でスーパーノードを隣接ノードの集合の MinHash でバケットに分け，同じバケットの中で
+    // まとめると表すのに要るスーパーエッジと補正が減る組をまとめる．補正は実際のエッジから求める．
+    // `max_error` は復元の誤差の上限で，各ノードについて `reconstruct` で得る隣接ノードと
+    // 実際の隣接ノードの違いが次数の `max_error` 倍以下になる範囲で補正を省く．
+    // 0 なら補正をすべて残すので元のエッジ集合を復元できる．負か NaN なら InvalidArgument
+    pub fn summarize(&self, max_error: f64) -> Result<Summary<'_, T>, GraphError> {
+        if max_error.is_nan() || max_error < 0.0 {
+            return Err(GraphError::InvalidArgument(format!(
+                "max_error must be non-negative, got {}",
+                max_error
+            )));
+        }
+
+        let mut ids: Vec<NodeID> = self.id_dict.values().copied().collect();
+        ids.sort_unstable();
+        let mut grouping = Grouping {
+            group_of: ids.iter().enumerate().map(|(i, &id)| (id, i)).collect(),
+            members: ids.iter().map(|&id| vec![id]).collect(),
+        };
+
+        for seed in 0..ROUNDS {
+            // スーパーノードの隣接ノード (メンバーの隣接ノードの和集合) の MinHash でバケットに分ける
+            let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+            for (g, members) in grouping.members.iter().enumerate() {
+                if members.is_empty() {
+                    continue;
+                }
+                let minhash = members
+                    .iter()
+                    .flat_map(|id| {
+                        let n = &self.core.nodes_dict[id];
+                        let out = n.children.iter().map(|&c| (true, c));
+                        out.chain(n.parents.iter().map(|&p| (false, p)))
+                    })
+                    .map(|neighbor| seeded_hash(seed, neighbor))
+                    .min();
+                // 隣接ノードの無いノードはまとめても得をしない
+                if let Some(h) = minhash {
+                    buckets.entry(h).or_default().push(g);
+                }
+            }
+            let mut buckets: Vec<Vec<usize>> = buckets.into_values().collect();
+            buckets.sort_unstable();
+
+            for bucket in buckets {
+                let mut kept: Vec<usize> = Vec::new();
+                for g in bucket {
+                    let alone = self.encoding_cost(&grouping, g, None);
+                    let best = kept
+                        .iter()
+                        .rev()
+                        .take(MAX_CANDIDATES)
+                        .map(|&k| {
+                            let separate = alone + self.encoding_cost(&grouping, k, None);
+                            let merged = self.encoding_cost(&grouping, k, Some(g));
+                            (separate as isize - merged as isize, k)
+                        })
+                        .filter(|&(saving, _)| saving > 0)
+                        .max_by_key(|&(saving, k)| (saving, std::cmp::Reverse(k)));
+                    match best {
+                        Some((_, k)) => {
+                            let moved = std::mem::take(&mut grouping.members[g]);
+                            for id in &moved {
+                                grouping.group_of.insert(*id, k);
+                            }
+                            grouping.members[k].extend(moved);
+                        }
+                        None => kept.push(g),
+                    }
+                }
+            }
+        }
+
+        // スーパーノードはメンバーを ID 順に並べ，最初のメンバーの順に番号を振り直す
+        let mut groups: Vec<Vec<NodeID>> = grouping
+            .members
+            .into_iter()
+            .filter(|m| !m.is_empty())
+            .collect();
+        for g in groups.iter_mut() {
+            g.sort_unstable();
+        }
+        groups.sort_unstable_by_key(|g| g[0]);
+        let group_of: HashMap<NodeID, usize> = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(i, g)| g.iter().map(move |&id| (id, i)))
+            .collect();
+
+        let mut edges_between: HashMap<(usize, usize), Vec<(NodeID, NodeID)>> = HashMap::new();
+        for (&id, n) in self.core.nodes_dict.iter() {
+            for &child in &n.children {
+                edges_between
+                    .entry((group_of[&id], group_of[&child]))
+                    .or_default()
+                    .push((id, child));
+            }
+        }
+
+        // スーパーエッジにした方が補正が少なければスーパーエッジにする．
+        // 密な組の欠けたエッジは全組を数えるが，全組の数は実際のエッジの 2 倍未満
+        let mut superedges = Vec::new();
+        let mut corrections: Vec<(NodeID, NodeID, bool)> = Vec::new();
+        let mut pairs: Vec<(usize, usize)> = edges_between.keys().copied().collect();
+        pairs.sort_unstable();
+        for (a, b) in pairs {
+            let edges = &edges_between[&(a, b)];
+            let full = groups[a].len() * groups[b].len();
+            if full - edges.len() + 1 < edges.len() {
+                superedges.push((a, b));
+                let present: HashSet<&(NodeID, NodeID)> = edges.iter().collect();
+                for &u in &groups[a] {
+                    for &v in &groups[b] {
+                        if !present.contains(&(u, v)) {
+                            corrections.push((u, v, false));
+                        }
+                    }
+                }
+            } else {
+                corrections.extend(edges.iter().map(|&(u, v)| (u, v, true)));
+            }
+        }
+        corrections.sort_unstable();
+
+        // 両端のノードの誤差の予算 (次数の max_error 倍) が残っている補正を省く．
+        // 自己ループの補正は省かない
+        let mut budget: HashMap<NodeID, usize> = self
+            .core
+            .nodes_dict
+            .iter()
+            .map(|(&id, n)| {
+                let degree = n.children.len() + n.parents.len();
+                (id, (max_error * degree as f64).floor() as usize)
+            })
+            .collect();
+        let nodes = self.nodes_by_id();
+        let mut additions = Vec::new();
+        let mut deletions = Vec::new();
+        for (u, v, is_addition) in corrections {
+            if u != v && budget[&u] > 0 && budget[&v] > 0 {
+                *budget.get_mut(&u).unwrap() -= 1;
+                *budget.get_mut(&v).unwrap() -= 1;
+                continue;
+            }
+            if is_addition {
+                additions.push((nodes[&u], nodes[&v]));
+            } else {
+                deletions.push((nodes[&u], nodes[&v]));
+            }
+        }
+
+        Ok(Summary {
+            supernodes: groups
+                .iter()
+                .map(|g| g.iter().map(|id| nodes[id]).collect())
+                .collect(),
+            superedges,
+            additions,
+            deletions,
+            edge_counts: edges_between
+                .into_iter()
+                .map(|(pair, edges)| (pair, edges.len()))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::super::{
+        error::GraphError,
+        graph::Graph,
+        rng::{Rng, XorShift64},
+    };
+
+    // 1..=3 はすべて 10, 11 を指す．4 は 10 だけを指す
+    fn sample() -> Graph<u32> {
+        let mut g = Graph::new();
+        for u in [1, 2, 3, 4, 10, 11] {
+            let _ = g.add_node(u);
+        }
+        for u in [1, 2, 3] {
+            let _ = g.add_edge(&u, &10);
+            let _ = g.add_edge(&u, &11);
+        }
+        let _ = g.add_edge(&4, &10);
+        g
+    }
+
+    // 各ノードについて，復元した隣接ノードと実際の隣接ノードの違いの数
+    fn errors<'a>(
+        g: &'a Graph<u32>,
+        reconstructed: &HashSet<(&'a u32, &'a u32)>,
+    ) -> Vec<(u32, usize, usize)> {
+        let actual = all_edges(g);
+        g.nodes()
+            .map(|u| {
+                let diff = actual
+                    .symmetric_difference(reconstructed)
+                    .filter(|(a, b)| *a == u || *b == u)
+                    .count();
+                let degree = actual.iter().filter(|(a, b)| *a == u || *b == u).count();
+                (*u, diff, degree)
+            })
+            .collect()
+    }
+
+    fn all_edges(g: &Graph<u32>) -> HashSet<(&u32, &u32)> {
+        g.edges().collect()
+    }
+
+    #[test]
+    fn test_summarize_lossless() {
+        let g = sample();
+        let s = g.summarize(0.0).unwrap();
+
+        // 1..=3 と 10, 11 がそれぞれまとまり，4 → 10 は追加の補正で表される
+        assert_eq!(
+            s.supernodes,
+            vec![vec![&1, &2, &3], vec![&4], vec![&10, &11]]
+        );
+        assert_eq!(s.superedges, vec![(0, 2)]);
+        assert_eq!(s.additions, vec![(&4, &10)]);
+        assert!(s.deletions.is_empty());
+        assert_eq!(s.cost(), 2);
+        assert_eq!(s.reconstruct(), all_edges(&g));
+        assert_eq!(s.to_graph().edge_weight(&0, &2), Some(6.0));
+        assert_eq!(s.to_graph().edge_weight(&1, &2), Some(1.0));
+    }
+
+    #[test]
+    fn test_summarize_with_error() {
+        let g = sample();
+        // 4 の次数は 1 なので 0.5 では補正を省けない
+        assert_eq!(g.summarize(0.5).unwrap().cost(), 2);
+
+        let s = g.summarize(1.0).unwrap();
+        assert_eq!(s.superedges, vec![(0, 2)]);
+        assert!(s.additions.is_empty() && s.deletions.is_empty());
+        assert_eq!(s.cost(), 1);
+        assert_eq!(s.reconstruct().len(), 6);
+
+        assert!(matches!(
+            g.summarize(-0.1),
+            Err(GraphError::InvalidArgument(_))
+        ));
+        assert!(g.summarize(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_summarize_error_bound() {
+        // 10 個のノードからなる 20 個の群．各群は次の群へほぼ密につながる
+        let mut rng = XorShift64::new(7);
+        let mut g = Graph::new();
+        for u in 0..200 {
+            let _ = g.add_node(u);
+        }
+        for u in 0..200u32 {
+            let next = (u / 10 + 1) % 20;
+            for v in next * 10..next * 10 + 10 {
+                if rng.next_f64() < 0.9 {
+                    let _ = g.add_edge(&u, &v);
+                }
+            }
+        }
+
+        let lossless = g.summarize(0.0).unwrap();
+        assert_eq!(lossless.reconstruct(), all_edges(&g));
+        assert!(lossless.cost() < g.edge_count() / 2);
+
+        for max_error in [0.1, 0.3] {
+            let s = g.summarize(max_error).unwrap();
+            assert!(s.cost() <= lossless.cost());
+            for (u, diff, degree) in errors(&g, &s.reconstruct()) {
+                assert!(
+                    diff as f64 <= max_error * degree as f64,
+                    "node {u}: {diff} errors for degree {degree}"
+                );
+            }
+        }
+    }
+}
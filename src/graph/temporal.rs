@@ -0,0 +1,254 @@
+use std::{borrow::Borrow, fmt::Debug, hash::Hash};
+
+use super::{core::NodeID, error::GraphError, graph::Graph, handle::NodeHandle};
+
+/// `analyze_windows` で計算する指標
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMetric {
+    Components,
+    Density,
+    Cycles,
+}
+
+/// 1 つの時間窓 [start, end) での指標．要求されなかった指標は None．
+/// start + 幅が u64 に収まらない最後の窓は end = u64::MAX で，時刻 u64::MAX も含む
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowStats {
+    pub start: u64,
+    pub end: u64,
+    // 窓の中のエッジに現れるノードとエッジの数
+    pub node_count: usize,
+    pub edge_count: usize,
+    // 弱連結成分の数
+    pub components: Option<usize>,
+    pub density: Option<f64>,
+    // サイクルを含む強連結成分の数
+    pub cyclic_components: Option<usize>,
+}
+
+/// エッジが時刻を持つグラフ．同じエッジが別の時刻に何度現れてもよい
+pub struct TemporalGraph<T: PartialEq + Eq + Hash> {
+    graph: Graph<T>,
+    events: Vec<(NodeID, NodeID, u64)>,
+}
+
+impl<T: PartialEq + Eq + Hash> Default for TemporalGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> TemporalGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            events: Vec::new(),
+        }
+    }
+
+    // すべての時刻のエッジをまとめたグラフ
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    pub fn add_node(&mut self, u: T) -> Result<NodeHandle, GraphError> {
+        self.graph.add_node(u)
+    }
+
+    // 時刻 `time` のエッジを追加する
+    pub fn add_edge<Q>(&mut self, u_from: &Q, u_to: &Q, time: u64) -> Result<(), GraphError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph.add_edge(u_from, u_to)?;
+
+        let from = *self
+            .graph
+            .node_id(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
+        let to = *self
+            .graph
+            .node_id(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
+        self.events.push((from, to, time));
+        Ok(())
+    }
+
+    // 時刻が [start, end) のエッジだけからなるグラフ (ノードはそれらのエッジに現れるもの)
+    pub fn snapshot(&self, start: u64, end: u64) -> Graph<&T> {
+        self.snapshot_between(start, Some(end))
+    }
+
+    // `end` が None なら start 以降のすべてのエッジ
+    fn snapshot_between(&self, start: u64, end: Option<u64>) -> Graph<&T> {
+        let nodes = self.graph.nodes_by_id();
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .events
+            .iter()
+            .filter(|&&(_, _, t)| start <= t && end.is_none_or(|end| t < end))
+            .map(|&(a, b, _)| (a, b))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut active: Vec<NodeID> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+        active.sort_unstable();
+        active.dedup();
+
+        let mut g = Graph::new();
+        for id in &active {
+            let _ = g.add_node(nodes[id]);
+        }
+        for (a, b) in edges {
+            let _ = g.add_edge(&nodes[&a], &nodes[&b]);
+        }
+        g
+    }
+
+    // 幅 `window` の窓を `step` ずつずらしながら指標を計算し，時系列として返す
+    // 窓は最初のエッジの時刻から始まり，最後のエッジの時刻を含む窓まで続く
+    pub fn analyze_windows(
+        &self,
+        window: u64,
+        step: u64,
+        metrics: &[WindowMetric],
+    ) -> Vec<WindowStats> {
+        let mut ret = Vec::new();
+        if window == 0 || step == 0 || self.events.is_empty() {
+            return ret;
+        }
+
+        let first = self.events.iter().map(|e| e.2).min().unwrap();
+        let last = self.events.iter().map(|e| e.2).max().unwrap();
+
+        let mut start = first;
+        loop {
+            // 桁あふれする窓は u64::MAX まで (それを含む) とし，そこで終える
+            let end = start.checked_add(window);
+            let g = self.snapshot_between(start, end);
+
+            ret.push(WindowStats {
+                start,
+                end: end.unwrap_or(u64::MAX),
+                node_count: g.node_count(),
+                edge_count: g.edge_count(),
+                components: metrics
+                    .contains(&WindowMetric::Components)
+                    .then(|| g.core.weakly_connected_components().len()),
+                density: metrics
+                    .contains(&WindowMetric::Density)
+                    .then(|| g.density()),
+                cyclic_components: metrics.contains(&WindowMetric::Cycles).then(|| {
+                    g.core
+                        .strongly_connected_components()
+                        .iter()
+                        .filter(|c| g.core.is_nontrivial_component(c, g.ignores_self_loops()))
+                        .count()
+                }),
+            });
+
+            match (end, start.checked_add(step)) {
+                (Some(end), Some(next)) if end <= last => start = next,
+                _ => break,
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TemporalGraph, WindowMetric};
+
+    fn sample() -> TemporalGraph<&'static str> {
+        let mut g = TemporalGraph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B", 0);
+        let _ = g.add_edge(&"B", &"A", 5);
+        let _ = g.add_edge(&"C", &"D", 12);
+        let _ = g.add_edge(&"A", &"B", 15); // 同じエッジが再び現れる
+        g
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let g = sample();
+
+        let s = g.snapshot(0, 10);
+        assert!(s.detect_cycle().is_some());
+        assert_eq!(s.edge_weight(&&"A", &&"B"), Some(1.0));
+        assert!(s.edge_weight(&&"C", &&"D").is_none());
+
+        assert!(g.snapshot(10, 20).detect_cycle().is_none());
+        assert!(g.snapshot(20, 30).detect_cycle().is_none());
+    }
+
+    #[test]
+    fn test_add_edge() {
+        let mut g = sample();
+        assert!(g.add_edge(&"A", &"Z", 1).is_err());
+        assert_eq!(g.graph().edge_weight(&"C", &"D"), Some(1.0));
+    }
+
+    #[test]
+    fn test_analyze_windows() {
+        let g = sample();
+        let stats = g.analyze_windows(
+            10,
+            5,
+            &[
+                WindowMetric::Components,
+                WindowMetric::Density,
+                WindowMetric::Cycles,
+            ],
+        );
+
+        assert_eq!(
+            stats.iter().map(|s| (s.start, s.end)).collect::<Vec<_>>(),
+            vec![(0, 10), (5, 15), (10, 20)]
+        );
+        assert_eq!(stats[0].edge_count, 2);
+        assert_eq!(stats[0].components, Some(1));
+        assert_eq!(stats[0].density, Some(1.0));
+        assert_eq!(stats[0].cyclic_components, Some(1));
+
+        assert_eq!(stats[1].node_count, 4);
+        assert_eq!(stats[1].components, Some(2));
+        assert_eq!(stats[1].cyclic_components, Some(0));
+
+        assert_eq!(stats[2].edge_count, 2);
+        assert_eq!(stats[2].density, Some(2.0 / 12.0));
+
+        let only_density = g.analyze_windows(100, 100, &[WindowMetric::Density]);
+        assert_eq!(only_density.len(), 1);
+        assert_eq!(only_density[0].components, None);
+        assert!(g.analyze_windows(0, 1, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_windows_at_max_time() {
+        let mut g = TemporalGraph::new();
+        for u in ["A", "B", "C"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B", u64::MAX - 15);
+        let _ = g.add_edge(&"B", &"C", u64::MAX);
+
+        let stats = g.analyze_windows(10, 10, &[]);
+        assert_eq!(
+            stats.iter().map(|s| (s.start, s.end)).collect::<Vec<_>>(),
+            vec![(u64::MAX - 15, u64::MAX - 5), (u64::MAX - 5, u64::MAX)]
+        );
+        assert_eq!(stats[1].edge_count, 1);
+        assert_eq!(stats.iter().map(|s| s.edge_count).sum::<usize>(), 2);
+
+        // 窓の終わりがちょうど u64::MAX になるときも u64::MAX の時刻を取りこぼさない
+        let stats = g.analyze_windows(15, 15, &[]);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1].edge_count, 1);
+    }
+}
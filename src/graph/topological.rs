@@ -0,0 +1,151 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // Kahn 法によるトポロジカル順 (入次数 0 のノードは ID の小さい順)．サイクルがあれば None
+    pub(crate) fn topological_order(&self) -> Option<Vec<NodeID>> {
+        let mut in_degree: HashMap<NodeID, usize> =
+            self.nodes_dict.keys().map(|&id| (id, 0)).collect();
+        for n in self.nodes_dict.values() {
+            for c in &n.children {
+                *in_degree.get_mut(c).unwrap() += 1;
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<NodeID>> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&id, _)| Reverse(id))
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes_dict.len());
+        while let Some(Reverse(id)) = ready.pop() {
+            order.push(id);
+            for c in &self.nodes_dict[&id].children {
+                let d = in_degree.get_mut(c).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.push(Reverse(*c));
+                }
+            }
+        }
+
+        (order.len() == self.nodes_dict.len()).then_some(order)
+    }
+
+    // トポロジカル順．maintain_topological_order で更新しているならその順序，
+    // そうでなければ topological_order で求める．サイクルがあれば None
+    pub fn topological_sort(&self) -> Option<Vec<NodeID>> {
+        match &self.topo {
+            Some(topo) => Some(topo.order().to_vec()),
+            None => self.topological_order(),
+        }
+    }
+
+    // 各ノードの層 (湧き出しからの最長距離)．サイクルがあれば None
+    pub fn topological_levels(&self) -> Option<HashMap<NodeID, usize>> {
+        let order = self.topological_order()?;
+        let mut level: HashMap<NodeID, usize> = HashMap::new();
+        for id in order {
+            let l = self.nodes_dict[&id]
+                .parents
+                .iter()
+                .map(|p| level[p] + 1)
+                .max()
+                .unwrap_or(0);
+            level.insert(id, l);
+        }
+        Some(level)
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // すべてのエッジが前から後ろに向かうノードの並び．
+    // maintain_topological_order を呼んであれば更新済みの順序をそのまま返す．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn topological_sort(&self) -> Result<Vec<&T>, Vec<&T>> {
+        match self.core.topological_sort() {
+            Some(ids) => Ok(self.nodes_from_ids(&ids)),
+            None => Err(self.find_cycle().unwrap()),
+        }
+    }
+
+    // DAG のノードを層に分ける．層 i のノードは湧き出しからの最長距離が i で，
+    // エッジは常に前の層から後ろの層に向かう．層の中はノードの追加順．
+    // サイクルがあれば見つけたサイクルをエラーとして返す
+    pub fn topological_levels(&self) -> Result<Vec<Vec<&T>>, Vec<&T>> {
+        let Some(level) = self.core.topological_levels() else {
+            return Err(self.find_cycle().unwrap());
+        };
+
+        let depth = level.values().max().map_or(0, |&l| l + 1);
+        let mut layers: Vec<Vec<NodeID>> = vec![Vec::new(); depth];
+        for (&id, &l) in &level {
+            layers[l].push(id);
+        }
+        Ok(layers
+            .iter_mut()
+            .map(|layer| {
+                layer.sort_unstable();
+                self.nodes_from_ids(layer)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_topological_levels() {
+        // A → B → D,  A → D,  C → D,  E は孤立
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        for (a, b) in [("A", "B"), ("B", "D"), ("A", "D"), ("C", "D")] {
+            let _ = g.add_edge(&a, &b);
+        }
+
+        assert_eq!(
+            g.topological_levels(),
+            Ok(vec![vec![&"A", &"C", &"E"], vec![&"B"], vec![&"D"]])
+        );
+
+        let _ = g.add_edge(&"D", &"A");
+        assert!(g.topological_levels().is_err());
+        assert_eq!(Graph::<u32>::new().topological_levels(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut g = Graph::new();
+        for u in ["D", "C", "B", "A"] {
+            let _ = g.add_node(u);
+        }
+        assert!(g.maintain_topological_order().is_ok());
+
+        let edges = [("A", "B"), ("B", "C"), ("C", "D"), ("A", "C")];
+        for (a, b) in edges {
+            let _ = g.add_edge(&a, &b);
+            let order = g.topological_sort().unwrap();
+            let rank = |u: &str| order.iter().position(|&&x| x == u).unwrap();
+            assert!(g.edges().all(|(x, y)| rank(x) < rank(y)));
+        }
+        assert_eq!(g.topological_sort(), Ok(vec![&"A", &"B", &"C", &"D"]));
+
+        // サイクルができたら更新をやめ，エッジを消せば通常の方法で求める
+        let _ = g.add_edge(&"D", &"B");
+        assert_eq!(g.topological_sort().unwrap_err().len(), 4);
+        let _ = g.remove_edge(&"D", &"B");
+        assert_eq!(g.topological_sort(), Ok(vec![&"A", &"B", &"C", &"D"]));
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashSet, hash::Hash};
+
+use super::{
+    core::{GraphCore, NodeID},
+    graph::Graph,
+};
+
+impl GraphCore {
+    // DAG の推移簡約で残すエッジ．u → v は，u の他の子から v に到達できるなら冗長
+    pub fn transitive_reduction(&self) -> Vec<(NodeID, NodeID)> {
+        let mut ret = Vec::new();
+        for (&u, n) in self.nodes_dict.iter() {
+            // u から長さ 2 以上の経路で到達できるノード
+            let mut indirect: HashSet<NodeID> = HashSet::new();
+            let mut stack: Vec<NodeID> = n
+                .children
+                .iter()
+                .flat_map(|c| self.nodes_dict[c].children.iter().copied())
+                .collect();
+            while let Some(v) = stack.pop() {
+                if indirect.insert(v) {
+                    stack.extend(self.nodes_dict[&v].children.iter().copied());
+                }
+            }
+
+            ret.extend(
+                n.children
+                    .iter()
+                    .filter(|v| !indirect.contains(v))
+                    .map(|&v| (u, v)),
+            );
+        }
+        ret.sort_unstable();
+        ret
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // 到達可能性を保ったまま冗長なエッジを取り除いたグラフ (残したエッジの重みは引き継ぐ)．
+    // サイクルがあると推移簡約は一意に定まらないので，見つけたサイクルをエラーとして返す
+    pub fn transitive_reduction(&self) -> Result<Graph<&T>, Vec<&T>> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(cycle);
+        }
+
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut g = Graph::new();
+        for id in &ids {
+            let _ = g.add_node(nodes[id]);
+        }
+        for (a, b) in self.core.transitive_reduction() {
+            match self.weights.get(&(a, b)) {
+                Some(&w) => g.add_weighted_edge(&nodes[&a], &nodes[&b], w),
+                None => g.add_edge(&nodes[&a], &nodes[&b]),
+            }
+            .unwrap(); // ノードはすべて追加済み
+        }
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    #[test]
+    fn test_transitive_reduction() {
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_weighted_edge(&"B", &"C", 2.0);
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"A", &"C"); // 冗長
+        let _ = g.add_edge(&"A", &"D"); // 冗長
+        let _ = g.add_edge(&"B", &"D"); // 冗長
+
+        let r = g.transitive_reduction().unwrap();
+        assert_eq!(r.edge_weight(&&"A", &&"B"), Some(1.0));
+        assert_eq!(r.edge_weight(&&"B", &&"C"), Some(2.0));
+        assert_eq!(r.edge_weight(&&"C", &&"D"), Some(1.0));
+        assert_eq!(r.edge_weight(&&"A", &&"C"), None);
+        assert_eq!(r.edge_weight(&&"A", &&"D"), None);
+        assert_eq!(r.edge_weight(&&"B", &&"D"), None);
+        assert!(r.reachability_index().can_reach(&&"A", &&"D"));
+    }
+
+    #[test]
+    fn test_transitive_reduction_cycle() {
+        let mut g = Graph::new();
+        let _ = g.add_node("A");
+        let _ = g.add_node("B");
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"B", &"A");
+
+        let cycle = g.transitive_reduction().err().unwrap();
+        assert_eq!(cycle.first(), cycle.last());
+    }
+}
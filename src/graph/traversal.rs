@@ -0,0 +1,357 @@
+use std::{
+    borrow::Borrow,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use super::{
+    core::{GraphCore, NodeID},
+    dijkstra::MinWeighted,
+    graph::Graph,
+    weight::Weight,
+};
+
+/// `Graph::dfs_iter` が返す深さ優先順のイテレータ
+pub struct Dfs<'a, T: PartialEq + Eq + Hash> {
+    graph: &'a Graph<T>,
+    nodes: HashMap<NodeID, &'a T>,
+    visited: HashSet<NodeID>,
+    stack: Vec<NodeID>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> Iterator for Dfs<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.visited.insert(node) {
+                continue;
+            }
+
+            if let Some(n) = self.graph.core.nodes_dict.get(&node) {
+                for &child in &n.children {
+                    if !self.visited.contains(&child) {
+                        self.stack.push(child);
+                    }
+                }
+            }
+
+            return Some(self.nodes[&node]);
+        }
+
+        None
+    }
+}
+
+/// `Graph::bfs_iter` が返す幅優先順のイテレータ
+pub struct Bfs<'a, T: PartialEq + Eq + Hash> {
+    graph: &'a Graph<T>,
+    nodes: HashMap<NodeID, &'a T>,
+    visited: HashSet<NodeID>,
+    queue: VecDeque<NodeID>,
+}
+
+impl<'a, T: PartialEq + Eq + Hash> Iterator for Bfs<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+
+        if let Some(n) = self.graph.core.nodes_dict.get(&node) {
+            for &child in &n.children {
+                // キューに積む時点で訪問済みにする
+                if self.visited.insert(child) {
+                    self.queue.push_back(child);
+                }
+            }
+        }
+
+        Some(self.nodes[&node])
+    }
+}
+
+/// `dfs_forest` / `bfs_forest` が返す探索木の 1 本
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraversalTree<'a, T> {
+    pub root: &'a T,
+    // 訪問した順のノード (先頭は root)
+    pub nodes: Vec<&'a T>,
+    // 木のエッジ (親, 子) を子の訪問順に
+    pub edges: Vec<(&'a T, &'a T)>,
+}
+
+impl GraphCore {
+    // 最良優先探索．訪問済みのノードから出るエッジのうち重みが最小のもの (同じならノード ID の
+    // 小さい方) の先を次に訪問する．累積ではなくエッジ 1 本の重みで選ぶので Prim 法と同じ順になる
+    pub fn traverse_best_first<F, V>(&self, start: NodeID, weight: F, visit: V)
+    where
+        F: Fn(NodeID, NodeID) -> f64,
+        V: FnMut(NodeID),
+    {
+        self.traverse_best_first_by(start, weight, visit)
+    }
+
+    // 重みの型を選べる最良優先探索．重みを足さないので桁あふれは起こらない
+    pub fn traverse_best_first_by<W, F, V>(&self, start: NodeID, weight: F, mut visit: V)
+    where
+        W: Weight,
+        F: Fn(NodeID, NodeID) -> W,
+        V: FnMut(NodeID),
+    {
+        let mut visited = HashSet::new();
+        let mut heap = BinaryHeap::from([MinWeighted(W::zero(), start)]);
+
+        while let Some(MinWeighted(_, node)) = heap.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            visit(node);
+
+            if let Some(n) = self.nodes_dict.get(&node) {
+                for &child in &n.children {
+                    if !visited.contains(&child) {
+                        heap.push(MinWeighted(weight(node, child), child));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // `start` から到達できるノードをエッジの重みの小さい順に広げながらたどり，
+    // 各ノードで `f` を実行する (GraphCore::traverse_best_first)
+    pub fn traverse_best_first<Q, F>(&self, start: &Q, mut f: F)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnMut(&T),
+    {
+        if let Some(&start_id) = self.node_id(start) {
+            let nodes = self.nodes_by_id();
+            self.core.traverse_best_first(
+                start_id,
+                |a, b| self.weight_of(a, b),
+                |id| f(nodes[&id]),
+            );
+        }
+    }
+
+    // 重みを `weight` で与える最良優先探索 (u32 や Duration などの重みも使える)
+    pub fn traverse_best_first_by<Q, W, F, V>(&self, start: &Q, weight: F, mut f: V)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Weight,
+        F: Fn(&T, &T) -> W,
+        V: FnMut(&T),
+    {
+        if let Some(&start_id) = self.node_id(start) {
+            let nodes = self.nodes_by_id();
+            self.core.traverse_best_first_by(
+                start_id,
+                |a, b| weight(nodes[&a], nodes[&b]),
+                |id| f(nodes[&id]),
+            );
+        }
+    }
+
+    // まだ訪問していないノードを根にして探索を繰り返し，すべてのノードを木に分ける．
+    // 根と子はノードの追加順に選ぶ
+    fn forest(&self, depth_first: bool) -> Vec<TraversalTree<'_, T>> {
+        let nodes = self.nodes_by_id();
+        let mut ids: Vec<NodeID> = nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let sorted_children = |id: NodeID| {
+            let mut children = self.core.children_of(id);
+            children.sort_unstable();
+            children
+        };
+
+        let mut visited: HashSet<NodeID> = HashSet::new();
+        let mut forest = Vec::new();
+        for root in ids {
+            if !visited.insert(root) {
+                continue;
+            }
+
+            let mut tree = TraversalTree {
+                root: nodes[&root],
+                nodes: vec![nodes[&root]],
+                edges: Vec::new(),
+            };
+            if depth_first {
+                // 探索中のパス (ノードと，まだ見ていない子)
+                let mut stack = vec![(root, sorted_children(root).into_iter())];
+                while let Some((node, children)) = stack.last_mut() {
+                    let node = *node;
+                    match children.find(|c| !visited.contains(c)) {
+                        Some(child) => {
+                            visited.insert(child);
+                            tree.nodes.push(nodes[&child]);
+                            tree.edges.push((nodes[&node], nodes[&child]));
+                            stack.push((child, sorted_children(child).into_iter()));
+                        }
+                        None => {
+                            stack.pop();
+                        }
+                    }
+                }
+            } else {
+                let mut queue = VecDeque::from([root]);
+                while let Some(node) = queue.pop_front() {
+                    for child in sorted_children(node) {
+                        if visited.insert(child) {
+                            tree.nodes.push(nodes[&child]);
+                            tree.edges.push((nodes[&node], nodes[&child]));
+                            queue.push_back(child);
+                        }
+                    }
+                }
+            }
+            forest.push(tree);
+        }
+
+        forest
+    }
+
+    // グラフ全体の深さ優先探索の森．どの始点からも届かないノードも必ずどれかの木に入る
+    pub fn dfs_forest(&self) -> Vec<TraversalTree<'_, T>> {
+        self.forest(true)
+    }
+
+    // グラフ全体の幅優先探索の森
+    pub fn bfs_forest(&self) -> Vec<TraversalTree<'_, T>> {
+        self.forest(false)
+    }
+
+    // `start` から到達できるノードを深さ優先順に返す．`start` が未登録なら空
+    pub fn dfs_iter<Q>(&self, start: &Q) -> Dfs<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Dfs {
+            graph: self,
+            nodes: self.nodes_by_id(),
+            visited: HashSet::new(),
+            stack: self.node_id(start).copied().into_iter().collect(),
+        }
+    }
+
+    // `start` から到達できるノードを幅優先順に返す．`start` が未登録なら空
+    pub fn bfs_iter<Q>(&self, start: &Q) -> Bfs<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start_id = self.node_id(start).copied();
+
+        Bfs {
+            graph: self,
+            nodes: self.nodes_by_id(),
+            visited: start_id.into_iter().collect(),
+            queue: start_id.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn sample() -> Graph<&'static str> {
+        // A → B → D, A → C → D, D → A
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_edge(&"A", &"B");
+        let _ = g.add_edge(&"A", &"C");
+        let _ = g.add_edge(&"B", &"D");
+        let _ = g.add_edge(&"C", &"D");
+        let _ = g.add_edge(&"D", &"A");
+        g
+    }
+
+    #[test]
+    fn test_dfs_iter() {
+        let g = sample();
+
+        let visited: Vec<_> = g.dfs_iter(&"A").collect();
+        assert_eq!(visited.len(), 4);
+        assert_eq!(visited[0], &"A");
+        assert_eq!(visited[2], &"D"); // B, C のどちらかの次に D
+        assert!(!visited.contains(&&"E"));
+
+        assert_eq!(g.dfs_iter(&"A").take(1).collect::<Vec<_>>(), vec![&"A"]);
+        assert_eq!(g.dfs_iter(&"Z").count(), 0);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let g = sample();
+
+        let visited: Vec<_> = g.bfs_iter(&"A").collect();
+        assert_eq!(visited.len(), 4);
+        assert_eq!(visited[0], &"A");
+        assert_eq!(visited[3], &"D");
+
+        assert_eq!(g.bfs_iter(&"E").collect::<Vec<_>>(), vec![&"E"]);
+        assert_eq!(g.bfs_iter(&"A").find(|&&u| u == "C"), Some(&"C"));
+        assert_eq!(g.bfs_iter(&"Z").count(), 0);
+    }
+
+    #[test]
+    fn test_traverse_best_first() {
+        // A → B (5), A → C (1), C → D (10), B → E (2)
+        let mut g = Graph::new();
+        for u in ["A", "B", "C", "D", "E"] {
+            let _ = g.add_node(u);
+        }
+        let _ = g.add_weighted_edge(&"A", &"B", 5.0);
+        let _ = g.add_weighted_edge(&"A", &"C", 1.0);
+        let _ = g.add_weighted_edge(&"C", &"D", 10.0);
+        let _ = g.add_weighted_edge(&"B", &"E", 2.0);
+        let _ = g.add_weighted_edge(&"E", &"A", 0.0);
+
+        let mut visited = Vec::new();
+        g.traverse_best_first(&"A", |u| visited.push(*u));
+        assert_eq!(visited, vec!["A", "C", "B", "E", "D"]);
+
+        let mut visited = Vec::new();
+        g.traverse_best_first(&"Z", |u| visited.push(*u));
+        assert!(visited.is_empty());
+
+        // 重みを逆順にすると重いエッジから広げる
+        let mut visited = Vec::new();
+        g.traverse_best_first_by(
+            &"A",
+            |a, b| 100 - g.edge_weight(a, b).unwrap() as u32,
+            |u| visited.push(*u),
+        );
+        assert_eq!(visited, vec!["A", "B", "E", "C", "D"]);
+    }
+
+    #[test]
+    fn test_forests() {
+        // A → B → D, A → C → D, D → A と F → E (F にはどこからも届かない)
+        let mut g = sample();
+        let _ = g.add_node("F");
+        let _ = g.add_edge(&"F", &"E");
+
+        let dfs = g.dfs_forest();
+        assert_eq!(dfs.len(), 3);
+        assert_eq!(dfs[0].nodes, vec![&"A", &"B", &"D", &"C"]);
+        assert_eq!(dfs[0].edges, vec![(&"A", &"B"), (&"B", &"D"), (&"A", &"C")]);
+        assert_eq!(dfs[1].root, &"E");
+        assert!(dfs[1].edges.is_empty());
+        assert_eq!(dfs[2].nodes, vec![&"F"]);
+
+        let bfs = g.bfs_forest();
+        assert_eq!(bfs[0].nodes, vec![&"A", &"B", &"C", &"D"]);
+        assert_eq!(bfs[0].edges, vec![(&"A", &"B"), (&"A", &"C"), (&"B", &"D")]);
+        assert_eq!(bfs.iter().map(|t| t.nodes.len()).sum::<usize>(), 6);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{cmp::Ordering, fmt::Debug, time::Duration};
+
+/// 経路の長さなどを足し合わせるアルゴリズムで使える重みの型
+///
+/// 整数と `Duration` は `checked_add` が桁あふれで None を返すので，長い経路で
+/// 値が巻き戻る代わりにエラーにできる．浮動小数点数は IEEE の規則どおり足す (inf になりうる)
+pub trait Weight: Copy + Debug {
+    fn zero() -> Self;
+
+    // これ以下の差は 0 とみなす (浮動小数点数の丸め誤差を吸収する)．整数と Duration は 0
+    fn epsilon() -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    // 最大流の残余容量などで使う．符号なし整数と Duration は負になると None
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn saturating_add(self, other: Self) -> Self;
+
+    // 全順序での比較 (浮動小数点数は total_cmp)
+    fn cmp_weight(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_weight_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn epsilon() -> Self {
+                    0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$t>::saturating_add(self, other)
+                }
+
+                fn cmp_weight(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_weight_for_float {
+    ($($t:ty: $eps:expr),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn epsilon() -> Self {
+                    $eps
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    self + other
+                }
+
+                fn cmp_weight(&self, other: &Self) -> Ordering {
+                    self.total_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_float!(f32: 1e-6, f64: 1e-9);
+
+impl Weight for Duration {
+    fn zero() -> Self {
+        Duration::ZERO
+    }
+
+    fn epsilon() -> Self {
+        Duration::ZERO
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Duration::checked_add(self, other)
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        Duration::checked_sub(self, other)
+    }
+
+    fn saturating_add(self, other: Self) -> Self {
+        Duration::saturating_add(self, other)
+    }
+
+    fn cmp_weight(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, time::Duration};
+
+    use super::Weight;
+
+    #[test]
+    fn test_weight() {
+        assert_eq!(Weight::checked_add(u32::MAX, 1), None);
+        assert_eq!(Weight::saturating_add(u32::MAX, 1), u32::MAX);
+        assert_eq!(Weight::checked_add(f64::MAX, f64::MAX), Some(f64::INFINITY));
+        assert_eq!(
+            Weight::checked_add(Duration::from_secs(1), Duration::from_millis(500)),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(Duration::MAX.checked_add(Duration::from_secs(1)), None);
+        assert_eq!(Weight::checked_sub(1u32, 2), None);
+        assert_eq!(Weight::checked_sub(1i32, 2), Some(-1));
+        assert_eq!(Weight::checked_sub(1.0, 2.0), Some(-1.0));
+        assert_eq!(f64::NAN.cmp_weight(&1.0), Ordering::Greater);
+        assert_eq!(<i64 as Weight>::zero(), 0);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{core::NodeID, graph::Graph};
+
+// ラベルを (自分のラベル, 子のラベルの多重集合, 親のラベルの多重集合) から作り直す際の辞書．
+// 2 つのグラフで共有して同じ構造に同じラベルが付くようにする
+type WlDictionary = HashMap<(usize, Vec<usize>, Vec<usize>), usize>;
+
+impl<T: PartialEq + Eq + Hash> Graph<T> {
+    // WL の部分木特徴 (各反復で現れたラベルの個数)
+    fn wl_features(&self, iterations: usize, dict: &mut WlDictionary) -> HashMap<usize, usize> {
+        // 初期ラベルはすべて同じ (構造だけを比べる)
+        let mut labels: HashMap<NodeID, usize> =
+            self.core.nodes_dict.keys().map(|&id| (id, 0)).collect();
+        let mut features: HashMap<usize, usize> = HashMap::new();
+        for &l in labels.values() {
+            *features.entry(l).or_default() += 1;
+        }
+
+        for _ in 0..iterations {
+            let next: HashMap<NodeID, usize> = self
+                .core
+                .nodes_dict
+                .iter()
+                .map(|(&id, n)| {
+                    let mut out: Vec<usize> = n.children.iter().map(|c| labels[c]).collect();
+                    out.sort_unstable();
+                    let mut inc: Vec<usize> = n.parents.iter().map(|p| labels[p]).collect();
+                    inc.sort_unstable();
+
+                    // 0 は初期ラベルなので 1 から振る
+                    let fresh = dict.len() + 1;
+                    (id, *dict.entry((labels[&id], out, inc)).or_insert(fresh))
+                })
+                .collect();
+
+            labels = next;
+            for &l in labels.values() {
+                *features.entry(l).or_default() += 1;
+            }
+        }
+        features
+    }
+
+    // Weisfeiler–Lehman 部分木カーネルによる構造の類似度 (正規化して 0 〜 1)．
+    // ノードの値は使わず，エッジの向きは区別する．どちらも空のグラフなら 1
+    pub fn wl_similarity(&self, other: &Graph<T>, iterations: usize) -> f64 {
+        let mut dict = WlDictionary::new();
+        let a = self.wl_features(iterations, &mut dict);
+        let b = other.wl_features(iterations, &mut dict);
+
+        let dot = |x: &HashMap<usize, usize>, y: &HashMap<usize, usize>| -> f64 {
+            x.iter()
+                .filter_map(|(l, &c)| y.get(l).map(|&d| (c * d) as f64))
+                .sum()
+        };
+
+        let (aa, bb) = (dot(&a, &a), dot(&b, &b));
+        if aa == 0.0 && bb == 0.0 {
+            return 1.0;
+        }
+        if aa == 0.0 || bb == 0.0 {
+            return 0.0;
+        }
+        dot(&a, &b) / (aa * bb).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+
+    fn cycle(names: &[u32]) -> Graph<u32> {
+        let mut g = Graph::new();
+        for &u in names {
+            let _ = g.add_node(u);
+        }
+        for (i, u) in names.iter().enumerate() {
+            let _ = g.add_edge(u, &names[(i + 1) % names.len()]);
+        }
+        g
+    }
+
+    #[test]
+    fn test_wl_similarity_isomorphic() {
+        // ノードの値が違っても構造が同じなら 1
+        let g = cycle(&[1, 2, 3, 4]);
+        let h = cycle(&[10, 20, 30, 40]);
+        assert!((g.wl_similarity(&h, 3) - 1.0).abs() < 1e-9);
+        assert_eq!(Graph::<u32>::new().wl_similarity(&Graph::new(), 2), 1.0);
+    }
+
+    #[test]
+    fn test_wl_similarity_different() {
+        let g = cycle(&[1, 2, 3, 4]);
+        let mut path = cycle(&[1, 2, 3, 4]);
+        let _ = path.remove_edge(&4, &1);
+
+        let s = g.wl_similarity(&path, 2);
+        assert!(s > 0.0 && s < 1.0);
+        // 反復を増やすほど違いがはっきりする
+        assert!(g.wl_similarity(&path, 3) <= s);
+        assert_eq!(g.wl_similarity(&Graph::new(), 2), 0.0);
+    }
+}
@@ -1,8 +1,5 @@
-use graph::graph::Graph;
-use usize_graph::graph::UsizeGraph;
-
-mod graph;
-mod usize_graph;
+use graph_analyses::graph::graph::Graph;
+use graph_analyses::usize_graph::graph::UsizeGraph;
 
 fn main() {
     {
@@ -31,8 +28,8 @@ fn main() {
         let node1 = "ndoe1";
         let node2 = "node2";
 
-        let _ = g.add_node(&node1);
-        let _ = g.add_node(&node2);
+        let _ = g.add_node(node1);
+        let _ = g.add_node(node2);
 
         let _ = g.add_edge(&node1, &node2);
         let _ = g.add_edge(&node2, &node1);
@@ -42,6 +39,8 @@ fn main() {
         for c in cycle {
             println!("{}", c);
         }
+
+        println!("{}", g.scc_report().to_json());
     }
 
     {
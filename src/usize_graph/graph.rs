@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use crate::graph::core::{GraphCore, NodeID};
+use crate::graph::{
+    core::{GraphCore, NodeID},
+    error::GraphError,
+    handle::NodeHandle,
+};
 
 pub struct UsizeGraph {
     id_counter: usize,
@@ -8,6 +12,12 @@ pub struct UsizeGraph {
     core: GraphCore,
 }
 
+impl Default for UsizeGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UsizeGraph {
     pub fn new() -> Self {
         Self {
@@ -33,9 +43,12 @@ impl UsizeGraph {
     }
 
     // 使用するノードを登録する
-    pub fn add_node(&mut self, u: usize) -> Result<(), String> {
-        if self.usize_id_dict.contains_key(&u) {
-            return Err(format!("node {} is already added", u));
+    pub fn add_node(&mut self, u: usize) -> Result<(), GraphError> {
+        if let Some(&node) = self.usize_id_dict.get(&u) {
+            // ノードを削除しないので世代は常に 0
+            return Err(GraphError::NodeAlreadyExists {
+                node: NodeHandle::new(node, 0),
+            });
         }
 
         let new_id = self.id_counter;
@@ -46,15 +59,15 @@ impl UsizeGraph {
     }
 
     // すでにエッジが登録されている場合 false が返される (ただし，複数のエッジとして登録はされる)
-    pub fn add_edge(&mut self, u_from: &usize, u_to: &usize) -> Result<bool, String> {
+    pub fn add_edge(&mut self, u_from: &usize, u_to: &usize) -> Result<bool, GraphError> {
         let from_id = *self
             .usize_id_dict
-            .get(&u_from)
-            .ok_or(format!("node {} is not added", u_from))?;
+            .get(u_from)
+            .ok_or(GraphError::NodeNotFound { arg: "from" })?;
         let to_id = *self
             .usize_id_dict
-            .get(&u_to)
-            .ok_or(format!("node {} is not added", u_to))?;
+            .get(u_to)
+            .ok_or(GraphError::NodeNotFound { arg: "to" })?;
 
         self.core.add_edge(from_id, to_id)
     }
@@ -65,6 +78,7 @@ impl UsizeGraph {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::UsizeGraph;
 